@@ -1,29 +1,168 @@
 //! Generate ADRs badge.
+//!
+//! Architecture decision records are looked up in `docs/adr` next to the
+//! package manifest, then in `docs/adr` at the workspace root (where
+//! workspaces usually keep them). The badge links to `docs/adr/index.typ`,
+//! relative to whichever directory contains the ADRs.
+//!
+//! The badge shows the number of ADRs: files named `NNNN-title.md` or
+//! `NNNN-title.typ` (a numeric prefix and a dash). An existing but empty
+//! ADR directory still produces a badge, `ADRs-0-lightgrey`; without the
+//! directory there is no badge.
 
-use std::io::Write;
+use std::path::{
+    Path,
+    PathBuf,
+};
 
-use anyhow::Result;
+use anyhow::{
+    Context,
+    Result,
+};
+
+use super::common::{
+    self,
+    Badge,
+    SkipReason,
+};
+use crate::commands::common::CargoOptions;
+
+/// ADR directory, relative to the package or workspace root.
+const ADR_DIR: &str = "docs/adr";
 
 /// Show the ADRs badge.
-pub async fn badge_adrs(writer: &mut dyn Write, package: &cargo_metadata::Package) -> Result<()> {
+pub async fn badge_adrs(
+    package: &cargo_metadata::Package,
+    cargo: CargoOptions,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
     logger.status("Generating", "ADRs badge");
 
-    let manifest_dir = package
-        .manifest_path
-        .as_std_path()
-        .parent()
-        .unwrap_or_else(|| std::path::Path::new("."));
+    let manifest_path = package.manifest_path.as_std_path().to_path_buf();
+    let adr_dir = tokio::task::spawn_blocking(move || {
+        let manifest_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+        // A workspace root that cannot be determined just means no fallback
+        let workspace_root = cargo
+            .metadata_command()
+            .manifest_path(&manifest_path)
+            .no_deps()
+            .exec()
+            .ok()
+            .map(|metadata| metadata.workspace_root.into_std_path_buf());
+        find_adr_dir(manifest_dir, workspace_root.as_deref())
+    })
+    .await
+    .context("Failed to spawn blocking task")?;
+
+    if let Some(adr_dir) = adr_dir {
+        let count = count_adrs(&adr_dir).await?;
+        let color = if count == 0 {
+            "lightgrey"
+        } else {
+            "informational"
+        };
+        let badge_url = common::badge_url(base_url, &format!("badge/ADRs-{}-{}", count, color));
+        return Ok(Ok(Badge::new(
+            "adrs",
+            "ADRs",
+            badge_url,
+            format!("{}/index.typ", ADR_DIR),
+        )));
+    }
+
+    Ok(Err(SkipReason::new(format!(
+        "{} does not exist in the package or the workspace root",
+        ADR_DIR
+    ))))
+}
+
+/// Count the ADR files in `adr_dir`.
+async fn count_adrs(adr_dir: &Path) -> Result<usize> {
+    let mut entries = tokio::fs::read_dir(adr_dir)
+        .await
+        .with_context(|| format!("Failed to read {}", adr_dir.display()))?;
+
+    let mut count = 0;
+    while let Some(entry) = entries.next_entry().await? {
+        if is_adr_file_name(&entry.file_name().to_string_lossy()) {
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+/// Whether a file name looks like an ADR: `NNNN-title.md` or
+/// `NNNN-title.typ`.
+fn is_adr_file_name(name: &str) -> bool {
+    let Some(stem) = name
+        .strip_suffix(".md")
+        .or_else(|| name.strip_suffix(".typ"))
+    else {
+        return false;
+    };
+    let Some((number, title)) = stem.split_once('-') else {
+        return false;
+    };
+
+    !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()) && !title.is_empty()
+}
+
+/// Find the ADR directory, preferring the package's own over the
+/// workspace root's.
+fn find_adr_dir(manifest_dir: &Path, workspace_root: Option<&Path>) -> Option<PathBuf> {
+    std::iter::once(manifest_dir)
+        .chain(workspace_root)
+        .map(|dir| dir.join(ADR_DIR))
+        .find(|dir| dir.is_dir())
+}
 
-    // Check if docs/adr/ directory exists
-    let adr_dir = manifest_dir.join("docs/adr");
-    let has_adrs = tokio::fs::metadata(&adr_dir).await.is_ok();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if has_adrs {
-        let badge_url = "https://img.shields.io/badge/ADRs-index-informational";
-        let badge_markdown = format!("[![ADRs]({})](docs/adr/index.typ)", badge_url);
-        writeln!(writer, "{}", badge_markdown)?;
+    #[test]
+    fn test_is_adr_file_name() {
+        assert!(is_adr_file_name("0001-use-gix.md"));
+        assert!(is_adr_file_name("12-record-decisions.typ"));
+        assert!(!is_adr_file_name("index.typ"));
+        assert!(!is_adr_file_name("README.md"));
+        assert!(!is_adr_file_name("0001-.md"));
+        assert!(!is_adr_file_name("0001-use-gix.txt"));
+        assert!(!is_adr_file_name("draft-0001.md"));
     }
 
-    Ok(())
+    #[tokio::test]
+    async fn test_count_adrs() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(count_adrs(dir.path()).await.unwrap(), 0);
+
+        for name in ["0001-a.md", "0002-b.typ", "index.typ", "template.md"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        assert_eq!(count_adrs(dir.path()).await.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_find_adr_dir() {
+        let workspace = tempfile::tempdir().unwrap();
+        let member = workspace.path().join("crates/member");
+        std::fs::create_dir_all(&member).unwrap();
+        assert_eq!(find_adr_dir(&member, Some(workspace.path())), None);
+
+        // Workspace-level ADRs are found for members
+        std::fs::create_dir_all(workspace.path().join(ADR_DIR)).unwrap();
+        assert_eq!(
+            find_adr_dir(&member, Some(workspace.path())),
+            Some(workspace.path().join(ADR_DIR))
+        );
+
+        // The package's own ADRs win
+        std::fs::create_dir_all(member.join(ADR_DIR)).unwrap();
+        assert_eq!(
+            find_adr_dir(&member, Some(workspace.path())),
+            Some(member.join(ADR_DIR))
+        );
+    }
 }
@@ -1,38 +1,614 @@
 //! Generate all badges.
 
 use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::Result;
 
+use super::common::{
+    self,
+    Badge,
+    BadgeFormat,
+    CacheOptions,
+    SkipReason,
+};
+use super::coverage::CoverageTool;
 use super::{
     adrs,
+    binary_size,
     coverage,
     crates_io,
+    database,
+    docs_coverage,
     docs_rs,
+    features,
     framework,
     license,
+    lines_of_code,
+    maintenance,
     number_of_tests,
     platform,
     runtime,
     rust_edition,
+    semver,
+    serialization,
 };
+use crate::commands::common::CargoOptions;
 
-/// Generate all badges
-pub async fn badge_all(
+/// Names of all badges, in the order `badge all` emits them.
+///
+/// These are the values of [`Badge::name`].
+pub const BADGE_NAMES: &[&str] = &[
+    "rustdocs",
+    "cratesio",
+    "license",
+    "maintenance",
+    "rust-edition",
+    "features",
+    "runtime",
+    "framework",
+    "database",
+    "platform",
+    "adrs",
+    "coverage",
+    "docs-coverage",
+    "lines-of-code",
+    "number-of-tests",
+    "binary-size",
+    "semver",
+    "serialization",
+];
+
+/// Badges that are too expensive (or too niche) to generate by default.
+///
+/// `binary-size` and `docs-coverage` need a build (the latter on a nightly
+/// toolchain that may not be installed), `semver` a registry download.
+///
+/// `badge all` only produces these when they are listed in the package's
+/// badge allowlist.
+pub const OPT_IN_BADGES: &[&str] = &["binary-size", "docs-coverage", "semver", "serialization"];
+
+/// Options controlling which badges are generated and how.
+#[derive(Debug, Clone, Default)]
+pub struct BadgeOptions {
+    /// Skip network requests and use heuristics to guess if the crate is
+    /// published.
+    pub no_network: bool,
+    /// Only emit the license badge for a valid SPDX expression.
+    pub spdx_validate: bool,
+    /// Fail instead of warning when a subprocess badge (such as the test
+    /// count) cannot be computed.
+    pub verbose: bool,
+    /// Badge image host, replacing `https://img.shields.io`.
+    ///
+    /// When `None`, `badge-base-url` from `[package.metadata.version-info]`
+    /// is used, falling back to shields.io.
+    pub badge_base_url: Option<String>,
+    /// Base URL prefixed to repo-relative badge links (e.g.
+    /// `https://github.com/owner/repo/blob/main`). When `None`, links stay
+    /// relative.
+    pub link_base: Option<String>,
+    /// Languages counted by the lines of code badge (tokei names). Empty
+    /// means Rust only.
+    pub languages: Vec<String>,
+    /// Tool measuring test coverage for the coverage badge.
+    pub coverage_tool: CoverageTool,
+    /// Pre-generated coverage report (`lcov.info` or Cobertura XML) read
+    /// instead of running [`BadgeOptions::coverage_tool`].
+    pub coverage_file: Option<PathBuf>,
+    /// Add doctests to the number-of-tests badge.
+    pub include_doctests: bool,
+    /// Only generate these badges (names as accepted by
+    /// [`normalize_badge_name`]), overriding the package's allowlist. Empty
+    /// means no restriction.
+    pub only: Vec<String>,
+    /// Show the crates.io badge even if every published version is yanked.
+    pub include_yanked: bool,
+    /// Alternative registry checked instead of crates.io: a crates.io-style
+    /// API base URL or a `sparse+` index URL. `None` means crates.io.
+    pub registry_api: Option<String>,
+    /// How cached badge results are read (`--cache-ttl`, `--no-cache`).
+    pub cache: CacheOptions,
+    /// How cargo subprocesses may access the network (`--offline`,
+    /// `--frozen`).
+    pub cargo: CargoOptions,
+    /// Timeout of each crates.io/docs.rs request (`--timeout`). `None` means
+    /// [`HTTP_TIMEOUT`](super::common::HTTP_TIMEOUT).
+    pub timeout: Option<Duration>,
+}
+
+/// Generate all badges for a package.
+///
+/// This is the stable library entry point for badge generation. Badges are
+/// returned in display order; badges that do not apply to the package (for
+/// example crates.io for an unpublished crate) are omitted. Use
+/// [`evaluate_badges`] to find out why.
+///
+/// If the package declares an allowlist in Cargo.toml, only those badges are
+/// generated:
+///
+/// ```toml
+/// [package.metadata.version-info]
+/// badges = ["crates_io", "license", "coverage"]
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use cargo_version_info::commands::{
+///     BadgeOptions,
+///     find_package,
+///     generate_badges,
+/// };
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let options = BadgeOptions::default();
+/// let package = find_package(options.cargo).await?;
+/// let badges = generate_badges(&package, &options).await?;
+/// for badge in &badges {
+///     println!("{}: {}", badge.name, badge.image_url);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn generate_badges(
+    package: &cargo_metadata::Package,
+    options: &BadgeOptions,
+) -> Result<Vec<Badge>> {
+    let outcomes = evaluate_badges(package, options).await?;
+    Ok(emitted_badges(&outcomes, options.link_base.as_deref()))
+}
+
+/// What a badge generator produced.
+#[derive(Debug, Clone)]
+pub struct BadgeOutcome {
+    /// The [`BADGE_NAMES`] entry of the generator.
+    pub name: &'static str,
+    /// The generated badges (several for `database`), or why the badge was
+    /// skipped.
+    pub result: Result<Vec<Badge>, SkipReason>,
+}
+
+impl BadgeOutcome {
+    /// Outcome of a generator producing at most one badge.
+    pub fn single(name: &'static str, result: Result<Badge, SkipReason>) -> Self {
+        Self {
+            name,
+            result: result.map(|badge| vec![badge]),
+        }
+    }
+}
+
+/// Run the generator of every wanted badge (see [`wanted_badges`]).
+///
+/// Outcomes are in display order. Links are left as the generators return
+/// them; [`emitted_badges`] resolves them.
+///
+/// # Errors
+///
+/// Returns an error if `options.only` or the allowlist is invalid, or a
+/// generator fails in a way that should abort the command.
+pub async fn evaluate_badges(
+    package: &cargo_metadata::Package,
+    options: &BadgeOptions,
+) -> Result<Vec<BadgeOutcome>> {
+    let base_url = common::badge_base_url(package, options.badge_base_url.as_deref());
+
+    let mut outcomes = Vec::new();
+    for name in wanted_badges(package, &options.only)? {
+        let outcome = match name {
+            "rustdocs" => BadgeOutcome::single(
+                name,
+                docs_rs::badge_rustdocs(
+                    package,
+                    options.no_network,
+                    options.timeout,
+                    options.cache,
+                    &base_url,
+                )
+                .await?,
+            ),
+            "cratesio" => BadgeOutcome::single(
+                name,
+                crates_io::badge_cratesio(
+                    package,
+                    options.no_network,
+                    options.include_yanked,
+                    options.registry_api.as_deref(),
+                    options.timeout,
+                    options.cache,
+                    &base_url,
+                )
+                .await?,
+            ),
+            "license" => BadgeOutcome::single(
+                name,
+                license::badge_license(package, options.spdx_validate, &base_url).await?,
+            ),
+            "maintenance" => BadgeOutcome::single(
+                name,
+                maintenance::badge_maintenance(package, &base_url).await?,
+            ),
+            "rust-edition" => BadgeOutcome::single(
+                name,
+                rust_edition::badge_rust_edition(package, &base_url).await?,
+            ),
+            "features" => {
+                BadgeOutcome::single(name, features::badge_features(package, &base_url).await?)
+            }
+            "runtime" => {
+                BadgeOutcome::single(name, runtime::badge_runtime(package, &base_url).await?)
+            }
+            "framework" => {
+                BadgeOutcome::single(name, framework::badge_framework(package, &base_url).await?)
+            }
+            "database" => BadgeOutcome {
+                name,
+                result: database::badge_database(package, &base_url).await?,
+            },
+            "platform" => {
+                BadgeOutcome::single(name, platform::badge_platform(package, &base_url).await?)
+            }
+            "adrs" => BadgeOutcome::single(
+                name,
+                adrs::badge_adrs(package, options.cargo, &base_url).await?,
+            ),
+            "coverage" => BadgeOutcome::single(
+                name,
+                coverage::badge_coverage(
+                    package,
+                    options.coverage_tool,
+                    options.coverage_file.as_deref(),
+                    options.cargo,
+                    options.cache,
+                    &base_url,
+                )
+                .await?,
+            ),
+            "docs-coverage" => BadgeOutcome::single(
+                name,
+                docs_coverage::badge_docs_coverage(
+                    package,
+                    options.cargo,
+                    options.cache,
+                    &base_url,
+                )
+                .await?,
+            ),
+            "lines-of-code" => BadgeOutcome::single(
+                name,
+                lines_of_code::badge_lines_of_code(
+                    package,
+                    &options.languages,
+                    options.cache,
+                    &base_url,
+                )
+                .await?,
+            ),
+            "number-of-tests" => BadgeOutcome::single(
+                name,
+                number_of_tests::badge_number_of_tests(
+                    package,
+                    options.verbose,
+                    options.include_doctests,
+                    options.cargo,
+                    options.cache,
+                    &base_url,
+                )
+                .await?,
+            ),
+            "binary-size" => BadgeOutcome::single(
+                name,
+                binary_size::badge_binary_size(package, options.cargo, options.cache, &base_url)
+                    .await?,
+            ),
+            "semver" => BadgeOutcome::single(
+                name,
+                semver::badge_semver(
+                    package,
+                    options.no_network,
+                    options.timeout,
+                    options.cargo,
+                    options.cache,
+                    &base_url,
+                )
+                .await?,
+            ),
+            "serialization" => BadgeOutcome::single(
+                name,
+                serialization::badge_serialization(package, &base_url).await?,
+            ),
+            _ => anyhow::bail!("No generator for badge {}", name),
+        };
+        outcomes.push(outcome);
+    }
+
+    Ok(outcomes)
+}
+
+/// The badges that were generated, with links resolved against
+/// `link_base` (see [`common::resolve_link`]).
+pub fn emitted_badges(outcomes: &[BadgeOutcome], link_base: Option<&str>) -> Vec<Badge> {
+    outcomes
+        .iter()
+        .filter_map(|outcome| outcome.result.as_ref().ok())
+        .flatten()
+        .map(|badge| Badge {
+            link: common::resolve_link(link_base, &badge.link),
+            ..badge.clone()
+        })
+        .collect()
+}
+
+/// Names of the badges `badge all` should generate for a package.
+///
+/// This is [`BADGE_NAMES`], restricted to `only` when it is not empty, or
+/// else to the package's allowlist if it declares one. Without either,
+/// [`OPT_IN_BADGES`] are left out.
+///
+/// # Errors
+///
+/// Returns an error if `only` contains an unknown badge name or the
+/// allowlist is malformed.
+pub fn wanted_badges(
+    package: &cargo_metadata::Package,
+    only: &[String],
+) -> Result<Vec<&'static str>> {
+    let allowlist = if only.is_empty() {
+        badge_allowlist(package)?
+    } else {
+        let mut names = Vec::new();
+        for name in only {
+            let Some(known) = normalize_badge_name(name) else {
+                anyhow::bail!(
+                    "Unknown badge: {} (expected one of {})",
+                    name,
+                    BADGE_NAMES.join(", ")
+                );
+            };
+            names.push(known.to_string());
+        }
+        Some(names)
+    };
+    Ok(BADGE_NAMES
+        .iter()
+        .copied()
+        .filter(|name| match &allowlist {
+            Some(names) => names.iter().any(|n| n == name),
+            None => !OPT_IN_BADGES.contains(name),
+        })
+        .collect())
+}
+
+/// Write whether each badge was emitted by `badge all`, and why not, for
+/// `badge all --explain`.
+///
+/// `outcomes` are the [`evaluate_badges`] results for `only`; a skipped
+/// badge is reported with the reason its generator gave. Badges without an
+/// outcome were not wanted and are reported as not selected (by `--only`,
+/// the allowlist, or because they are opt-in).
+///
+/// # Errors
+///
+/// Returns an error if the allowlist is invalid or writing fails.
+pub fn write_explanation(
     writer: &mut dyn Write,
     package: &cargo_metadata::Package,
-    no_network: bool,
+    only: &[String],
+    outcomes: &[BadgeOutcome],
 ) -> Result<()> {
-    docs_rs::badge_rustdocs(writer, package, no_network).await?;
-    crates_io::badge_cratesio(writer, package, no_network).await?;
-    license::badge_license(writer, package).await?;
-    rust_edition::badge_rust_edition(writer, package).await?;
-    runtime::badge_runtime(writer, package).await?;
-    framework::badge_framework(writer, package).await?;
-    platform::badge_platform(writer, package).await?;
-    adrs::badge_adrs(writer, package).await?;
-    coverage::badge_coverage(writer, package).await?;
-    number_of_tests::badge_number_of_tests(writer, package).await?;
+    let has_allowlist = only.is_empty() && badge_allowlist(package)?.is_some();
 
+    for name in BADGE_NAMES {
+        let outcome = outcomes.iter().find(|outcome| outcome.name == *name);
+        let status = match outcome.map(|outcome| &outcome.result) {
+            Some(Ok(_)) => "emitted".to_string(),
+            Some(Err(reason)) => format!("skipped, {}", reason),
+            None if !only.is_empty() => "not selected, not listed in --only".to_string(),
+            None if has_allowlist => {
+                "not selected, not in the badges allowlist in Cargo.toml".to_string()
+            }
+            None => "not selected, opt-in (list it in `badges` under \
+                     [package.metadata.version-info])"
+                .to_string(),
+        };
+        writeln!(writer, "{}: {}", name, status)?;
+    }
     Ok(())
 }
+
+/// Read the badge allowlist from `[package.metadata.version-info] badges`.
+///
+/// Returns `None` when no allowlist is declared (all badges are wanted).
+/// Names are normalized with [`normalize_badge_name`]; unknown names are
+/// skipped with a warning.
+fn badge_allowlist(package: &cargo_metadata::Package) -> Result<Option<Vec<String>>> {
+    let Some(value) = common::version_info_setting(&package.metadata, "badges") else {
+        return Ok(None);
+    };
+    let Some(entries) = value.as_array() else {
+        anyhow::bail!("package.metadata.version-info.badges must be an array of badge names");
+    };
+
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+    let mut names = Vec::new();
+    for entry in entries {
+        let Some(entry) = entry.as_str() else {
+            anyhow::bail!("package.metadata.version-info.badges must be an array of badge names");
+        };
+        match normalize_badge_name(entry) {
+            Some(name) => names.push(name.to_string()),
+            None => logger.warning(
+                "Ignoring",
+                &format!(
+                    "unknown badge '{}' in package.metadata.version-info.badges",
+                    entry
+                ),
+            ),
+        }
+    }
+
+    Ok(Some(names))
+}
+
+/// Map a user-supplied badge name to its canonical [`BADGE_NAMES`] entry.
+///
+/// Matching is case-insensitive, treats `_` like `-`, and accepts the module
+/// style spellings (`crates_io`, `docs_rs`).
+pub fn normalize_badge_name(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_lowercase().replace('_', "-");
+    let name = match name.as_str() {
+        "crates-io" => "cratesio",
+        "docs-rs" | "docsrs" => "rustdocs",
+        "tests" => "number-of-tests",
+        "sloc" | "loc" => "lines-of-code",
+        "semver-checks" => "semver",
+        other => other,
+    };
+
+    BADGE_NAMES.iter().copied().find(|known| *known == name)
+}
+
+/// Generate all badges and write them in the requested format.
+///
+/// `options` is passed through to [`generate_badges`] unchanged.
+pub async fn badge_all(
+    writer: &mut dyn Write,
+    package: &cargo_metadata::Package,
+    options: &BadgeOptions,
+    format: BadgeFormat,
+) -> Result<()> {
+    let badges = generate_badges(package, options).await?;
+    common::render_badges(writer, &badges, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_badge_name() {
+        assert_eq!(normalize_badge_name("crates_io"), Some("cratesio"));
+        assert_eq!(normalize_badge_name("docs_rs"), Some("rustdocs"));
+        assert_eq!(normalize_badge_name("License"), Some("license"));
+        assert_eq!(normalize_badge_name("rust_edition"), Some("rust-edition"));
+        assert_eq!(normalize_badge_name("ADRs"), Some("adrs"));
+        assert_eq!(normalize_badge_name("sparkles"), None);
+    }
+
+    #[test]
+    fn test_badge_names_match_generated_names() {
+        for name in BADGE_NAMES {
+            assert_eq!(normalize_badge_name(name), Some(*name));
+        }
+    }
+
+    #[test]
+    fn test_write_explanation() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            r#"[package]
+name = "test"
+version = "0.1.0"
+license = "MIT"
+
+[package.metadata.version-info]
+badges = ["license", "features"]
+"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src").join("lib.rs"), "").unwrap();
+        let package = crate::commands::common::find_workspace_member(
+            "test",
+            Some(&manifest),
+            CargoOptions::default(),
+        )
+        .unwrap();
+        let outcomes = [
+            BadgeOutcome::single(
+                "license",
+                Ok(Badge::new(
+                    "license",
+                    "License",
+                    "https://img.shields.io/badge/license-MIT-blue",
+                    "Cargo.toml",
+                )),
+            ),
+            BadgeOutcome::single("features", Err(SkipReason::new("no features declared"))),
+        ];
+
+        let mut output = Vec::new();
+        write_explanation(&mut output, &package, &[], &outcomes).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), BADGE_NAMES.len());
+        assert!(output.contains("license: emitted\n"), "{}", output);
+        assert!(
+            output.contains("features: skipped, no features declared\n"),
+            "{}",
+            output
+        );
+        assert!(output.contains("rustdocs: not selected, not in the badges allowlist"));
+
+        let mut output = Vec::new();
+        write_explanation(&mut output, &package, &["features".to_string()], &[]).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("license: not selected, not listed in --only"));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_badges_skip_reasons() {
+        let options = BadgeOptions {
+            spdx_validate: true,
+            only: vec!["license".to_string()],
+            ..BadgeOptions::default()
+        };
+
+        let mut explanations = Vec::new();
+        for license in ["", "license = \"Not a license\"\n"] {
+            let dir = tempfile::tempdir().unwrap();
+            let manifest = dir.path().join("Cargo.toml");
+            std::fs::write(
+                &manifest,
+                format!(
+                    "[package]\nname = \"test\"\nversion = \"0.1.0\"\n{}",
+                    license
+                ),
+            )
+            .unwrap();
+            std::fs::create_dir_all(dir.path().join("src")).unwrap();
+            std::fs::write(dir.path().join("src").join("lib.rs"), "").unwrap();
+            let package = crate::commands::common::find_workspace_member(
+                "test",
+                Some(&manifest),
+                CargoOptions::default(),
+            )
+            .unwrap();
+
+            let outcomes = evaluate_badges(&package, &options).await.unwrap();
+            let mut output = Vec::new();
+            write_explanation(&mut output, &package, &options.only, &outcomes).unwrap();
+            let output = String::from_utf8(output).unwrap();
+            let line = output
+                .lines()
+                .find(|line| line.starts_with("license:"))
+                .unwrap()
+                .to_string();
+            explanations.push(line);
+        }
+
+        assert_eq!(
+            explanations[0],
+            "license: skipped, no `license` field in Cargo.toml"
+        );
+        assert!(
+            explanations[1]
+                .starts_with("license: skipped, `Not a license` is not a valid SPDX expression: "),
+            "{}",
+            explanations[1]
+        );
+    }
+}
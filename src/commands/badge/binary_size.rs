@@ -0,0 +1,220 @@
+//! Generate release binary size badge.
+
+use anyhow::{
+    Context,
+    Result,
+};
+
+use super::common::{
+    self,
+    Badge,
+    CacheOptions,
+    SkipReason,
+};
+use crate::commands::common::CargoOptions;
+
+/// Show the release binary size badge.
+///
+/// Builds the package with `cargo build --release` and reports the size of
+/// its executable. When the package has no binary target, the badge is
+/// skipped. Building is expensive, so this badge is only part of `badge all`
+/// when listed in the badge allowlist.
+pub async fn badge_binary_size(
+    package: &cargo_metadata::Package,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+    // Use ephemeral status (cyan) for subprocess operations
+    logger.status("Generating", "binary size badge");
+
+    let has_binary = package.targets.iter().any(|target| target.is_bin());
+    if !has_binary {
+        return Ok(Err(SkipReason::new("the package has no binary target")));
+    }
+
+    let size = match get_binary_size(&mut logger, package, cargo, cache).await? {
+        Ok(size) => size,
+        Err(reason) => return Ok(Err(reason)),
+    };
+
+    let badge_url = common::badge_url(
+        base_url,
+        &format!(
+            "badge/binary-{}-blue",
+            format_size(size).replace(' ', "%20")
+        ),
+    );
+    Ok(Ok(Badge::new(
+        "binary-size",
+        "Binary Size",
+        &badge_url,
+        "Cargo.toml",
+    )))
+}
+
+/// Build the package in release mode and stat its executable.
+/// Uses cache if available and valid.
+async fn get_binary_size(
+    logger: &mut cargo_plugin_utils::logger::Logger,
+    package: &cargo_metadata::Package,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+) -> Result<Result<u64, SkipReason>> {
+    // Try to load from cache first
+    if let Some(cached) =
+        common::load_cache_if_fresh::<u64>("binary-size", package, common::LOCAL_CACHE_TTL, cache)
+            .await?
+    {
+        return Ok(Ok(cached));
+    }
+
+    let package_name = package.name.clone();
+    let output = cargo_plugin_utils::logger::run_subprocess(
+        logger,
+        move || {
+            let mut cmd = common::cargo_command(cargo);
+            cmd.arg("build");
+            cmd.arg("--release");
+            cmd.arg("--package");
+            cmd.arg(package_name.as_str());
+            cmd.arg("--message-format");
+            cmd.arg("json");
+            cmd
+        },
+        None,
+    )
+    .await?;
+
+    if !output.success() {
+        return Ok(Err(SkipReason::new("`cargo build --release` failed")));
+    }
+
+    let stdout = output
+        .stdout_str()
+        .context("Failed to parse cargo build output")?;
+    let Some(executable) = find_executable(&stdout, &package.name) else {
+        return Ok(Err(SkipReason::new(
+            "no executable for the package in the cargo build output",
+        )));
+    };
+
+    let size = tokio::fs::metadata(&executable)
+        .await
+        .with_context(|| format!("Failed to stat {}", executable))?
+        .len();
+
+    // Save to cache
+    common::save_cache("binary-size", package, &size).await?;
+    Ok(Ok(size))
+}
+
+/// Find the executable built for `package_name` in cargo's JSON messages.
+///
+/// Prefers the binary named after the package when there are several.
+fn find_executable(output: &str, package_name: &str) -> Option<String> {
+    let mut executables = Vec::new();
+
+    for line in output.lines() {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        if json.get("reason").and_then(|r| r.as_str()) != Some("compiler-artifact") {
+            continue;
+        }
+
+        let is_our_package = json
+            .get("package_id")
+            .and_then(|id| id.as_str())
+            .is_some_and(|id| is_package_id(id, package_name));
+        let is_bin = json
+            .get("target")
+            .and_then(|t| t.get("kind"))
+            .and_then(|k| k.as_array())
+            .is_some_and(|kinds| kinds.iter().any(|kind| kind == "bin"));
+        if !is_our_package || !is_bin {
+            continue;
+        }
+
+        let name = json
+            .get("target")
+            .and_then(|t| t.get("name"))
+            .and_then(|n| n.as_str())
+            .unwrap_or_default()
+            .to_string();
+        if let Some(executable) = json.get("executable").and_then(|e| e.as_str()) {
+            executables.push((name, executable.to_string()));
+        }
+    }
+
+    let preferred = executables
+        .iter()
+        .position(|(name, _)| name == package_name)
+        .unwrap_or(0);
+    executables
+        .into_iter()
+        .nth(preferred)
+        .map(|(_, executable)| executable)
+}
+
+/// Check whether a cargo package id refers to `package_name`.
+///
+/// Accepts both the legacy `name@version` style and package id specs such as
+/// `path+file:///work/foo#name@version`.
+fn is_package_id(id: &str, package_name: &str) -> bool {
+    let suffix = format!("{}@", package_name);
+    id.starts_with(&suffix) || id.contains(&format!("#{}", suffix))
+}
+
+/// Format a byte count for humans, e.g. `2.1 MB`.
+///
+/// Uses binary (1024-based) units.
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KB", "MB", "GB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.0 KB");
+        assert_eq!(format_size(2_202_010), "2.1 MB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GB");
+    }
+
+    #[test]
+    fn test_find_executable() {
+        let output = concat!(
+            r#"{"reason":"compiler-artifact","package_id":"serde@1.0.0","target":{"kind":["lib"],"name":"serde"},"executable":null}"#,
+            "\n",
+            r#"{"reason":"compiler-artifact","package_id":"foo@0.1.0","target":{"kind":["bin"],"name":"helper"},"executable":"/t/release/helper"}"#,
+            "\n",
+            r#"{"reason":"compiler-artifact","package_id":"foo@0.1.0","target":{"kind":["bin"],"name":"foo"},"executable":"/t/release/foo"}"#,
+            "\n",
+            r#"{"reason":"build-finished","success":true}"#,
+        );
+
+        assert_eq!(
+            find_executable(output, "foo"),
+            Some("/t/release/foo".to_string())
+        );
+        assert_eq!(find_executable(output, "bar"), None);
+    }
+}
@@ -1,11 +1,320 @@
 //! Common utilities for badge generation.
 
-use std::path::PathBuf;
+use std::fmt;
+use std::io::Write;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::str::FromStr;
+use std::time::{
+    Duration,
+    SystemTime,
+    UNIX_EPOCH,
+};
 
 use anyhow::{
     Context,
     Result,
 };
+use portable_pty::CommandBuilder;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::commands::common::CargoOptions;
+
+/// Output format for rendered badges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BadgeFormat {
+    /// Markdown image links: `[![alt](url)](link)`.
+    #[default]
+    Markdown,
+    /// AsciiDoc inline images: `image:url[alt,link=link]`.
+    Asciidoc,
+}
+
+impl FromStr for BadgeFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "asciidoc" | "adoc" => Ok(Self::Asciidoc),
+            _ => anyhow::bail!("Invalid format: {}", format),
+        }
+    }
+}
+
+/// A single badge, independent of output format.
+///
+/// Badge generators return these instead of writing output directly, so the
+/// same badges can be rendered as Markdown, AsciiDoc, or consumed as data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Badge {
+    /// Stable badge identifier, matching the `badge` subcommand name (e.g.
+    /// `license`, `rust-edition`).
+    pub name: String,
+    /// Alt text for the badge image.
+    pub alt: String,
+    /// URL of the badge image.
+    pub image_url: String,
+    /// Link target when the badge is clicked.
+    pub link: String,
+}
+
+impl Badge {
+    /// Create a new badge.
+    pub fn new(
+        name: impl Into<String>,
+        alt: impl Into<String>,
+        image_url: impl Into<String>,
+        link: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            alt: alt.into(),
+            image_url: image_url.into(),
+            link: link.into(),
+        }
+    }
+
+    /// Write this badge in the requested format, followed by a newline.
+    pub fn render(&self, writer: &mut dyn Write, format: BadgeFormat) -> Result<()> {
+        match format {
+            BadgeFormat::Markdown => writeln!(
+                writer,
+                "[![{}]({})]({})",
+                self.alt, self.image_url, self.link
+            )?,
+            BadgeFormat::Asciidoc => writeln!(
+                writer,
+                "image:{}[{},link={}]",
+                self.image_url, self.alt, self.link
+            )?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Why a badge was not generated.
+///
+/// Generators return `Ok(Err(reason))` when a badge does not apply to the
+/// package or its value could not be computed, and `Err` only for failures
+/// that should abort the whole command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkipReason(String);
+
+impl SkipReason {
+    /// Create a skip reason from a human-readable message.
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self(reason.into())
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Write badges in the requested format, one per line.
+pub fn render_badges(writer: &mut dyn Write, badges: &[Badge], format: BadgeFormat) -> Result<()> {
+    for badge in badges {
+        badge.render(writer, format)?;
+    }
+
+    Ok(())
+}
+
+/// Default timeout of a single badge HTTP request (see `--timeout`).
+pub const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Attempts made for a badge HTTP request before giving up.
+const HTTP_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled for every further retry.
+const HTTP_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Total time allowed for one badge HTTP check, retries included, so a
+/// flaky network cannot hang `badge all`.
+const HTTP_TOTAL_BUDGET: Duration = Duration::from_secs(15);
+
+/// Create the HTTP client used by badge checks (crates.io, docs.rs).
+///
+/// `timeout` applies to each request; `None` means [`HTTP_TIMEOUT`].
+pub fn http_client(timeout: Option<Duration>) -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(timeout.unwrap_or(HTTP_TIMEOUT))
+        .user_agent("cargo-version-info")
+        .build()
+        .context("Failed to create HTTP client")
+}
+
+/// Send a request, retrying transient failures with exponential backoff.
+///
+/// Network errors (timeouts, connection failures) and 5xx responses are
+/// retried up to [`HTTP_ATTEMPTS`] times within [`HTTP_TOTAL_BUDGET`]. Any
+/// other response, including 404, is returned as is: it is a real answer,
+/// not a transient failure. Each attempt is cut off at `timeout` (`None`
+/// means [`HTTP_TIMEOUT`]) or when the budget runs out, whichever is first.
+///
+/// # Errors
+///
+/// Returns the last network error if every attempt failed.
+pub async fn send_with_retry(
+    request: reqwest::RequestBuilder,
+    timeout: Option<Duration>,
+) -> Result<reqwest::Response> {
+    let timeout = timeout.unwrap_or(HTTP_TIMEOUT);
+    let start = std::time::Instant::now();
+    let mut attempt = 1;
+    loop {
+        let this_request = request
+            .try_clone()
+            .context("HTTP request cannot be retried")?
+            .timeout(attempt_timeout(timeout, start.elapsed()));
+        let result = this_request.send().await;
+
+        let retryable = match &result {
+            Ok(response) => is_retryable_status(response.status()),
+            Err(error) => error.is_timeout() || error.is_connect(),
+        };
+        let delay = retry_delay(attempt);
+        if !retryable || attempt >= HTTP_ATTEMPTS || start.elapsed() + delay >= HTTP_TOTAL_BUDGET {
+            return Ok(result?);
+        }
+
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Whether an error was caused by the network (DNS, connection, timeout,
+/// reading the response) rather than by a definite answer or a local
+/// failure.
+///
+/// Badge checks treat these as "unknown": the badge is skipped with a
+/// warning instead of aborting `badge all`.
+pub fn is_network_error(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|cause| cause.downcast_ref::<reqwest::Error>().is_some())
+}
+
+/// Whether a response status indicates a transient server failure.
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+}
+
+/// Timeout of an attempt started `elapsed` into the retry budget.
+///
+/// A per-request timeout replaces the client's, so it must not exceed
+/// `timeout` either.
+fn attempt_timeout(timeout: Duration, elapsed: Duration) -> Duration {
+    timeout.min(HTTP_TOTAL_BUDGET.saturating_sub(elapsed))
+}
+
+/// Delay before retrying after failed attempt number `attempt` (from 1).
+fn retry_delay(attempt: u32) -> Duration {
+    HTTP_RETRY_DELAY * 2u32.pow(attempt.saturating_sub(1))
+}
+
+/// Create a `cargo` command for badge subprocesses.
+///
+/// Under `--offline`/`--frozen`, `CARGO_NET_OFFLINE` is set on the command so
+/// cargo does not touch the network.
+pub fn cargo_command(cargo: CargoOptions) -> CommandBuilder {
+    let mut cmd = CommandBuilder::new("cargo");
+    if cargo.is_offline() {
+        cmd.env("CARGO_NET_OFFLINE", "true");
+    }
+    cmd
+}
+
+/// Default badge image host.
+pub const DEFAULT_BADGE_BASE_URL: &str = "https://img.shields.io";
+
+/// Resolve the badge base URL for a package.
+///
+/// Uses, in order: `override_url` (from `--badge-base-url`),
+/// `badge-base-url` in `[package.metadata.version-info]`, then
+/// [`DEFAULT_BADGE_BASE_URL`]. Trailing slashes are removed.
+pub fn badge_base_url(package: &cargo_metadata::Package, override_url: Option<&str>) -> String {
+    let base_url = override_url
+        .or_else(|| {
+            version_info_setting(&package.metadata, "badge-base-url").and_then(|url| url.as_str())
+        })
+        .unwrap_or(DEFAULT_BADGE_BASE_URL);
+
+    base_url.trim_end_matches('/').to_string()
+}
+
+/// Build a badge image URL from a base URL and a shields-style path (e.g.
+/// `badge/tests-5-blue` or `crates/v/serde`).
+pub fn badge_url(base_url: &str, path: &str) -> String {
+    format!(
+        "{}/{}",
+        base_url.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    )
+}
+
+/// Resolve a badge link against an optional base URL.
+///
+/// Repo-relative links (`Cargo.toml`, `tests/`, `docs/adr/index.typ`) become
+/// `{link_base}/{link}`, so badges keep working where the README is rendered
+/// away from the repository (e.g. crates.io). Absolute links (containing
+/// `://`) and links without a base are returned unchanged, which also makes
+/// resolving twice harmless.
+pub fn resolve_link(link_base: Option<&str>, link: &str) -> String {
+    match link_base {
+        Some(base) if !link.contains("://") => format!(
+            "{}/{}",
+            base.trim_end_matches('/'),
+            link.trim_start_matches("./").trim_start_matches('/')
+        ),
+        _ => link.to_string(),
+    }
+}
+
+/// Escape text for a shields.io static badge path segment.
+///
+/// Shields uses `-` and `_` as separators, so literal dashes and underscores
+/// must be doubled.
+pub fn shields_escape(text: &str) -> String {
+    text.replace('-', "--")
+        .replace('_', "__")
+        .replace(' ', "%20")
+        .replace('/', "%2F")
+}
+
+/// Pick a badge color for a coverage percentage.
+pub fn coverage_color(percent: u8) -> &'static str {
+    if percent >= 80 {
+        "brightgreen"
+    } else if percent >= 60 {
+        "green"
+    } else if percent >= 40 {
+        "yellow"
+    } else {
+        "red"
+    }
+}
+
+/// Read a setting from `[package.metadata.version-info]`.
+///
+/// Pass `&package.metadata`, which `cargo_metadata` exposes as JSON.
+pub fn version_info_setting<'a>(
+    metadata: &'a serde_json::Value,
+    key: &str,
+) -> Option<&'a serde_json::Value> {
+    metadata.get("version-info")?.get(key)
+}
 
 /// Heuristically guess if a crate is likely published on crates.io/docs.rs.
 ///
@@ -73,72 +382,367 @@ pub async fn guess_if_published(package: &cargo_metadata::Package) -> Result<boo
 }
 
 /// Compute cache key for invalidation.
-/// Uses git commit hash if available, otherwise falls back to Cargo.toml mtime.
+///
+/// Uses the git commit hash of the repository containing the manifest if
+/// available. Outside a git checkout (e.g. a source tarball in CI), or in a
+/// repository without commits, falls back to the manifest's modification
+/// time, then to a hash of its content. Never fails because git is missing.
 pub async fn compute_cache_key(package: &cargo_metadata::Package) -> Result<String> {
-    // Try git commit hash first
-    let git_hash = tokio::task::spawn_blocking(|| {
-        let repo = match gix::discover(".") {
-            Ok(r) => r,
-            Err(_) => return None,
-        };
+    let manifest_path = package.manifest_path.as_std_path().to_path_buf();
+    tokio::task::spawn_blocking(move || cache_key_for_manifest(&manifest_path))
+        .await
+        .context("Failed to spawn blocking task")
+}
 
-        match repo.head_id() {
-            Ok(id) => Some(id.to_hex().to_string()),
-            Err(_) => None,
-        }
-    })
-    .await
-    .context("Failed to spawn blocking task")?;
+/// Synchronous implementation of [`compute_cache_key`].
+fn cache_key_for_manifest(manifest_path: &Path) -> String {
+    let manifest_dir = manifest_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
 
-    if let Some(hash) = git_hash {
-        return Ok(hash);
+    // Git commit hash first
+    if let Ok(repo) = gix::discover(manifest_dir)
+        && let Ok(id) = repo.head_id()
+    {
+        return id.to_hex().to_string();
     }
 
-    // Fall back to Cargo.toml modification time
-    let manifest_path = package.manifest_path.as_std_path();
-    let mtime = tokio::task::spawn_blocking({
-        let path = manifest_path.to_path_buf();
-        move || {
-            std::fs::metadata(&path)
-                .ok()
-                .and_then(|meta| meta.modified().ok())
-                .map(|time| {
-                    time.duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs()
-                        .to_string()
-                })
-        }
-    })
-    .await
-    .context("Failed to spawn blocking task")?;
+    // Then Cargo.toml modification time
+    let mtime = std::fs::metadata(manifest_path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok());
+    if let Some(mtime) = mtime {
+        return format!("mtime-{}", mtime.as_secs());
+    }
 
-    Ok(mtime.unwrap_or_else(|| "unknown".to_string()))
+    // Then Cargo.toml content
+    match std::fs::read(manifest_path) {
+        Ok(content) => format!(
+            "content-{:016x}",
+            crate::commands::common::fnv1a_64(&content)
+        ),
+        Err(_) => "unknown".to_string(),
+    }
 }
 
 /// Get cache file path for badge caches.
+///
+/// The file lives in the cargo target directory and is keyed on the
+/// workspace root, so each checkout has its own cache even when the target
+/// directory is shared (see [`crate::commands::common::cache_file_path`]).
 pub fn get_badge_cache_path(cache_name: &str) -> Result<PathBuf> {
-    let target_dir = if let Ok(dir) = std::env::var("CARGO_TARGET_DIR") {
-        PathBuf::from(dir)
-    } else {
-        // Try to find target directory relative to current dir
-        let mut path = std::env::current_dir()?;
-        let mut found = None;
-        loop {
-            let target = path.join("target");
-            if target.exists() {
-                found = Some(target);
-                break;
-            }
-            if let Some(parent) = path.parent() {
-                path = parent.to_path_buf();
-            } else {
-                break;
-            }
-        }
-        // Fallback to current dir
-        found.unwrap_or_else(|| std::env::current_dir().unwrap().join("target"))
+    crate::commands::common::cache_file_path(cache_name)
+}
+
+/// Default TTL for caches keyed on the commit (or manifest mtime).
+///
+/// The cache key already invalidates these on every change; the TTL only
+/// bounds how long a result survives on a long-lived, unchanged checkout.
+pub const LOCAL_CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Default TTL for caches of network lookups (crates.io, docs.rs).
+pub const NETWORK_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Cache behavior shared by all cached badges (`--cache-ttl`,
+/// `--no-cache`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheOptions {
+    /// TTL overriding every cache kind's own TTL (`--cache-ttl`).
+    pub ttl: Option<Duration>,
+    /// Never read caches (`--no-cache`). Fresh results are still saved.
+    pub no_cache: bool,
+}
+
+/// A cached badge value with the data needed to validate it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry<T> {
+    /// Package name
+    package: String,
+    /// Cache key (git commit hash or file mtime)
+    cache_key: String,
+    /// When the value was computed (seconds since the Unix epoch)
+    fetched_at: u64,
+    /// The cached value
+    value: T,
+}
+
+/// Seconds since the Unix epoch.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Whether an entry fetched at `fetched_at` is still within `ttl` at `now`.
+fn is_fresh(fetched_at: u64, now: u64, ttl: Duration) -> bool {
+    now.saturating_sub(fetched_at) < ttl.as_secs()
+}
+
+/// Load a cached value if it is still valid.
+///
+/// The entry must belong to `package`, match the current
+/// [`compute_cache_key`], and be younger than `ttl` (or the
+/// [`CacheOptions::ttl`] override). Missing, unreadable, or outdated caches,
+/// and [`CacheOptions::no_cache`], all yield `None`.
+pub async fn load_cache_if_fresh<T: DeserializeOwned>(
+    cache_name: &str,
+    package: &cargo_metadata::Package,
+    ttl: Duration,
+    cache: CacheOptions,
+) -> Result<Option<T>> {
+    if cache.no_cache {
+        return Ok(None);
+    }
+
+    let cache_path = get_badge_cache_path(cache_name)?;
+    let Ok(contents) = tokio::fs::read_to_string(&cache_path).await else {
+        return Ok(None);
+    };
+    // Caches written by older versions (or corrupted) are simply recomputed
+    let Ok(entry) = serde_json::from_str::<CacheEntry<T>>(&contents) else {
+        return Ok(None);
+    };
+
+    let ttl = cache.ttl.unwrap_or(ttl);
+    if entry.package != package.name.as_str()
+        || !is_fresh(entry.fetched_at, unix_now(), ttl)
+        || entry.cache_key != compute_cache_key(package).await?
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(entry.value))
+}
+
+/// Save a value to a badge cache, stamped with the current time.
+pub async fn save_cache<T: Serialize>(
+    cache_name: &str,
+    package: &cargo_metadata::Package,
+    value: &T,
+) -> Result<()> {
+    let entry = CacheEntry {
+        package: package.name.to_string(),
+        cache_key: compute_cache_key(package).await?,
+        fetched_at: unix_now(),
+        value,
     };
 
-    Ok(target_dir.join(format!(".cargo-version-info-{}-cache.json", cache_name)))
+    let cache_path = get_badge_cache_path(cache_name)?;
+
+    // Create parent directory if it doesn't exist
+    if let Some(parent) = cache_path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .context("Failed to create cache directory")?;
+    }
+
+    let json = serde_json::to_string_pretty(&entry).context("Failed to serialize cache")?;
+
+    tokio::fs::write(&cache_path, json)
+        .await
+        .context("Failed to write cache file")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_outside_git() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(&manifest, "[package]\nname = \"a\"\n").unwrap();
+
+        let key = cache_key_for_manifest(&manifest);
+        assert!(key.starts_with("mtime-"), "unexpected key: {}", key);
+        assert_eq!(cache_key_for_manifest(&manifest), key);
+
+        // A missing manifest still yields a key rather than an error
+        assert_eq!(
+            cache_key_for_manifest(&dir.path().join("missing/Cargo.toml")),
+            "unknown"
+        );
+    }
+
+    #[test]
+    fn test_render_badge_markdown() {
+        let badge = Badge::new(
+            "number-of-tests",
+            "Tests",
+            "https://img.shields.io/badge/tests-5-blue",
+            "tests/",
+        );
+        let mut output = Vec::new();
+        badge.render(&mut output, BadgeFormat::Markdown).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "[![Tests](https://img.shields.io/badge/tests-5-blue)](tests/)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_badge_asciidoc() {
+        let badge = Badge::new(
+            "number-of-tests",
+            "Tests",
+            "https://img.shields.io/badge/tests-5-blue",
+            "tests/",
+        );
+        let mut output = Vec::new();
+        badge.render(&mut output, BadgeFormat::Asciidoc).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "image:https://img.shields.io/badge/tests-5-blue[Tests,link=tests/]\n"
+        );
+    }
+
+    #[test]
+    fn test_badge_url() {
+        assert_eq!(
+            badge_url(DEFAULT_BADGE_BASE_URL, "badge/tests-5-blue"),
+            "https://img.shields.io/badge/tests-5-blue"
+        );
+        assert_eq!(
+            badge_url("https://shields.example.com/", "/crates/v/serde"),
+            "https://shields.example.com/crates/v/serde"
+        );
+    }
+
+    #[test]
+    fn test_shields_escape() {
+        assert_eq!(
+            shields_escape("MIT OR Apache-2.0"),
+            "MIT%20OR%20Apache--2.0"
+        );
+        assert_eq!(shields_escape("a_b/c"), "a__b%2Fc");
+    }
+
+    #[test]
+    fn test_coverage_color() {
+        assert_eq!(coverage_color(95), "brightgreen");
+        assert_eq!(coverage_color(80), "brightgreen");
+        assert_eq!(coverage_color(65), "green");
+        assert_eq!(coverage_color(40), "yellow");
+        assert_eq!(coverage_color(10), "red");
+    }
+
+    #[test]
+    fn test_version_info_setting() {
+        let metadata = serde_json::json!({
+            "version-info": { "platform": "Kubernetes" },
+            "other-tool": { "platform": "ignored" },
+        });
+
+        assert_eq!(
+            version_info_setting(&metadata, "platform").and_then(|v| v.as_str()),
+            Some("Kubernetes")
+        );
+        assert!(version_info_setting(&metadata, "badges").is_none());
+        assert!(version_info_setting(&serde_json::Value::Null, "platform").is_none());
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let ttl = Duration::from_secs(60);
+        assert!(is_fresh(1_000, 1_000, ttl));
+        assert!(is_fresh(1_000, 1_059, ttl));
+        assert!(!is_fresh(1_000, 1_060, ttl));
+        // Clock skew: entries from the future count as fresh
+        assert!(is_fresh(2_000, 1_000, ttl));
+        assert!(!is_fresh(1_000, 1_000, Duration::ZERO));
+    }
+
+    #[test]
+    fn test_cache_entry_round_trip() {
+        let entry = CacheEntry {
+            package: "demo".to_string(),
+            cache_key: "abc123".to_string(),
+            fetched_at: 1_700_000_000,
+            value: 42u32,
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        let parsed: CacheEntry<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.value, 42);
+        assert_eq!(parsed.fetched_at, 1_700_000_000);
+
+        // Entries without `fetched_at` (written by older versions) don't parse
+        assert!(
+            serde_json::from_str::<CacheEntry<u32>>(
+                r#"{"package":"demo","cache_key":"abc123","test_count":42}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_is_network_error() {
+        assert!(!is_network_error(&anyhow::anyhow!("cache unreadable")));
+        let io = std::io::Error::other("disk full");
+        assert!(!is_network_error(
+            &anyhow::Error::new(io).context("Failed to save cache")
+        ));
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        assert!(is_retryable_status(reqwest::StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(
+            reqwest::StatusCode::SERVICE_UNAVAILABLE
+        ));
+        assert!(!is_retryable_status(reqwest::StatusCode::NOT_FOUND));
+        assert!(!is_retryable_status(reqwest::StatusCode::OK));
+
+        assert_eq!(retry_delay(1), HTTP_RETRY_DELAY);
+        assert_eq!(retry_delay(2), HTTP_RETRY_DELAY * 2);
+        assert_eq!(retry_delay(3), HTTP_RETRY_DELAY * 4);
+
+        let timeout = Duration::from_secs(5);
+        assert_eq!(attempt_timeout(timeout, Duration::ZERO), timeout);
+        assert_eq!(
+            attempt_timeout(timeout, HTTP_TOTAL_BUDGET - Duration::from_secs(2)),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            attempt_timeout(timeout, HTTP_TOTAL_BUDGET + Duration::from_secs(1)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_resolve_link() {
+        let base = Some("https://github.com/owner/repo/blob/main/");
+        assert_eq!(
+            resolve_link(base, "Cargo.toml"),
+            "https://github.com/owner/repo/blob/main/Cargo.toml"
+        );
+        assert_eq!(
+            resolve_link(base, "docs/adr/index.typ"),
+            "https://github.com/owner/repo/blob/main/docs/adr/index.typ"
+        );
+        assert_eq!(
+            resolve_link(base, "https://crates.io/crates/foo"),
+            "https://crates.io/crates/foo"
+        );
+        assert_eq!(resolve_link(None, "tests/"), "tests/");
+    }
+
+    #[test]
+    fn test_badge_format_from_str() {
+        assert_eq!(
+            "markdown".parse::<BadgeFormat>().unwrap(),
+            BadgeFormat::Markdown
+        );
+        assert_eq!(
+            "asciidoc".parse::<BadgeFormat>().unwrap(),
+            BadgeFormat::Asciidoc
+        );
+        assert!("html".parse::<BadgeFormat>().is_err());
+    }
 }
@@ -1,97 +1,265 @@
 //! Generate test coverage badge.
+//!
+//! Coverage is measured with one of the supported [`CoverageTool`]s:
+//!
+//! - `llvm-cov` (default): `cargo llvm-cov --summary-only --json`, reading the
+//!   line coverage from the printed JSON summary
+//! - `tarpaulin`: `cargo tarpaulin --out Json`, reading the coverage from the
+//!   `tarpaulin-report.json` it writes
+//!
+//! Alternatively, `--coverage-file` reads the percentage from a report
+//! generated earlier (`lcov.info` or Cobertura XML) without running cargo.
+
+use std::path::Path;
+use std::str::FromStr;
 
 use anyhow::{
     Context,
     Result,
 };
-use portable_pty::CommandBuilder;
-use serde::{
-    Deserialize,
-    Serialize,
+
+use super::common::{
+    self,
+    Badge,
+    CacheOptions,
+    SkipReason,
+};
+use crate::commands::common::CargoOptions;
+use crate::exit_code::{
+    ExitCode,
+    ExitError,
 };
 
-use super::common;
+/// Tool used to measure test coverage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CoverageTool {
+    /// `cargo llvm-cov`.
+    #[default]
+    LlvmCov,
+    /// `cargo tarpaulin`.
+    Tarpaulin,
+}
+
+impl FromStr for CoverageTool {
+    type Err = anyhow::Error;
+
+    fn from_str(tool: &str) -> Result<Self> {
+        match tool {
+            "llvm-cov" => Ok(Self::LlvmCov),
+            "tarpaulin" => Ok(Self::Tarpaulin),
+            _ => anyhow::bail!(
+                "Invalid coverage tool: {} (expected llvm-cov or tarpaulin)",
+                tool
+            ),
+        }
+    }
+}
+
+impl CoverageTool {
+    /// The cargo subcommand running the tool.
+    fn subcommand(self) -> &'static str {
+        match self {
+            Self::LlvmCov => "llvm-cov",
+            Self::Tarpaulin => "tarpaulin",
+        }
+    }
+
+    /// The crate providing the cargo subcommand.
+    fn crate_name(self) -> &'static str {
+        match self {
+            Self::LlvmCov => "cargo-llvm-cov",
+            Self::Tarpaulin => "cargo-tarpaulin",
+        }
+    }
+
+    /// Name of the cache holding this tool's result.
+    fn cache_name(self) -> &'static str {
+        match self {
+            Self::LlvmCov => "coverage",
+            Self::Tarpaulin => "coverage-tarpaulin",
+        }
+    }
+
+    /// Arguments (after `cargo`) measuring coverage of `package_name`.
+    ///
+    /// `output_dir` receives report files for tools that don't print their
+    /// report to stdout.
+    fn args(self, package_name: &str, output_dir: &Path) -> Vec<String> {
+        match self {
+            Self::LlvmCov => vec![
+                "llvm-cov".to_string(),
+                "--package".to_string(),
+                package_name.to_string(),
+                "--summary-only".to_string(),
+                "--json".to_string(),
+            ],
+            Self::Tarpaulin => vec![
+                "tarpaulin".to_string(),
+                "--packages".to_string(),
+                package_name.to_string(),
+                "--out".to_string(),
+                "Json".to_string(),
+                "--output-dir".to_string(),
+                output_dir.display().to_string(),
+            ],
+        }
+    }
+
+    /// Read the report produced by a successful run.
+    fn read_report(self, stdout: String, output_dir: &Path) -> Result<String> {
+        match self {
+            Self::LlvmCov => Ok(stdout),
+            Self::Tarpaulin => {
+                let report = output_dir.join("tarpaulin-report.json");
+                std::fs::read_to_string(&report)
+                    .with_context(|| format!("Failed to read {}", report.display()))
+            }
+        }
+    }
+
+    /// Extract the overall coverage percentage from the tool's report.
+    fn parse_percentage(self, report: &str) -> Option<f64> {
+        match self {
+            Self::LlvmCov => parse_llvm_cov_json(report),
+            Self::Tarpaulin => parse_tarpaulin_json(report),
+        }
+    }
+}
 
 /// Show the test coverage badge.
+///
+/// With `coverage_file`, the percentage is read from that report and `tool`
+/// is not run.
 pub async fn badge_coverage(
-    writer: &mut dyn std::io::Write,
     package: &cargo_metadata::Package,
-) -> Result<()> {
+    tool: CoverageTool,
+    coverage_file: Option<&Path>,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
+    let coverage = coverage_percentage(package, tool, coverage_file, cargo, cache).await?;
+    Ok(coverage.map(|coverage| coverage_badge(package, coverage, base_url)))
+}
+
+/// Measure the test coverage percentage (rounded).
+///
+/// Reads `coverage_file` if given, otherwise runs `tool`. Returns the skip
+/// reason if the tool is not installed or coverage could not be measured.
+///
+/// # Errors
+///
+/// Returns an error if `coverage_file` cannot be read or parsed, or the
+/// tool cannot be started.
+pub async fn coverage_percentage(
+    package: &cargo_metadata::Package,
+    tool: CoverageTool,
+    coverage_file: Option<&Path>,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+) -> Result<Result<u8, SkipReason>> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
     // Use ephemeral status (cyan) for subprocess operations
     logger.status("Generating", "coverage badge");
 
-    // Try to get coverage using cargo-llvm-cov
-    let coverage = get_coverage_percentage(&mut logger, package).await?;
-
-    if let Some(coverage) = coverage {
-        // Determine badge color based on coverage percentage
-        let color = if coverage >= 80 {
-            "brightgreen"
-        } else if coverage >= 60 {
-            "green"
-        } else if coverage >= 40 {
-            "yellow"
-        } else {
-            "red"
-        };
+    match coverage_file {
+        Some(path) => Ok(Ok(read_coverage_file(path)?)),
+        None => get_coverage_percentage(&mut logger, package, tool, cargo, cache).await,
+    }
+}
 
-        let badge_url = format!(
-            "https://img.shields.io/badge/coverage-{}%25-{}",
-            coverage, color
-        );
+/// Check measured coverage against a `--threshold`.
+///
+/// # Errors
+///
+/// Returns an [`ExitError`] with [`ExitCode::BelowThreshold`] if coverage is
+/// below `threshold` or could not be measured.
+pub fn check_threshold(coverage: Option<u8>, threshold: u8) -> Result<()> {
+    match coverage {
+        Some(coverage) if coverage >= threshold => Ok(()),
+        Some(coverage) => Err(ExitError::new(
+            ExitCode::BelowThreshold,
+            format!(
+                "Coverage {}% is below the threshold of {}%",
+                coverage, threshold
+            ),
+        )
+        .into()),
+        None => Err(ExitError::new(
+            ExitCode::BelowThreshold,
+            format!(
+                "Coverage could not be measured; cannot check the threshold of {}%",
+                threshold
+            ),
+        )
+        .into()),
+    }
+}
 
-        // Determine link target: prefer GitHub repository, fallback to coverage
-        // directory
-        let link_target = if let Some(repo) = &package.repository {
-            // Link to GitHub Actions if it's a GitHub repo, otherwise just the repo
-            if repo.contains("github.com") {
-                format!("{}/actions", repo)
-            } else {
-                repo.clone()
-            }
-        } else {
-            "coverage/".to_string()
-        };
+/// Build the coverage badge for a measured percentage.
+pub fn coverage_badge(package: &cargo_metadata::Package, coverage: u8, base_url: &str) -> Badge {
+    // Determine badge color based on coverage percentage
+    let color = common::coverage_color(coverage);
 
-        let badge_markdown = format!("[![Coverage]({})]({})", badge_url, link_target);
-        writeln!(writer, "{}", badge_markdown)?;
-    }
+    let badge_url = common::badge_url(
+        base_url,
+        &format!("badge/coverage-{}%25-{}", coverage, color),
+    );
 
-    Ok(())
-}
+    // Determine link target: prefer GitHub repository, fallback to coverage
+    // directory
+    let link_target = if let Some(repo) = &package.repository {
+        // Link to the default branch's GitHub Actions runs if it's a GitHub
+        // repo, otherwise just the repo
+        if repo.contains("github.com") {
+            let manifest_dir = package
+                .manifest_path
+                .as_std_path()
+                .parent()
+                .unwrap_or_else(|| Path::new("."));
+            let branch = crate::commands::common::default_branch(manifest_dir);
+            format!(
+                "{}/actions?query=branch%3A{}",
+                repo.trim_end_matches('/'),
+                branch
+            )
+        } else {
+            repo.clone()
+        }
+    } else {
+        "coverage/".to_string()
+    };
 
-/// Cache entry for coverage results.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct CoverageCache {
-    /// Package name
-    package: String,
-    /// Cache key (git commit hash or file mtime)
-    cache_key: String,
-    /// Coverage percentage
-    coverage: u8,
+    Badge::new("coverage", "Coverage", &badge_url, &link_target)
 }
 
-/// Get test coverage percentage using cargo-llvm-cov.
+/// Get test coverage percentage using the given tool.
 /// Uses cache if available and valid.
 async fn get_coverage_percentage(
     logger: &mut cargo_plugin_utils::logger::Logger,
     package: &cargo_metadata::Package,
-) -> Result<Option<u8>> {
+    tool: CoverageTool,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+) -> Result<Result<u8, SkipReason>> {
     // Try to load from cache first
-    if let Some(cached) = load_coverage_cache(package).await? {
-        let current_key = common::compute_cache_key(package).await?;
-        if cached.cache_key == current_key && package.name == cached.package {
-            return Ok(Some(cached.coverage));
-        }
+    if let Some(cached) = common::load_cache_if_fresh::<u8>(
+        tool.cache_name(),
+        package,
+        common::LOCAL_CACHE_TTL,
+        cache,
+    )
+    .await?
+    {
+        return Ok(Ok(cached));
     }
 
-    // Check if cargo-llvm-cov is available
+    // Check if the tool is available
     let version_output = cargo_plugin_utils::logger::run_subprocess(
         logger,
-        || {
-            let mut cmd = CommandBuilder::new("cargo");
-            cmd.arg("llvm-cov");
+        move || {
+            let mut cmd = common::cargo_command(cargo);
+            cmd.arg(tool.subcommand());
             cmd.arg("--version");
             cmd
         },
@@ -101,22 +269,23 @@ async fn get_coverage_percentage(
 
     if !version_output.success() {
         eprintln!(
-            "Warning: cargo-llvm-cov is not installed. Install it with: cargo binstall cargo-llvm-cov (or cargo install cargo-llvm-cov)"
+            "Warning: {0} is not installed. Install it with: cargo binstall {0} (or cargo install {0})",
+            tool.crate_name()
         );
-        return Ok(None);
+        return Ok(Err(SkipReason::new(format!(
+            "{} is not installed",
+            tool.crate_name()
+        ))));
     }
 
-    // Run cargo llvm-cov to get coverage
-    let package_name = package.name.clone();
+    // Run the tool to measure coverage
+    let output_dir = tempfile::tempdir().context("Failed to create coverage output directory")?;
+    let args = tool.args(package.name.as_str(), output_dir.path());
     let output = cargo_plugin_utils::logger::run_subprocess(
         logger,
         move || {
-            let mut cmd = CommandBuilder::new("cargo");
-            cmd.arg("llvm-cov");
-            cmd.arg("--package");
-            cmd.arg(package_name.as_str());
-            cmd.arg("--summary-only");
-            cmd.arg("--json");
+            let mut cmd = common::cargo_command(cargo);
+            cmd.args(&args);
             cmd
         },
         None,
@@ -124,75 +293,208 @@ async fn get_coverage_percentage(
     .await?;
 
     if !output.success() {
-        return Ok(None);
+        return Ok(Err(SkipReason::new(format!(
+            "`cargo {}` failed",
+            tool.subcommand()
+        ))));
     }
 
-    // Parse JSON output to extract coverage percentage
     let stdout = output
         .stdout_str()
-        .context("Failed to parse cargo-llvm-cov output")?;
-
-    // cargo-llvm-cov JSON format: {"data": [{"totals": {"lines": {"percent": 85.5},
-    // ...}}], ...}
-    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&stdout)
-        && let Some(data) = json.get("data").and_then(|d| d.as_array())
-        && let Some(first_data) = data.first()
-        && let Some(percent) = first_data
-            .get("totals")
-            .and_then(|t| t.get("lines"))
-            .and_then(|l| l.get("percent"))
-            .and_then(|p| p.as_f64())
-    {
+        .with_context(|| format!("Failed to parse cargo {} output", tool.subcommand()))?;
+    let report = tool.read_report(stdout, output_dir.path())?;
+
+    if let Some(percent) = tool.parse_percentage(&report) {
         let coverage = percent.round() as u8;
         // Save to cache
-        save_coverage_cache(package, coverage).await?;
-        return Ok(Some(coverage));
+        common::save_cache(tool.cache_name(), package, &coverage).await?;
+        return Ok(Ok(coverage));
     }
 
-    Ok(None)
+    Ok(Err(SkipReason::new(format!(
+        "no coverage percentage in the `cargo {}` report",
+        tool.subcommand()
+    ))))
 }
 
-/// Load coverage from cache.
-async fn load_coverage_cache(_package: &cargo_metadata::Package) -> Result<Option<CoverageCache>> {
-    let cache_path = common::get_badge_cache_path("coverage")?;
+/// Read the overall line coverage from a pre-generated report file.
+///
+/// Cobertura XML is recognized by its leading `<`; anything else is parsed
+/// as an lcov tracefile.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or contains no coverage data.
+fn read_coverage_file(path: &Path) -> Result<u8> {
+    let report = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read coverage file {}", path.display()))?;
 
-    if !cache_path.exists() {
-        return Ok(None);
+    let percent = if report.trim_start().starts_with('<') {
+        parse_cobertura(&report)
+    } else {
+        parse_lcov(&report)
+    };
+    let percent = percent.with_context(|| {
+        format!(
+            "No coverage data in {} (expected lcov.info or Cobertura XML)",
+            path.display()
+        )
+    })?;
+
+    Ok(percent.round() as u8)
+}
+
+/// Parse line coverage from an lcov tracefile.
+///
+/// Sums the `LF` (lines found) and `LH` (lines hit) records of every source
+/// file.
+fn parse_lcov(report: &str) -> Option<f64> {
+    let (mut found, mut hit) = (0u64, 0u64);
+    for line in report.lines() {
+        if let Some(count) = line.trim().strip_prefix("LF:") {
+            found += count.trim().parse::<u64>().ok()?;
+        } else if let Some(count) = line.trim().strip_prefix("LH:") {
+            hit += count.trim().parse::<u64>().ok()?;
+        }
+    }
+    if found == 0 {
+        return None;
     }
 
-    let contents = tokio::fs::read_to_string(&cache_path)
-        .await
-        .context("Failed to read cache file")?;
+    Some(hit as f64 * 100.0 / found as f64)
+}
 
-    let cache: CoverageCache =
-        serde_json::from_str(&contents).context("Failed to parse cache file")?;
+/// Parse line coverage from a Cobertura XML report.
+///
+/// Reads the `line-rate` attribute (0 to 1) of the root `<coverage>` element.
+fn parse_cobertura(report: &str) -> Option<f64> {
+    let re = regex::Regex::new(r#"<coverage\b[^>]*\sline-rate="([0-9.]+)""#)
+        .expect("valid cobertura regex");
+    let rate: f64 = re.captures(report)?.get(1)?.as_str().parse().ok()?;
 
-    Ok(Some(cache))
+    Some(rate * 100.0)
 }
 
-/// Save coverage to cache.
-async fn save_coverage_cache(package: &cargo_metadata::Package, coverage: u8) -> Result<()> {
-    let cache_key = common::compute_cache_key(package).await?;
-    let cache = CoverageCache {
-        package: package.name.to_string(),
-        cache_key,
-        coverage,
-    };
+/// Parse line coverage from `cargo llvm-cov --json` output.
+///
+/// Format: `{"data": [{"totals": {"lines": {"percent": 85.5}, ...}}], ...}`
+fn parse_llvm_cov_json(report: &str) -> Option<f64> {
+    let json = serde_json::from_str::<serde_json::Value>(report).ok()?;
+    json.get("data")?
+        .as_array()?
+        .first()?
+        .get("totals")?
+        .get("lines")?
+        .get("percent")?
+        .as_f64()
+}
+
+/// Parse coverage from a `tarpaulin-report.json`.
+///
+/// Recent tarpaulin versions record the overall `coverage` percentage at the
+/// top level; older ones only list per-file `covered`/`coverable` line
+/// counts, which are summed instead.
+fn parse_tarpaulin_json(report: &str) -> Option<f64> {
+    let json = serde_json::from_str::<serde_json::Value>(report).ok()?;
+    if let Some(coverage) = json.get("coverage").and_then(|c| c.as_f64()) {
+        return Some(coverage);
+    }
+
+    let (covered, coverable) =
+        json.get("files")?
+            .as_array()?
+            .iter()
+            .fold((0, 0), |(covered, coverable), file| {
+                let count = |key| file.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+                (covered + count("covered"), coverable + count("coverable"))
+            });
+    if coverable == 0 {
+        return None;
+    }
+
+    Some(covered as f64 * 100.0 / coverable as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_tool_from_str() {
+        assert_eq!(
+            "llvm-cov".parse::<CoverageTool>().unwrap(),
+            CoverageTool::LlvmCov
+        );
+        assert_eq!(
+            "tarpaulin".parse::<CoverageTool>().unwrap(),
+            CoverageTool::Tarpaulin
+        );
+        assert!("grcov".parse::<CoverageTool>().is_err());
+    }
 
-    let cache_path = common::get_badge_cache_path("coverage")?;
+    #[test]
+    fn test_parse_llvm_cov_json() {
+        let report =
+            r#"{"data":[{"totals":{"lines":{"count":200,"covered":171,"percent":85.5}}}]}"#;
+        assert_eq!(parse_llvm_cov_json(report), Some(85.5));
+        assert_eq!(parse_llvm_cov_json("{}"), None);
+    }
+
+    #[test]
+    fn test_parse_tarpaulin_json() {
+        assert_eq!(
+            parse_tarpaulin_json(r#"{"files":[],"coverage":72.25}"#),
+            Some(72.25)
+        );
+        assert_eq!(
+            parse_tarpaulin_json(
+                r#"{"files":[{"covered":3,"coverable":4},{"covered":1,"coverable":4}]}"#
+            ),
+            Some(50.0)
+        );
+        assert_eq!(parse_tarpaulin_json(r#"{"files":[]}"#), None);
+    }
 
-    // Create parent directory if it doesn't exist
-    if let Some(parent) = cache_path.parent() {
-        tokio::fs::create_dir_all(parent)
-            .await
-            .context("Failed to create cache directory")?;
+    #[test]
+    fn test_check_threshold() {
+        assert!(check_threshold(Some(80), 80).is_ok());
+        assert!(check_threshold(Some(95), 80).is_ok());
+
+        let below = check_threshold(Some(79), 80).unwrap_err();
+        assert_eq!(ExitCode::from_error(&below), ExitCode::BelowThreshold);
+        let unknown = check_threshold(None, 80).unwrap_err();
+        assert_eq!(ExitCode::from_error(&unknown), ExitCode::BelowThreshold);
     }
 
-    let json = serde_json::to_string_pretty(&cache).context("Failed to serialize cache")?;
+    #[test]
+    fn test_parse_lcov() {
+        let report = "TN:\nSF:src/lib.rs\nDA:1,1\nLF:10\nLH:8\nend_of_record\n\
+                      SF:src/main.rs\nLF:10\nLH:2\nend_of_record\n";
+        assert_eq!(parse_lcov(report), Some(50.0));
+        assert_eq!(parse_lcov("TN:\n"), None);
+    }
 
-    tokio::fs::write(&cache_path, json)
-        .await
-        .context("Failed to write cache file")?;
+    #[test]
+    fn test_parse_cobertura() {
+        let report = r#"<?xml version="1.0"?>
+<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">
+<coverage lines-valid="200" lines-covered="171" line-rate="0.855" branch-rate="0" version="1.9">
+  <packages><package name="a" line-rate="0.5"/></packages>
+</coverage>"#;
+        assert_eq!(parse_cobertura(report), Some(85.5));
+        assert_eq!(parse_cobertura("<coverage/>"), None);
+    }
 
-    Ok(())
+    #[test]
+    fn test_read_coverage_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let lcov = dir.path().join("lcov.info");
+        std::fs::write(&lcov, "SF:src/lib.rs\nLF:3\nLH:2\nend_of_record\n").unwrap();
+        assert_eq!(read_coverage_file(&lcov).unwrap(), 67);
+
+        let empty = dir.path().join("empty.info");
+        std::fs::write(&empty, "").unwrap();
+        assert!(read_coverage_file(&empty).is_err());
+        assert!(read_coverage_file(&dir.path().join("missing.xml")).is_err());
+    }
 }
@@ -1,61 +1,486 @@
 //! Generate crates.io badge.
+//!
+//! A crate counts as published when crates.io knows it and at least one of
+//! its versions is not yanked. With `--include-yanked`, a crate whose
+//! versions are all yanked still gets the badge.
+//!
+//! With `--registry-api`, the check targets an alternative registry instead:
+//! either its crates.io-compatible web API (`https://registry.example.com`)
+//! or its sparse index (`sparse+https://registry.example.com/index/`). The
+//! shields.io crates badge only knows crates.io, so such registries get a
+//! static badge with the latest version and the registry's host as label.
 
-use std::io::Write;
+use std::time::Duration;
 
 use anyhow::{
     Context,
     Result,
 };
 
-use super::common::guess_if_published;
+use super::common::{
+    self,
+    Badge,
+    CacheOptions,
+    SkipReason,
+    guess_if_published,
+};
+
+/// Base URL of the crates.io web API, the default registry.
+pub const CRATES_IO_API: &str = "https://crates.io";
+
+/// Where the publication check looks for a crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Registry<'a> {
+    /// A registry with a crates.io-compatible `/api/v1/crates/<name>` API.
+    Api(&'a str),
+    /// A sparse index (`sparse+<URL>`), as configured for cargo.
+    Sparse(&'a str),
+}
+
+impl<'a> Registry<'a> {
+    /// Parse a `--registry-api` URL; `None` means crates.io.
+    fn parse(registry_api: Option<&'a str>) -> Self {
+        let url = registry_api.unwrap_or(CRATES_IO_API);
+        match url.strip_prefix("sparse+") {
+            Some(index) => Registry::Sparse(index.trim_end_matches('/')),
+            None => Registry::Api(url.trim_end_matches('/')),
+        }
+    }
+
+    /// Whether this is crates.io itself.
+    fn is_crates_io(&self) -> bool {
+        *self == Registry::Api(CRATES_IO_API)
+    }
+
+    /// URL describing `package_name` (API response or index file).
+    fn lookup_url(&self, package_name: &str) -> String {
+        match self {
+            Registry::Api(base) => format!("{}/api/v1/crates/{}", base, package_name),
+            Registry::Sparse(index) => format!("{}/{}", index, sparse_index_path(package_name)),
+        }
+    }
+
+    /// Human-facing link to the crate in this registry.
+    fn crate_link(&self, package_name: &str) -> String {
+        match self {
+            Registry::Api(base) => format!("{}/crates/{}", base, package_name),
+            // Sparse indexes have no crate pages; link the index entry
+            Registry::Sparse(_) => self.lookup_url(package_name),
+        }
+    }
+
+    /// Base URL of the API or index.
+    fn url(&self) -> &'a str {
+        let (Registry::Api(url) | Registry::Sparse(url)) = *self;
+        url
+    }
+
+    /// Host name of the registry, used as badge label.
+    fn host(&self) -> &'a str {
+        let url = self.url();
+        let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+        without_scheme.split('/').next().unwrap_or(without_scheme)
+    }
+
+    /// Cache name for the publication check, distinct per registry.
+    fn cache_name(&self, include_yanked: bool) -> String {
+        let mut name = if include_yanked {
+            "crates-io-published-incl-yanked".to_string()
+        } else {
+            "crates-io-published".to_string()
+        };
+        if !self.is_crates_io() {
+            name.push('-');
+            name.extend(
+                self.url()
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }),
+            );
+        }
+        name
+    }
+
+    /// Whether a lookup response lists at least one version that is not
+    /// yanked.
+    fn has_unyanked_version(&self, response: &str) -> bool {
+        match self {
+            Registry::Api(_) => has_unyanked_version(response),
+            Registry::Sparse(_) => !sparse_versions(response).is_empty(),
+        }
+    }
 
-/// Check if crate is published on crates.io.
+    /// Newest non-yanked version in a lookup response.
+    fn latest_version(&self, response: &str) -> Option<String> {
+        match self {
+            Registry::Api(_) => parse_latest_version(response),
+            Registry::Sparse(_) => sparse_latest_version(response),
+        }
+    }
+}
+
+/// Check if crate is published on crates.io (or the `registry_api`
+/// registry).
 ///
 /// Uses HTTP request when `no_network` is false, otherwise uses heuristics.
+/// Unless `include_yanked` is set, a crate with only yanked versions is not
+/// considered published. Network results are cached for
+/// [`common::NETWORK_CACHE_TTL`].
 async fn is_published_on_crates_io(
     package_name: &str,
     package: &cargo_metadata::Package,
     no_network: bool,
+    include_yanked: bool,
+    registry: Registry<'_>,
+    timeout: Option<Duration>,
+    cache: CacheOptions,
 ) -> Result<bool> {
     if no_network {
         guess_if_published(package).await
     } else {
-        let api_url = format!("https://crates.io/api/v1/crates/{}", package_name);
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(5))
-            .build()
-            .context("Failed to create HTTP client")?;
-
-        let response = client
-            .get(&api_url)
-            .header("User-Agent", "cargo-version-info")
-            .send()
+        let cache_name = registry.cache_name(include_yanked);
+        if let Some(published) = common::load_cache_if_fresh::<bool>(
+            &cache_name,
+            package,
+            common::NETWORK_CACHE_TTL,
+            cache,
+        )
+        .await?
+        {
+            return Ok(published);
+        }
+
+        let api_url = registry.lookup_url(package_name);
+        let client = common::http_client(timeout)?;
+        let response = common::send_with_retry(client.get(&api_url), timeout)
             .await
-            .context("Failed to check crates.io")?;
+            .with_context(|| format!("Failed to check {}", registry.host()))?;
+
+        let published = if !response.status().is_success() {
+            false
+        } else if include_yanked {
+            true
+        } else {
+            let body = response
+                .text()
+                .await
+                .with_context(|| format!("Failed to read {} response", registry.host()))?;
+            registry.has_unyanked_version(&body)
+        };
+        common::save_cache(&cache_name, package, &published).await?;
+        Ok(published)
+    }
+}
+
+/// Get the newest non-yanked version of a crate on crates.io.
+///
+/// Prefers the newest stable version. Returns `None` if the crate is not
+/// published (or every version is yanked). `timeout` applies to each request
+/// (see [`common::http_client`]).
+///
+/// # Errors
+///
+/// Returns an error if crates.io cannot be reached (see
+/// [`common::is_network_error`]).
+pub async fn latest_published_version(
+    package_name: &str,
+    timeout: Option<Duration>,
+) -> Result<Option<String>> {
+    latest_version_in(package_name, Registry::parse(None), timeout).await
+}
+
+/// Get the newest non-yanked version of a crate in `registry`.
+async fn latest_version_in(
+    package_name: &str,
+    registry: Registry<'_>,
+    timeout: Option<Duration>,
+) -> Result<Option<String>> {
+    let api_url = registry.lookup_url(package_name);
+    let client = common::http_client(timeout)?;
+    let response = common::send_with_retry(client.get(&api_url), timeout)
+        .await
+        .with_context(|| format!("Failed to check {}", registry.host()))?;
+    if !response.status().is_success() {
+        return Ok(None);
+    }
+
+    let body = response
+        .text()
+        .await
+        .with_context(|| format!("Failed to read {} response", registry.host()))?;
+    Ok(registry.latest_version(&body))
+}
 
-        Ok(response.status().is_success())
+/// Path of a crate's file in a sparse index, relative to the index root.
+///
+/// Follows cargo's layout: `1/a`, `2/ab`, `3/a/abc`, and `ab/cd/abcd...` for
+/// longer (lowercased) names.
+fn sparse_index_path(package_name: &str) -> String {
+    let name = package_name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
     }
 }
 
+/// Non-yanked versions in a sparse index file (one JSON object per line).
+fn sparse_versions(response: &str) -> Vec<String> {
+    response
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|entry| entry.get("yanked").and_then(|yanked| yanked.as_bool()) == Some(false))
+        .filter_map(|entry| {
+            entry
+                .get("vers")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+/// Newest non-yanked version in a sparse index file, preferring stable
+/// versions like crates.io's `max_stable_version`.
+fn sparse_latest_version(response: &str) -> Option<String> {
+    let versions: Vec<cargo_metadata::semver::Version> = sparse_versions(response)
+        .iter()
+        .filter_map(|version| version.parse().ok())
+        .collect();
+    let stable = versions
+        .iter()
+        .filter(|version| version.pre.is_empty())
+        .max();
+    stable
+        .or_else(|| versions.iter().max())
+        .map(ToString::to_string)
+}
+
+/// Extract the newest non-yanked version from a crates.io crate response.
+fn parse_latest_version(response: &str) -> Option<String> {
+    let json = serde_json::from_str::<serde_json::Value>(response).ok()?;
+    let krate = json.get("crate")?;
+    ["max_stable_version", "max_version"]
+        .iter()
+        .filter_map(|key| krate.get(key).and_then(|v| v.as_str()))
+        // crates.io reports "0.0.0" when every version is yanked
+        .find(|version| *version != "0.0.0")
+        .map(str::to_string)
+}
+
+/// Whether a crates.io `/api/v1/crates/<name>` response lists at least one
+/// version that is not yanked.
+fn has_unyanked_version(response: &str) -> bool {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(response) else {
+        return false;
+    };
+    json.get("versions")
+        .and_then(|versions| versions.as_array())
+        .is_some_and(|versions| {
+            versions.iter().any(|version| {
+                version
+                    .get("yanked")
+                    .and_then(|yanked| yanked.as_bool())
+                    .is_some_and(|yanked| !yanked)
+            })
+        })
+}
+
 /// Show the crates.io badge if the project is published there.
+///
+/// With `registry_api` (see [`CRATES_IO_API`] for the default), the check
+/// and the badge target that registry instead.
 pub async fn badge_cratesio(
-    writer: &mut dyn Write,
     package: &cargo_metadata::Package,
     no_network: bool,
-) -> Result<()> {
+    include_yanked: bool,
+    registry_api: Option<&str>,
+    timeout: Option<Duration>,
+    cache: CacheOptions,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
     logger.status("Generating", "crates.io badge");
 
     let package_name = &package.name;
+    let registry = Registry::parse(registry_api);
+
+    // A 404 means unpublished; a network failure means we don't know, so
+    // skip this badge without failing the others
+    let published = match is_published_on_crates_io(
+        package_name,
+        package,
+        no_network,
+        include_yanked,
+        registry,
+        timeout,
+        cache,
+    )
+    .await
+    {
+        Ok(published) => published,
+        Err(error) if common::is_network_error(&error) => {
+            logger.warning("Skipping", &format!("crates.io badge: {:#}", error));
+            return Ok(Err(SkipReason::new(format!(
+                "{} could not be reached: {:#}",
+                registry.host(),
+                error
+            ))));
+        }
+        Err(error) => return Err(error),
+    };
+
+    if !published {
+        let reason = if no_network {
+            "the crate does not look published (--no-network heuristics)".to_string()
+        } else if include_yanked {
+            format!("the crate is not published on {}", registry.host())
+        } else {
+            format!(
+                "the crate has no unyanked version on {} (see --include-yanked)",
+                registry.host()
+            )
+        };
+        return Ok(Err(SkipReason::new(reason)));
+    }
+
+    if registry.is_crates_io() {
+        let badge_url = common::badge_url(base_url, &format!("crates/v/{}", package_name));
+        let link = format!("https://crates.io/crates/{}", package_name);
+        return Ok(Ok(Badge::new("cratesio", "crates.io", &badge_url, &link)));
+    }
+
+    // shields.io cannot query other registries (often private), so render
+    // the version now; offline, the manifest version is the best guess
+    let latest = if no_network {
+        None
+    } else {
+        latest_version_in(package_name, registry, timeout)
+            .await
+            .unwrap_or_else(|error| {
+                logger.warning("Using", &format!("manifest version: {:#}", error));
+                None
+            })
+    };
+    let version = latest.unwrap_or_else(|| package.version.to_string());
+    let badge_url = registry_badge_url(base_url, registry.host(), &version);
+    Ok(Ok(Badge::new(
+        "cratesio",
+        registry.host(),
+        &badge_url,
+        &registry.crate_link(package_name),
+    )))
+}
+
+/// Static version badge for a crate in an alternative registry.
+fn registry_badge_url(base_url: &str, host: &str, version: &str) -> String {
+    common::badge_url(
+        base_url,
+        &format!(
+            "badge/{}-v{}-orange",
+            common::shields_escape(host),
+            common::shields_escape(version)
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if is_published_on_crates_io(package_name, package, no_network).await? {
-        let badge_url = format!("https://img.shields.io/crates/v/{}", package_name);
-        let badge_markdown = format!(
-            "[![crates.io]({})](https://crates.io/crates/{})",
-            badge_url, package_name
+    #[test]
+    fn test_parse_latest_version() {
+        assert_eq!(
+            parse_latest_version(
+                r#"{"crate":{"max_version":"2.0.0-rc.1","max_stable_version":"1.4.2"}}"#
+            ),
+            Some("1.4.2".to_string())
+        );
+        assert_eq!(
+            parse_latest_version(r#"{"crate":{"max_version":"0.1.0","max_stable_version":null}}"#),
+            Some("0.1.0".to_string())
+        );
+        assert_eq!(
+            parse_latest_version(r#"{"crate":{"max_version":"0.0.0"}}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_has_unyanked_version() {
+        assert!(has_unyanked_version(
+            r#"{"crate":{"name":"a"},"versions":[{"num":"0.2.0","yanked":true},{"num":"0.1.0","yanked":false}]}"#
+        ));
+        assert!(!has_unyanked_version(
+            r#"{"crate":{"name":"a"},"versions":[{"num":"0.1.0","yanked":true}]}"#
+        ));
+        assert!(!has_unyanked_version(r#"{"versions":[]}"#));
+        assert!(!has_unyanked_version("not json"));
+    }
+
+    #[test]
+    fn test_registry() {
+        let crates_io = Registry::parse(None);
+        assert!(crates_io.is_crates_io());
+        assert!(Registry::parse(Some("https://crates.io/")).is_crates_io());
+        assert_eq!(
+            crates_io.lookup_url("serde"),
+            "https://crates.io/api/v1/crates/serde"
+        );
+        assert_eq!(crates_io.cache_name(false), "crates-io-published");
+
+        let api = Registry::parse(Some("https://registry.example.com/"));
+        assert_eq!(api.host(), "registry.example.com");
+        assert_eq!(
+            api.crate_link("my-crate"),
+            "https://registry.example.com/crates/my-crate"
+        );
+        assert_eq!(
+            api.cache_name(true),
+            "crates-io-published-incl-yanked-https---registry-example-com"
+        );
+
+        let sparse = Registry::parse(Some("sparse+https://example.com/index/"));
+        assert_eq!(sparse, Registry::Sparse("https://example.com/index"));
+        assert_eq!(sparse.host(), "example.com");
+        assert_eq!(
+            sparse.lookup_url("My-Crate"),
+            "https://example.com/index/my/-c/my-crate"
+        );
+    }
+
+    #[test]
+    fn test_sparse_index_path() {
+        assert_eq!(sparse_index_path("a"), "1/a");
+        assert_eq!(sparse_index_path("ab"), "2/ab");
+        assert_eq!(sparse_index_path("abc"), "3/a/abc");
+        assert_eq!(sparse_index_path("serde"), "se/rd/serde");
+    }
+
+    #[test]
+    fn test_sparse_latest_version() {
+        let index = concat!(
+            r#"{"name":"a","vers":"0.1.0","yanked":false}"#,
+            "\n",
+            r#"{"name":"a","vers":"0.3.0","yanked":true}"#,
+            "\n",
+            r#"{"name":"a","vers":"0.2.0","yanked":false}"#,
+            "\n",
+            r#"{"name":"a","vers":"1.0.0-rc.1","yanked":false}"#,
+            "\n",
+        );
+        assert_eq!(sparse_latest_version(index), Some("0.2.0".to_string()));
+        assert!(Registry::Sparse("x").has_unyanked_version(index));
+        assert!(
+            !Registry::Sparse("x")
+                .has_unyanked_version(r#"{"name":"a","vers":"0.1.0","yanked":true}"#)
         );
-        writeln!(writer, "{}", badge_markdown)?;
     }
 
-    Ok(())
+    #[test]
+    fn test_registry_badge_url() {
+        assert_eq!(
+            registry_badge_url("https://img.shields.io", "my-registry.dev", "1.2.0-rc.1"),
+            "https://img.shields.io/badge/my--registry.dev-v1.2.0--rc.1-orange"
+        );
+    }
 }
@@ -0,0 +1,67 @@
+//! Generate database layer badges.
+
+use anyhow::Result;
+
+use super::common::{
+    self,
+    Badge,
+    SkipReason,
+};
+
+/// Database crates detected in `[dependencies]`, with their badge labels.
+const DATABASE_CRATES: &[(&str, &str)] = &[
+    ("sqlx", "SQLx"),
+    ("diesel", "Diesel"),
+    ("sea-orm", "SeaORM"),
+];
+
+/// Show a database badge for every database crate the package depends on.
+pub async fn badge_database(
+    package: &cargo_metadata::Package,
+    base_url: &str,
+) -> Result<Result<Vec<Badge>, SkipReason>> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+    logger.status("Generating", "database badge");
+
+    let labels = detect_databases(package.dependencies.iter().map(|dep| dep.name.as_str()));
+    if labels.is_empty() {
+        return Ok(Err(SkipReason::new(
+            "no supported database dependency found (sqlx, diesel, sea-orm)",
+        )));
+    }
+
+    let badges = labels
+        .into_iter()
+        .map(|label| {
+            let badge_url = common::badge_url(
+                base_url,
+                &format!("badge/database-{}-blue", common::shields_escape(label)),
+            );
+            Badge::new("database", "Database", badge_url, "Cargo.toml")
+        })
+        .collect();
+
+    Ok(Ok(badges))
+}
+
+/// Labels of the database crates among `dependencies`, in
+/// [`DATABASE_CRATES`] order.
+fn detect_databases<'a>(dependencies: impl Iterator<Item = &'a str> + Clone) -> Vec<&'static str> {
+    DATABASE_CRATES
+        .iter()
+        .filter(|(name, _)| dependencies.clone().any(|dep| dep == *name))
+        .map(|(_, label)| *label)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_databases() {
+        let deps = ["serde", "sea-orm", "sqlx"];
+        assert_eq!(detect_databases(deps.into_iter()), ["SQLx", "SeaORM"]);
+        assert!(detect_databases(["tokio"].into_iter()).is_empty());
+    }
+}
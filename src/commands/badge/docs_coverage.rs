@@ -0,0 +1,161 @@
+//! Generate documentation coverage badge.
+
+use anyhow::{
+    Context,
+    Result,
+};
+
+use super::common::{
+    self,
+    Badge,
+    CacheOptions,
+    SkipReason,
+};
+use crate::commands::common::CargoOptions;
+
+/// Show the documentation coverage badge.
+pub async fn badge_docs_coverage(
+    package: &cargo_metadata::Package,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+    // Use ephemeral status (cyan) for subprocess operations
+    logger.status("Generating", "docs coverage badge");
+
+    let coverage = match get_docs_coverage_percentage(&mut logger, package, cargo, cache).await? {
+        Ok(coverage) => coverage,
+        Err(reason) => return Ok(Err(reason)),
+    };
+
+    let color = common::coverage_color(coverage);
+    let badge_url = common::badge_url(
+        base_url,
+        &format!("badge/docs%20coverage-{}%25-{}", coverage, color),
+    );
+
+    let link_target = if common::guess_if_published(package).await? {
+        format!("https://docs.rs/{}", package.name)
+    } else {
+        "src/lib.rs".to_string()
+    };
+
+    Ok(Ok(Badge::new(
+        "docs-coverage",
+        "Docs Coverage",
+        &badge_url,
+        &link_target,
+    )))
+}
+
+/// Get documentation coverage percentage using rustdoc's `--show-coverage`.
+///
+/// Requires a nightly toolchain. Returns the skip reason when nightly or the
+/// unstable flag is unavailable. Uses cache if available and valid.
+async fn get_docs_coverage_percentage(
+    logger: &mut cargo_plugin_utils::logger::Logger,
+    package: &cargo_metadata::Package,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+) -> Result<Result<u8, SkipReason>> {
+    // Try to load from cache first
+    if let Some(cached) =
+        common::load_cache_if_fresh::<u8>("docs-coverage", package, common::LOCAL_CACHE_TTL, cache)
+            .await?
+    {
+        return Ok(Ok(cached));
+    }
+
+    let package_name = package.name.clone();
+    let output = cargo_plugin_utils::logger::run_subprocess(
+        logger,
+        move || {
+            let mut cmd = common::cargo_command(cargo);
+            cmd.arg("+nightly");
+            cmd.arg("rustdoc");
+            cmd.arg("--package");
+            cmd.arg(package_name.as_str());
+            cmd.arg("--");
+            cmd.arg("-Z");
+            cmd.arg("unstable-options");
+            cmd.arg("--show-coverage");
+            cmd.arg("--output-format");
+            cmd.arg("json");
+            cmd
+        },
+        None,
+    )
+    .await?;
+
+    // Nightly toolchain or unstable flag not available - stay silent
+    if !output.success() {
+        return Ok(Err(SkipReason::new(
+            "`cargo +nightly rustdoc --show-coverage` failed (is a nightly toolchain installed?)",
+        )));
+    }
+
+    let stdout = output
+        .stdout_str()
+        .context("Failed to parse rustdoc coverage output")?;
+
+    let Some(coverage) = parse_docs_coverage(&stdout) else {
+        return Ok(Err(SkipReason::new(
+            "rustdoc's coverage report lists no documentable items",
+        )));
+    };
+
+    common::save_cache("docs-coverage", package, &coverage).await?;
+    Ok(Ok(coverage))
+}
+
+/// Parse rustdoc's JSON coverage report into a percentage.
+///
+/// The report maps each source file to its item counts:
+/// `{"src/lib.rs": {"total": 10, "with_docs": 8, ...}, ...}`
+fn parse_docs_coverage(stdout: &str) -> Option<u8> {
+    let json = stdout.lines().find_map(|line| {
+        serde_json::from_str::<serde_json::Value>(line.trim())
+            .ok()
+            .filter(|value| value.is_object())
+    })?;
+
+    let (total, with_docs) =
+        json.as_object()?
+            .values()
+            .fold((0u64, 0u64), |(total, with_docs), file| {
+                (
+                    total + file.get("total").and_then(|t| t.as_u64()).unwrap_or(0),
+                    with_docs + file.get("with_docs").and_then(|d| d.as_u64()).unwrap_or(0),
+                )
+            });
+
+    if total == 0 {
+        return None;
+    }
+
+    Some(((with_docs as f64 / total as f64) * 100.0).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_docs_coverage() {
+        let stdout = r#"{"src/lib.rs":{"total":10,"with_docs":8,"total_examples":2,"with_examples":1},"src/util.rs":{"total":10,"with_docs":5,"total_examples":0,"with_examples":0}}"#;
+        assert_eq!(parse_docs_coverage(stdout), Some(65));
+    }
+
+    #[test]
+    fn test_parse_docs_coverage_skips_noise() {
+        let stdout = "warning: something\n{\"src/lib.rs\":{\"total\":4,\"with_docs\":4}}\n";
+        assert_eq!(parse_docs_coverage(stdout), Some(100));
+    }
+
+    #[test]
+    fn test_parse_docs_coverage_no_items() {
+        assert_eq!(parse_docs_coverage("{}"), None);
+        assert_eq!(parse_docs_coverage("not json"), None);
+    }
+}
@@ -1,60 +1,98 @@
 //! Generate docs.rs badge.
 
-use std::io::Write;
+use std::time::Duration;
 
 use anyhow::{
     Context,
     Result,
 };
 
-use super::common::guess_if_published;
+use super::common::{
+    self,
+    Badge,
+    CacheOptions,
+    SkipReason,
+    guess_if_published,
+};
 
 /// Check if crate is published on docs.rs.
 ///
 /// Uses HTTP request when `no_network` is false, otherwise uses heuristics.
+/// Network results are cached for [`common::NETWORK_CACHE_TTL`].
 async fn is_published_on_docs_rs(
     package_name: &str,
     package: &cargo_metadata::Package,
     no_network: bool,
+    timeout: Option<Duration>,
+    cache: CacheOptions,
 ) -> Result<bool> {
     if no_network {
         guess_if_published(package).await
     } else {
+        if let Some(published) = common::load_cache_if_fresh::<bool>(
+            "docs-rs-published",
+            package,
+            common::NETWORK_CACHE_TTL,
+            cache,
+        )
+        .await?
+        {
+            return Ok(published);
+        }
+
         let docs_url = format!("https://docs.rs/{}", package_name);
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(5))
-            .build()
-            .context("Failed to create HTTP client")?;
-
-        let response = client
-            .head(&docs_url)
-            .send()
+        let client = common::http_client(timeout)?;
+        let response = common::send_with_retry(client.head(&docs_url), timeout)
             .await
             .context("Failed to check docs.rs")?;
 
-        Ok(response.status().is_success())
+        let published = response.status().is_success();
+        common::save_cache("docs-rs-published", package, &published).await?;
+        Ok(published)
     }
 }
 
 /// Show the docs.rs badge if the project is published there.
 pub async fn badge_rustdocs(
-    writer: &mut dyn Write,
     package: &cargo_metadata::Package,
     no_network: bool,
-) -> Result<()> {
+    timeout: Option<Duration>,
+    cache: CacheOptions,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
     logger.status("Generating", "docs.rs badge");
 
     let package_name = &package.name;
 
-    if is_published_on_docs_rs(package_name, package, no_network).await? {
-        let badge_url = format!("https://img.shields.io/docsrs/{}", package_name);
-        let badge_markdown = format!(
-            "[![docs.rs]({})](https://docs.rs/{})",
-            badge_url, package_name
-        );
-        writeln!(writer, "{}", badge_markdown)?;
+    // A 404 means unpublished; a network failure means we don't know, so
+    // skip this badge without failing the others
+    let published =
+        match is_published_on_docs_rs(package_name, package, no_network, timeout, cache).await {
+            Ok(published) => published,
+            Err(error) if common::is_network_error(&error) => {
+                logger.warning("Skipping", &format!("docs.rs badge: {:#}", error));
+                return Ok(Err(SkipReason::new(format!(
+                    "docs.rs could not be reached: {:#}",
+                    error
+                ))));
+            }
+            Err(error) => return Err(error),
+        };
+
+    if published {
+        let badge_url = common::badge_url(base_url, &format!("docsrs/{}", package_name));
+        let link = format!("https://docs.rs/{}", package_name);
+        return Ok(Ok(Badge::new("rustdocs", "docs.rs", &badge_url, &link)));
     }
 
-    Ok(())
+    if no_network {
+        Ok(Err(SkipReason::new(
+            "the crate does not look published (--no-network heuristics)",
+        )))
+    } else {
+        Ok(Err(SkipReason::new(
+            "the crate is not published on docs.rs",
+        )))
+    }
 }
@@ -0,0 +1,41 @@
+//! Generate features count badge.
+
+use anyhow::Result;
+
+use super::common::{
+    self,
+    Badge,
+    SkipReason,
+};
+
+/// Show the number of Cargo features badge.
+///
+/// Counts the features declared in `[features]`, excluding `default`. When
+/// the crate declares no features, the badge is skipped.
+pub async fn badge_features(
+    package: &cargo_metadata::Package,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+    logger.status("Generating", "features badge");
+
+    let count = package
+        .features
+        .keys()
+        .filter(|name| name.as_str() != "default")
+        .count();
+
+    if count > 0 {
+        let badge_url = common::badge_url(base_url, &format!("badge/features-{}-blue", count));
+        return Ok(Ok(Badge::new(
+            "features",
+            "Features",
+            &badge_url,
+            "Cargo.toml",
+        )));
+    }
+
+    Ok(Err(SkipReason::new(
+        "no features declared in `[features]` (besides `default`)",
+    )))
+}
@@ -1,14 +1,18 @@
 //! Generate framework badge.
 
-use std::io::Write;
-
 use anyhow::Result;
 
+use super::common::{
+    self,
+    Badge,
+    SkipReason,
+};
+
 /// Show the framework badge.
 pub async fn badge_framework(
-    writer: &mut dyn Write,
     package: &cargo_metadata::Package,
-) -> Result<()> {
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
     logger.status("Generating", "framework badge");
 
@@ -16,14 +20,17 @@ pub async fn badge_framework(
     let has_axum = package.dependencies.iter().any(|dep| dep.name == "axum");
 
     if has_axum {
-        let badge_url = "https://img.shields.io/badge/web%20framework-Axum-blueviolet";
-        let badge_markdown = format!(
-            "[![Framework]({})](docs/adr/0008-web-framework-axum.typ)",
-            badge_url
-        );
-        writeln!(writer, "{}", badge_markdown)?;
+        let badge_url = common::badge_url(base_url, "badge/web%20framework-Axum-blueviolet");
+        return Ok(Ok(Badge::new(
+            "framework",
+            "Framework",
+            badge_url,
+            "docs/adr/0008-web-framework-axum.typ",
+        )));
     }
     // Future: add other frameworks (actix-web, warp, etc.)
 
-    Ok(())
+    Ok(Err(SkipReason::new(
+        "no supported web framework dependency (axum)",
+    )))
 }
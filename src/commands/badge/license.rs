@@ -1,26 +1,221 @@
 //! Generate license badge.
 
-use std::io::Write;
-
 use anyhow::Result;
 
+use super::common::{
+    self,
+    Badge,
+    SkipReason,
+};
+
 /// Show the license badge.
+///
+/// When `spdx_validate` is set, the license expression must parse as a valid
+/// SPDX expression; otherwise a warning is logged and the badge is skipped.
 pub async fn badge_license(
-    writer: &mut dyn Write,
     package: &cargo_metadata::Package,
-) -> Result<()> {
+    spdx_validate: bool,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
     logger.status("Generating", "license badge");
 
     if let Some(license) = &package.license {
-        let license_encoded = license.replace(' ', "%20");
-        let badge_url = format!("https://img.shields.io/crates/l/{}", license_encoded);
-        let badge_markdown = format!(
-            "[![license]({})](https://opensource.org/licenses/{})",
-            badge_url, license_encoded
+        if spdx_validate && let Err(e) = parse_spdx_expression(license) {
+            logger.warning(
+                "Skipping",
+                &format!(
+                    "license badge: invalid SPDX expression '{}': {}",
+                    license, e
+                ),
+            );
+            return Ok(Err(SkipReason::new(format!(
+                "`{}` is not a valid SPDX expression: {}",
+                license, e
+            ))));
+        }
+
+        let badge_url = common::badge_url(base_url, &format!("crates/l/{}", package.name));
+        let license_file = find_license_file(package).await;
+        let link = license_link(license, license_file.as_deref());
+        return Ok(Ok(Badge::new("license", "license", &badge_url, &link)));
+    }
+
+    Ok(Err(SkipReason::new("no `license` field in Cargo.toml")))
+}
+
+/// Find the license file to link to for compound license expressions.
+///
+/// Prefers the `license-file` manifest field, then a `LICENSE` file next to
+/// the manifest.
+async fn find_license_file(package: &cargo_metadata::Package) -> Option<String> {
+    if let Some(license_file) = &package.license_file {
+        return Some(license_file.to_string());
+    }
+
+    let manifest_dir = package.manifest_path.as_std_path().parent()?;
+    for name in ["LICENSE", "LICENSE.md", "LICENSE.txt"] {
+        if tokio::fs::metadata(manifest_dir.join(name)).await.is_ok() {
+            return Some(name.to_string());
+        }
+    }
+
+    None
+}
+
+/// Build the link target for a license badge.
+///
+/// A single license links to its opensource.org page. Compound expressions
+/// (`OR`, `AND`, `WITH`) have no such page, so they link to the license file
+/// if one exists, otherwise to `Cargo.toml`.
+fn license_link(license: &str, license_file: Option<&str>) -> String {
+    let tokens = tokenize_spdx(license);
+    if let [single] = tokens.as_slice() {
+        let id = single.strip_suffix('+').unwrap_or(single);
+        return format!("https://opensource.org/licenses/{}", id);
+    }
+
+    license_file.unwrap_or("Cargo.toml").to_string()
+}
+
+/// Parse an SPDX license expression and return the license identifiers it
+/// references.
+///
+/// Identifiers are checked against the full SPDX license and exception lists.
+/// Cargo's legacy `/` separator is accepted as a synonym for `OR`, and the
+/// deprecated `+` suffix is accepted on GNU licenses.
+///
+/// # Errors
+///
+/// Returns an error for unknown identifiers or malformed expressions.
+pub fn parse_spdx_expression(expression: &str) -> Result<Vec<String>> {
+    let mode = spdx::ParseMode {
+        allow_slash_as_or_operator: true,
+        allow_postfix_plus_on_gpl: true,
+        ..spdx::ParseMode::STRICT
+    };
+    let parsed = spdx::Expression::parse_mode(expression, mode).map_err(|e| {
+        match e.original.get(e.span.clone()).filter(|t| !t.is_empty()) {
+            Some(token) => anyhow::anyhow!("{} '{}'", e.reason, token),
+            None => anyhow::anyhow!("{}", e.reason),
+        }
+    })?;
+
+    // Report identifiers as written; the parsed form folds GNU `-only` and
+    // `-or-later` variants into their root license.
+    Ok(parsed
+        .requirements()
+        .map(|requirement| {
+            let span = requirement.span.start as usize..requirement.span.end as usize;
+            expression[span].to_string()
+        })
+        .collect())
+}
+
+/// Split an SPDX expression into identifiers, operators, and parentheses.
+fn tokenize_spdx(expression: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+
+    for c in expression.chars() {
+        match c {
+            '(' | ')' | '/' => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                tokens.push(c.to_string());
+            }
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_license() {
+        assert_eq!(parse_spdx_expression("MIT").unwrap(), vec!["MIT"]);
+    }
+
+    #[test]
+    fn test_parse_compound_expression() {
+        assert_eq!(
+            parse_spdx_expression("MIT OR Apache-2.0").unwrap(),
+            vec!["MIT", "Apache-2.0"]
+        );
+        assert_eq!(
+            parse_spdx_expression("(MIT OR Apache-2.0) AND Unicode-3.0").unwrap(),
+            vec!["MIT", "Apache-2.0", "Unicode-3.0"]
+        );
+        assert_eq!(
+            parse_spdx_expression("Apache-2.0 WITH LLVM-exception").unwrap(),
+            vec!["Apache-2.0"]
+        );
+    }
+
+    #[test]
+    fn test_parse_legacy_slash_separator() {
+        assert_eq!(
+            parse_spdx_expression("MIT/Apache-2.0").unwrap(),
+            vec!["MIT", "Apache-2.0"]
         );
-        writeln!(writer, "{}", badge_markdown)?;
     }
 
-    Ok(())
+    #[test]
+    fn test_parse_full_spdx_list() {
+        assert_eq!(parse_spdx_expression("Vim").unwrap(), vec!["Vim"]);
+        assert_eq!(
+            parse_spdx_expression("GPL-2.0-or-later WITH Font-exception-2.0").unwrap(),
+            vec!["GPL-2.0-or-later"]
+        );
+        assert_eq!(
+            parse_spdx_expression("LicenseRef-Proprietary").unwrap(),
+            vec!["LicenseRef-Proprietary"]
+        );
+        assert_eq!(parse_spdx_expression("GPL-2.0+").unwrap(), vec!["GPL-2.0"]);
+    }
+
+    #[test]
+    fn test_license_link_single() {
+        assert_eq!(
+            license_link("MIT", Some("LICENSE")),
+            "https://opensource.org/licenses/MIT"
+        );
+        assert_eq!(
+            license_link("GPL-2.0+", None),
+            "https://opensource.org/licenses/GPL-2.0"
+        );
+    }
+
+    #[test]
+    fn test_license_link_compound() {
+        assert_eq!(
+            license_link("MIT OR Apache-2.0", Some("LICENSE")),
+            "LICENSE"
+        );
+        assert_eq!(license_link("MIT OR Apache-2.0", None), "Cargo.toml");
+        assert_eq!(license_link("MIT/Apache-2.0", None), "Cargo.toml");
+    }
+
+    #[test]
+    fn test_parse_invalid_expressions() {
+        assert!(parse_spdx_expression("").is_err());
+        assert!(parse_spdx_expression("NotALicense").is_err());
+        assert!(parse_spdx_expression("MIT OR").is_err());
+        assert!(parse_spdx_expression("(MIT OR Apache-2.0").is_err());
+        assert!(parse_spdx_expression("MIT Apache-2.0").is_err());
+        assert!(parse_spdx_expression("MIT WITH NotAnException").is_err());
+    }
 }
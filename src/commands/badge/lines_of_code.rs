@@ -0,0 +1,190 @@
+//! Generate lines of code badge.
+
+use anyhow::{
+    Context,
+    Result,
+};
+use portable_pty::CommandBuilder;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::common::{
+    self,
+    Badge,
+    CacheOptions,
+    SkipReason,
+};
+
+/// Language counted when no `--languages` filter is given.
+const DEFAULT_LANGUAGE: &str = "Rust";
+
+/// Show the lines of code badge (requires tokei).
+///
+/// Sums the code lines (excluding comments and blanks) that tokei reports
+/// for `languages` in the package directory. An empty `languages` counts
+/// Rust only. When tokei is not installed, the badge is skipped.
+pub async fn badge_lines_of_code(
+    package: &cargo_metadata::Package,
+    languages: &[String],
+    cache: CacheOptions,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+    // Use ephemeral status (cyan) for subprocess operations
+    logger.status("Generating", "lines of code badge");
+
+    let languages = if languages.is_empty() {
+        vec![DEFAULT_LANGUAGE.to_string()]
+    } else {
+        languages.to_vec()
+    };
+
+    let lines = match get_lines_of_code(&mut logger, package, &languages, cache).await? {
+        Ok(lines) => lines,
+        Err(reason) => return Ok(Err(reason)),
+    };
+
+    let badge_url = common::badge_url(base_url, &format!("badge/lines%20of%20code-{}-blue", lines));
+    Ok(Ok(Badge::new(
+        "lines-of-code",
+        "Lines of Code",
+        &badge_url,
+        "src/",
+    )))
+}
+
+/// Cached lines of code result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LinesOfCodeCache {
+    /// Languages that were counted
+    languages: Vec<String>,
+    /// Number of code lines
+    lines: u64,
+}
+
+/// Count code lines using tokei.
+/// Uses cache if available and valid.
+async fn get_lines_of_code(
+    logger: &mut cargo_plugin_utils::logger::Logger,
+    package: &cargo_metadata::Package,
+    languages: &[String],
+    cache: CacheOptions,
+) -> Result<Result<u64, SkipReason>> {
+    // Try to load from cache first
+    if let Some(cached) = common::load_cache_if_fresh::<LinesOfCodeCache>(
+        "lines-of-code",
+        package,
+        common::LOCAL_CACHE_TTL,
+        cache,
+    )
+    .await?
+        && cached.languages == languages
+    {
+        return Ok(Ok(cached.lines));
+    }
+
+    // Check if tokei is available; stay silent if it is not
+    let version_output = cargo_plugin_utils::logger::run_subprocess(
+        logger,
+        || {
+            let mut cmd = CommandBuilder::new("tokei");
+            cmd.arg("--version");
+            cmd
+        },
+        None,
+    )
+    .await;
+    if !version_output.is_ok_and(|output| output.success()) {
+        return Ok(Err(SkipReason::new("tokei is not installed")));
+    }
+
+    let package_dir = package
+        .manifest_path
+        .parent()
+        .map(|dir| dir.to_string())
+        .unwrap_or_else(|| ".".to_string());
+    let output = cargo_plugin_utils::logger::run_subprocess(
+        logger,
+        move || {
+            let mut cmd = CommandBuilder::new("tokei");
+            cmd.arg("--output");
+            cmd.arg("json");
+            cmd.arg(package_dir.as_str());
+            cmd
+        },
+        None,
+    )
+    .await?;
+
+    if !output.success() {
+        return Ok(Err(SkipReason::new("tokei failed")));
+    }
+
+    let stdout = output
+        .stdout_str()
+        .context("Failed to parse tokei output")?;
+    let Some(lines) = count_code_lines(&stdout, languages) else {
+        return Ok(Err(SkipReason::new(format!(
+            "tokei found no {} code",
+            languages.join(", ")
+        ))));
+    };
+
+    // Save to cache
+    let cache = LinesOfCodeCache {
+        languages: languages.to_vec(),
+        lines,
+    };
+    common::save_cache("lines-of-code", package, &cache).await?;
+    Ok(Ok(lines))
+}
+
+/// Sum the `code` counts for `languages` in tokei's JSON output.
+///
+/// tokei reports one object per language, keyed by language name:
+/// `{"Rust": {"code": 1234, ...}, "TOML": {...}, "Total": {...}}`. Language
+/// names are matched case-insensitively. Returns `None` if the output is not
+/// valid JSON or none of the languages are present.
+fn count_code_lines(output: &str, languages: &[String]) -> Option<u64> {
+    let json: serde_json::Value = serde_json::from_str(output).ok()?;
+    let stats = json.as_object()?;
+
+    let counts: Vec<u64> = stats
+        .iter()
+        .filter(|(name, _)| {
+            languages
+                .iter()
+                .any(|language| language.eq_ignore_ascii_case(name))
+        })
+        .filter_map(|(_, language)| language.get("code").and_then(|code| code.as_u64()))
+        .collect();
+
+    if counts.is_empty() {
+        return None;
+    }
+    Some(counts.iter().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_code_lines() {
+        let output = r#"{
+            "Rust": {"blanks": 10, "code": 120, "comments": 5},
+            "TOML": {"blanks": 1, "code": 30, "comments": 0},
+            "Total": {"blanks": 11, "code": 150, "comments": 5}
+        }"#;
+
+        assert_eq!(count_code_lines(output, &["rust".to_string()]), Some(120));
+        assert_eq!(
+            count_code_lines(output, &["Rust".to_string(), "TOML".to_string()]),
+            Some(150)
+        );
+        assert_eq!(count_code_lines(output, &["Python".to_string()]), None);
+        assert_eq!(count_code_lines("not json", &["Rust".to_string()]), None);
+    }
+}
@@ -0,0 +1,117 @@
+//! Generate maintenance status badge.
+
+use anyhow::Result;
+
+use super::common::{
+    self,
+    Badge,
+    SkipReason,
+};
+
+/// Show the maintenance status badge.
+///
+/// Reads `[badges] maintenance = { status = "..." }` from the manifest. When
+/// no status is declared (or the status is `none`), the badge is skipped.
+pub async fn badge_maintenance(
+    package: &cargo_metadata::Package,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+    logger.status("Generating", "maintenance badge");
+
+    let manifest_path = package.manifest_path.as_std_path();
+    let Some(status) = read_maintenance_status(manifest_path).await else {
+        return Ok(Err(SkipReason::new(
+            "no `[badges] maintenance` status declared in Cargo.toml",
+        )));
+    };
+
+    if let Some(color) = maintenance_color(&status) {
+        let badge_url = common::badge_url(
+            base_url,
+            &format!("badge/maintenance-{}-{}", status.replace('-', "%20"), color),
+        );
+        return Ok(Ok(Badge::new(
+            "maintenance",
+            "Maintenance",
+            &badge_url,
+            "Cargo.toml",
+        )));
+    }
+
+    Ok(Err(SkipReason::new(format!(
+        "maintenance status `{}` has no badge",
+        status
+    ))))
+}
+
+/// Read the maintenance status from the `[badges]` table of a manifest.
+async fn read_maintenance_status(manifest: &std::path::Path) -> Option<String> {
+    let contents = tokio::fs::read_to_string(manifest).await.ok()?;
+    let value: toml::Value = toml::from_str(&contents).ok()?;
+    value
+        .get("badges")
+        .and_then(|badges| badges.get("maintenance"))
+        .and_then(|maintenance| maintenance.get("status"))
+        .and_then(|status| status.as_str())
+        .map(ToString::to_string)
+}
+
+/// Map a maintenance status to a badge color.
+///
+/// Returns `None` for `none` and unknown statuses.
+fn maintenance_color(status: &str) -> Option<&'static str> {
+    match status {
+        "actively-developed" => Some("brightgreen"),
+        "passively-maintained" => Some("yellowgreen"),
+        "as-is" => Some("yellow"),
+        "experimental" => Some("blue"),
+        "looking-for-maintainer" => Some("orange"),
+        "deprecated" => Some("red"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_maintenance_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            r#"
+[package]
+name = "test"
+version = "0.1.0"
+
+[badges]
+maintenance = { status = "actively-developed" }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_maintenance_status(&manifest).await.as_deref(),
+            Some("actively-developed")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_maintenance_status_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(&manifest, "[package]\nname = \"test\"\n").unwrap();
+
+        assert_eq!(read_maintenance_status(&manifest).await, None);
+    }
+
+    #[test]
+    fn test_maintenance_color() {
+        assert_eq!(maintenance_color("actively-developed"), Some("brightgreen"));
+        assert_eq!(maintenance_color("deprecated"), Some("red"));
+        assert_eq!(maintenance_color("none"), None);
+    }
+}
@@ -9,24 +9,45 @@
 //! # Generate all badges
 //! cargo version-info badge all
 //!
+//! # Generate all badges for a workspace member
+//! cargo version-info badge --package my-crate all
+//!
+//! # Generate only some badges
+//! cargo version-info badge all --only coverage,number-of-tests
+//!
+//! # Explain which badges `badge all` emits and why others are skipped
+//! cargo version-info badge all --explain
+//!
 //! # Generate docs.rs badge (only if published)
 //! cargo version-info badge rustdocs
 //!
 //! # Generate crates.io badge (only if published)
 //! cargo version-info badge cratesio
 //!
+//! # Check an alternative registry's sparse index instead of crates.io
+//! cargo version-info badge cratesio --registry-api sparse+https://registry.example.com/index/
+//!
 //! # Generate license badge
 //! cargo version-info badge license
 //!
+//! # Only emit the license badge for a valid SPDX expression
+//! cargo version-info badge license --spdx-validate
+//!
 //! # Generate Rust edition badge
 //! cargo version-info badge rust-edition
 //!
+//! # Generate features count badge
+//! cargo version-info badge features
+//!
 //! # Generate runtime badge
 //! cargo version-info badge runtime
 //!
 //! # Generate framework badge
 //! cargo version-info badge framework
 //!
+//! # Generate database badges (SQLx, Diesel, SeaORM)
+//! cargo version-info badge database
+//!
 //! # Generate platform badge
 //! cargo version-info badge platform
 //!
@@ -36,39 +57,130 @@
 //! # Generate coverage badge (requires cargo-llvm-cov)
 //! cargo version-info badge coverage
 //!
+//! # Measure coverage with cargo-tarpaulin instead
+//! cargo version-info badge coverage --coverage-tool tarpaulin
+//!
+//! # Use a coverage report generated by an earlier CI step
+//! cargo version-info badge coverage --coverage-file lcov.info
+//!
+//! # Emit the badge and fail (exit code 12) below 80% coverage
+//! cargo version-info badge coverage --threshold 80
+//!
 //! # Generate number of tests badge
 //! cargo version-info badge number-of-tests
+//! cargo version-info badge number-of-tests --include-doctests
+//!
+//! # Generate documentation coverage badge (requires nightly)
+//! cargo version-info badge docs-coverage
+//!
+//! # Generate lines of code badge (requires tokei)
+//! cargo version-info badge lines-of-code
+//! cargo version-info badge lines-of-code --languages rust,toml
+//!
+//! # Generate release binary size badge (builds with --release)
+//! cargo version-info badge binary-size
+//!
+//! # Generate maintenance status badge (from [badges] in Cargo.toml)
+//! cargo version-info badge maintenance
+//!
+//! # Check the version bump against the API (requires cargo-semver-checks)
+//! cargo version-info badge semver
+//!
+//! # Generate serialization badge (Serde and formats)
+//! cargo version-info badge serialization
 //!
 //! # Use heuristics instead of network requests
 //! cargo version-info badge all --no-network
 //! cargo version-info badge rustdocs --no-network
+//!
+//! # Render badges as AsciiDoc instead of Markdown
+//! cargo version-info badge all --format asciidoc
+//!
+//! # Badge data for README sync tooling, keyed by stable badge kind
+//! cargo version-info badge all --format json --with-kind
+//!
+//! # Fail (and say why) if a badge cannot be produced
+//! cargo version-info badge coverage --fail-on-missing
+//!
+//! # Use a self-hosted shields instance
+//! cargo version-info badge all --badge-base-url https://shields.example.com
+//!
+//! # Absolute badge links for READMEs rendered on crates.io
+//! cargo version-info badge all --link-base https://github.com/owner/repo/blob/main
+//!
+//! # List the available badges and what they need (network, tools)
+//! cargo version-info badge --list
+//!
+//! # Recompute cached results (test count, coverage, crates.io lookups, ...)
+//! cargo version-info badge all --no-cache
+//! cargo version-info badge all --cache-ttl 3600
 //! ```
 
 mod adrs;
 mod all;
+mod binary_size;
 mod common;
-mod coverage;
+pub(crate) mod coverage;
 mod crates_io;
+mod database;
+mod docs_coverage;
 mod docs_rs;
+mod features;
 mod framework;
 mod license;
+mod lines_of_code;
+mod maintenance;
 mod number_of_tests;
 mod platform;
 mod runtime;
 mod rust_edition;
+mod semver;
+mod serialization;
 
 use std::io::Write;
+use std::path::PathBuf;
 
 // Re-export for use by other commands (like release_page)
-pub use all::badge_all;
+pub use all::{
+    BADGE_NAMES,
+    BadgeOptions,
+    badge_all,
+    generate_badges,
+};
 use anyhow::{
     Context,
     Result,
 };
 use clap::{
+    CommandFactory,
     Parser,
     Subcommand,
+    ValueEnum,
+};
+pub use common::{
+    Badge,
+    BadgeFormat,
+    CacheOptions,
 };
+pub use coverage::CoverageTool;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use self::all::BadgeOutcome;
+use crate::commands::common::CargoOptions;
+
+/// Output format of the `badge` command (`--format`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BadgeOutputFormat {
+    /// Markdown image links.
+    #[value(alias = "md")]
+    Markdown,
+    /// AsciiDoc inline image macros.
+    #[value(alias = "adoc")]
+    Asciidoc,
+    /// JSON array of badge objects.
+    Json,
+}
 
 /// Arguments for the `badge` command.
 #[derive(Parser, Debug)]
@@ -82,102 +194,543 @@ pub struct BadgeArgs {
     #[arg(long)]
     pub no_network: bool,
 
-    /// The badge subcommand to execute.
+    /// Workspace member to generate badges for (standard cargo flag).
+    ///
+    /// Defaults to the package in the current directory.
+    #[arg(long, short = 'p', global = true, value_name = "NAME")]
+    pub package: Option<String>,
+
+    /// Output format for the badges.
+    ///
+    /// - `markdown`: Markdown image links (default)
+    /// - `asciidoc`: AsciiDoc inline image macros
+    /// - `json`: JSON array of `{name, alt, image_url, link}` objects
+    #[arg(long, global = true, value_enum, default_value = "markdown")]
+    pub format: BadgeOutputFormat,
+
+    /// Add `kind` and `markdown` to each badge in `--format json` output.
+    ///
+    /// `kind` is a stable identifier of the badge type, one of
+    /// [`BADGE_NAMES`]: `rustdocs`, `cratesio`, `license`, `maintenance`,
+    /// `rust-edition`, `features`, `runtime`, `framework`, `database`,
+    /// `platform`, `adrs`, `coverage`, `docs-coverage`, `lines-of-code`,
+    /// `number-of-tests`, `binary-size`, `semver`, `serialization`.
+    /// `markdown` is the rendered badge and `image_url` the raw badge image
+    /// URL, so README tooling can replace badges by kind without parsing
+    /// Markdown.
+    #[arg(long, global = true)]
+    pub with_kind: bool,
+
+    /// Fail when the requested badge cannot be produced.
+    ///
+    /// By default a badge that does not apply (unpublished crate, no license
+    /// field, missing cargo-llvm-cov, ...) is silently omitted. With this
+    /// flag the command exits non-zero and explains why; for `badge all`,
+    /// every skipped badge is listed.
+    #[arg(long, global = true)]
+    pub fail_on_missing: bool,
+
+    /// Treat subprocess failures as errors.
+    ///
+    /// When `cargo test --no-run` fails, the number-of-tests badge is
+    /// normally skipped with a warning showing cargo's output. With this
+    /// flag the command fails instead.
+    #[arg(long, global = true)]
+    pub verbose: bool,
+
+    /// Base URL of the badge image service.
+    ///
+    /// Replaces `https://img.shields.io` in every badge, for self-hosted
+    /// shields instances or proxies. Can also be set with `badge-base-url`
+    /// under `[package.metadata.version-info]` in Cargo.toml.
+    #[arg(long, global = true, value_name = "URL")]
+    pub badge_base_url: Option<String>,
+
+    /// Base URL for repo-relative badge links.
+    ///
+    /// Links such as `Cargo.toml`, `tests/` or `docs/adr/index.typ` become
+    /// `<URL>/Cargo.toml`, ..., so badges work where the README is rendered
+    /// outside the repository (e.g. crates.io). Use the repository's blob
+    /// URL, e.g. `https://github.com/owner/repo/blob/main`. Absolute links
+    /// are unchanged.
+    #[arg(long, global = true, value_name = "URL")]
+    pub link_base: Option<String>,
+
+    /// Check publication in an alternative registry instead of crates.io.
+    ///
+    /// Either the base URL of a registry with a crates.io-compatible web API
+    /// (`https://registry.example.com`, queried at `/api/v1/crates/<name>`)
+    /// or a sparse index as configured for cargo
+    /// (`sparse+https://registry.example.com/index/`). The crates.io badge
+    /// then shows the registry's latest version and links to the registry.
+    #[arg(long, global = true, value_name = "URL")]
+    pub registry_api: Option<String>,
+
+    /// Maximum age of cached badge results, in seconds.
+    ///
+    /// Overrides each badge's own TTL (a week for results tied to the current
+    /// commit, an hour for crates.io/docs.rs lookups).
+    #[arg(long, global = true, value_name = "SECONDS")]
+    pub cache_ttl: Option<u64>,
+
+    /// Ignore cached badge results and recompute everything.
+    ///
+    /// Fresh results are still written to the cache.
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// List the available badges instead of generating one.
+    ///
+    /// Prints each badge kind with a one-line description and what it needs:
+    /// network access (crates.io, docs.rs) or an external tool
+    /// (cargo-llvm-cov, tokei, ...). Needs no package or network.
+    #[arg(long, alias = "list-badges")]
+    pub list: bool,
+
+    /// How cargo may access the network, from the global `--offline` and
+    /// `--frozen` flags.
+    #[arg(skip)]
+    pub cargo: CargoOptions,
+
+    /// Timeout of each crates.io/docs.rs request, from the global
+    /// `--timeout` flag.
+    #[arg(skip)]
+    pub timeout: Option<std::time::Duration>,
+
+    /// The badge subcommand to execute (required unless `--list` is given).
     #[command(subcommand)]
-    pub subcommand: BadgeSubcommand,
+    pub subcommand: Option<BadgeSubcommand>,
 }
 
 /// Subcommands for the badge command.
 #[derive(Subcommand, Debug)]
 pub enum BadgeSubcommand {
     /// Generate all badges (including rustdocs and cratesio if published).
-    All,
+    ///
+    /// Restrict the set with `badges = [...]` under
+    /// `[package.metadata.version-info]` in Cargo.toml, or with `--only`.
+    All {
+        /// Only generate these badges (comma-separated), overriding the
+        /// allowlist in Cargo.toml.
+        #[arg(long, value_delimiter = ',', value_name = "BADGES")]
+        only: Vec<String>,
+
+        /// Report for every badge whether it is emitted, and why not,
+        /// instead of printing the badges.
+        ///
+        /// Runs the same detection as generating the badges and reports the
+        /// reason each generator gives, so a sparse badge row can be
+        /// understood (e.g. `coverage: skipped, cargo-llvm-cov is not
+        /// installed`).
+        #[arg(long)]
+        explain: bool,
+    },
     /// Show the docs.rs badge if the project is published there, otherwise no
     /// output.
     Rustdocs,
     /// Show the crates.io badge if the project is published there, otherwise no
     /// output.
-    Cratesio,
+    Cratesio {
+        /// Show the badge even if every published version is yanked.
+        #[arg(long)]
+        include_yanked: bool,
+    },
     /// Show the license badge.
-    License,
+    License {
+        /// Validate the license as an SPDX expression and skip the badge
+        /// (with a warning) if it does not parse.
+        #[arg(long)]
+        spdx_validate: bool,
+    },
     /// Show the Rust edition badge.
     #[command(name = "rust-edition")]
     RustEdition,
+    /// Show the number of Cargo features badge (excluding `default`).
+    Features,
     /// Show the runtime badge (Tokio, etc.).
     Runtime,
     /// Show the framework badge (Axum, etc.).
     Framework,
+    /// Show a database badge per detected database crate (SQLx, Diesel,
+    /// SeaORM).
+    Database,
     /// Show the platform badge (Fly.io, Vercel, etc.).
+    ///
+    /// Set `platform = "..."` under `[package.metadata.version-info]` to
+    /// override detection.
     Platform,
-    /// Show the ADRs badge if docs/adr/ exists.
+    /// Show the ADRs badge if docs/adr/ exists in the package or workspace
+    /// root.
     ADRs,
-    /// Show the test coverage badge (requires cargo-llvm-cov).
-    Coverage,
+    /// Show the test coverage badge (requires cargo-llvm-cov or
+    /// cargo-tarpaulin).
+    Coverage {
+        /// Tool measuring coverage: `llvm-cov` (default) or `tarpaulin`.
+        #[arg(long, default_value = "llvm-cov")]
+        coverage_tool: String,
+
+        /// Read coverage from an existing `lcov.info` or Cobertura XML
+        /// report instead of running a coverage tool.
+        #[arg(long, value_name = "PATH")]
+        coverage_file: Option<PathBuf>,
+
+        /// Minimum coverage percentage.
+        ///
+        /// The badge is still emitted, but the command exits with code 12
+        /// when coverage is below the threshold (or cannot be measured).
+        #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+        threshold: Option<u8>,
+    },
     /// Show the number of tests badge.
     #[command(name = "number-of-tests")]
-    NumberOfTests,
+    NumberOfTests {
+        /// Add doctests (`cargo test --doc`) to the count.
+        #[arg(long)]
+        include_doctests: bool,
+    },
+    /// Show the documentation coverage badge (requires nightly rustdoc).
+    #[command(name = "docs-coverage")]
+    DocsCoverage,
+    /// Show the lines of code badge (requires tokei).
+    #[command(name = "lines-of-code")]
+    LinesOfCode {
+        /// Languages to count, as tokei names them (comma-separated).
+        /// Defaults to Rust.
+        #[arg(long, value_delimiter = ',')]
+        languages: Vec<String>,
+    },
+    /// Show the release binary size badge (runs `cargo build --release`).
+    ///
+    /// Not part of `badge all` unless listed in the badge allowlist.
+    #[command(name = "binary-size")]
+    BinarySize,
+    /// Show the maintenance status badge from `[badges]` in Cargo.toml.
+    Maintenance,
+    /// Show whether the version bump since the last crates.io release
+    /// matches the API changes (requires cargo-semver-checks).
+    ///
+    /// Not part of `badge all` unless listed in the badge allowlist.
+    Semver,
+    /// Show the serialization badge (Serde and detected formats such as
+    /// JSON or TOML).
+    ///
+    /// Not part of `badge all` unless listed in the badge allowlist.
+    Serialization,
+}
+
+/// What a badge needs besides the package itself.
+///
+/// Returns whether it queries the network and which external tool (if any)
+/// must be installed.
+fn badge_requirements(name: &str) -> (bool, Option<&'static str>) {
+    match name {
+        "rustdocs" | "cratesio" => (true, None),
+        "semver" => (true, Some("cargo-semver-checks")),
+        "coverage" => (false, Some("cargo-llvm-cov or cargo-tarpaulin")),
+        "docs-coverage" => (false, Some("nightly rustdoc")),
+        "lines-of-code" => (false, Some("tokei")),
+        _ => (false, None),
+    }
+}
+
+/// Write every badge kind with its description and requirements, for
+/// `badge --list`.
+///
+/// Descriptions are the `BadgeSubcommand` help texts, so the list cannot
+/// drift from the subcommands.
+fn write_badge_list(writer: &mut dyn Write) -> Result<()> {
+    let command = BadgeArgs::command();
+    writeln!(writer, "{:<17} {:<36} DESCRIPTION", "BADGE", "REQUIRES")?;
+    for name in BADGE_NAMES {
+        // Subcommand names are kebab-case (`ad-rs` for `ADRs`)
+        let description = command
+            .get_subcommands()
+            .find(|sub| sub.get_name().replace('-', "") == name.replace('-', ""))
+            .and_then(|sub| sub.get_about())
+            .map(|about| about.to_string())
+            .unwrap_or_default();
+
+        let (network, tool) = badge_requirements(name);
+        let mut requires: Vec<&str> = Vec::new();
+        if network {
+            requires.push("network");
+        }
+        requires.extend(tool);
+        if all::OPT_IN_BADGES.contains(name) {
+            requires.push("opt-in");
+        }
+        let requires = if requires.is_empty() {
+            "-".to_string()
+        } else {
+            requires.join(", ")
+        };
+
+        writeln!(writer, "{:<17} {:<36} {}", name, requires, description)?;
+    }
+    Ok(())
 }
 
 /// Generate badges for quality metrics.
 pub fn badge(args: BadgeArgs) -> Result<()> {
+    badge_to_writer(args, &mut std::io::stdout())
+}
+
+/// Generate badges for quality metrics, writing them to `writer`.
+pub fn badge_to_writer(args: BadgeArgs, writer: &mut dyn Write) -> Result<()> {
+    if args.list {
+        return write_badge_list(writer);
+    }
     let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
-    rt.block_on(badge_async(args))
+    rt.block_on(badge_async(args, writer))
 }
 
 /// Async entry point for badge generation.
-async fn badge_async(args: BadgeArgs) -> Result<()> {
+async fn badge_async(args: BadgeArgs, writer: &mut dyn Write) -> Result<()> {
+    let subcommand = args
+        .subcommand
+        .context("Missing badge subcommand (see `cargo version-info badge --list`)")?;
+
     // Create logger - status messages go to stderr, badges to stdout
     let mut logger = cargo_plugin_utils::logger::Logger::new();
 
     // Detect package from Cargo's context (working directory when
     // --manifest-path is used)
     logger.status("Checking", "package metadata");
-    let package = find_package().await?;
-
-    // Buffer all badge output to avoid mixing with stderr status lines
-    let mut buffer = Vec::new();
+    let package = match args.package.clone() {
+        Some(name) => tokio::task::spawn_blocking(move || {
+            crate::commands::common::find_workspace_member(&name, None, args.cargo)
+        })
+        .await
+        .context("Failed to spawn blocking task")??,
+        None => find_package(args.cargo).await?,
+    };
+    if args.with_kind && args.format != BadgeOutputFormat::Json {
+        anyhow::bail!("--with-kind requires --format json");
+    }
+    let cache = CacheOptions {
+        ttl: args.cache_ttl.map(std::time::Duration::from_secs),
+        no_cache: args.no_cache,
+    };
 
     // Drop the initial logger - each badge function creates its own
     drop(logger);
 
-    match args.subcommand {
-        BadgeSubcommand::All => {
+    // Reported after the badge has been written
+    let mut coverage_failure = None;
+    let base_url = common::badge_base_url(&package, args.badge_base_url.as_deref());
+
+    let outcomes = match subcommand {
+        BadgeSubcommand::All { only, explain } => {
             // Each badge function manages its own status logging via Drop
-            docs_rs::badge_rustdocs(&mut buffer, &package, args.no_network).await?;
-            crates_io::badge_cratesio(&mut buffer, &package, args.no_network).await?;
-            license::badge_license(&mut buffer, &package).await?;
-            rust_edition::badge_rust_edition(&mut buffer, &package).await?;
-            runtime::badge_runtime(&mut buffer, &package).await?;
-            framework::badge_framework(&mut buffer, &package).await?;
-            platform::badge_platform(&mut buffer, &package).await?;
-            adrs::badge_adrs(&mut buffer, &package).await?;
-            coverage::badge_coverage(&mut buffer, &package).await?;
-            number_of_tests::badge_number_of_tests(&mut buffer, &package).await?;
-
-            Ok(())
+            let options = BadgeOptions {
+                no_network: args.no_network,
+                verbose: args.verbose,
+                badge_base_url: args.badge_base_url.clone(),
+                link_base: args.link_base.clone(),
+                only,
+                registry_api: args.registry_api.clone(),
+                cache,
+                cargo: args.cargo,
+                timeout: args.timeout,
+                ..BadgeOptions::default()
+            };
+            let outcomes = all::evaluate_badges(&package, &options).await?;
+            if explain {
+                // Only the verdicts are reported; nothing is rendered
+                return all::write_explanation(writer, &package, &options.only, &outcomes);
+            }
+            outcomes
         }
-        BadgeSubcommand::Rustdocs => {
-            docs_rs::badge_rustdocs(&mut buffer, &package, args.no_network).await
+        BadgeSubcommand::Rustdocs => vec![BadgeOutcome::single(
+            "rustdocs",
+            docs_rs::badge_rustdocs(&package, args.no_network, args.timeout, cache, &base_url)
+                .await?,
+        )],
+        BadgeSubcommand::Cratesio { include_yanked } => vec![BadgeOutcome::single(
+            "cratesio",
+            crates_io::badge_cratesio(
+                &package,
+                args.no_network,
+                include_yanked,
+                args.registry_api.as_deref(),
+                args.timeout,
+                cache,
+                &base_url,
+            )
+            .await?,
+        )],
+        BadgeSubcommand::License { spdx_validate } => vec![BadgeOutcome::single(
+            "license",
+            license::badge_license(&package, spdx_validate, &base_url).await?,
+        )],
+        BadgeSubcommand::RustEdition => vec![BadgeOutcome::single(
+            "rust-edition",
+            rust_edition::badge_rust_edition(&package, &base_url).await?,
+        )],
+        BadgeSubcommand::Features => vec![BadgeOutcome::single(
+            "features",
+            features::badge_features(&package, &base_url).await?,
+        )],
+        BadgeSubcommand::Runtime => vec![BadgeOutcome::single(
+            "runtime",
+            runtime::badge_runtime(&package, &base_url).await?,
+        )],
+        BadgeSubcommand::Framework => vec![BadgeOutcome::single(
+            "framework",
+            framework::badge_framework(&package, &base_url).await?,
+        )],
+        BadgeSubcommand::Database => vec![BadgeOutcome {
+            name: "database",
+            result: database::badge_database(&package, &base_url).await?,
+        }],
+        BadgeSubcommand::Platform => vec![BadgeOutcome::single(
+            "platform",
+            platform::badge_platform(&package, &base_url).await?,
+        )],
+        BadgeSubcommand::ADRs => vec![BadgeOutcome::single(
+            "adrs",
+            adrs::badge_adrs(&package, args.cargo, &base_url).await?,
+        )],
+        BadgeSubcommand::Coverage {
+            coverage_tool,
+            coverage_file,
+            threshold,
+        } => {
+            let tool: CoverageTool = coverage_tool.parse()?;
+            let coverage = coverage::coverage_percentage(
+                &package,
+                tool,
+                coverage_file.as_deref(),
+                args.cargo,
+                cache,
+            )
+            .await?;
+            if let Some(threshold) = threshold {
+                coverage_failure =
+                    coverage::check_threshold(coverage.as_ref().ok().copied(), threshold).err();
+            }
+            vec![BadgeOutcome::single(
+                "coverage",
+                coverage.map(|coverage| coverage::coverage_badge(&package, coverage, &base_url)),
+            )]
         }
-        BadgeSubcommand::Cratesio => {
-            crates_io::badge_cratesio(&mut buffer, &package, args.no_network).await
+        BadgeSubcommand::NumberOfTests { include_doctests } => vec![BadgeOutcome::single(
+            "number-of-tests",
+            number_of_tests::badge_number_of_tests(
+                &package,
+                args.verbose,
+                include_doctests,
+                args.cargo,
+                cache,
+                &base_url,
+            )
+            .await?,
+        )],
+        BadgeSubcommand::DocsCoverage => vec![BadgeOutcome::single(
+            "docs-coverage",
+            docs_coverage::badge_docs_coverage(&package, args.cargo, cache, &base_url).await?,
+        )],
+        BadgeSubcommand::LinesOfCode { languages } => vec![BadgeOutcome::single(
+            "lines-of-code",
+            lines_of_code::badge_lines_of_code(&package, &languages, cache, &base_url).await?,
+        )],
+        BadgeSubcommand::BinarySize => vec![BadgeOutcome::single(
+            "binary-size",
+            binary_size::badge_binary_size(&package, args.cargo, cache, &base_url).await?,
+        )],
+        BadgeSubcommand::Maintenance => vec![BadgeOutcome::single(
+            "maintenance",
+            maintenance::badge_maintenance(&package, &base_url).await?,
+        )],
+        BadgeSubcommand::Semver => vec![BadgeOutcome::single(
+            "semver",
+            semver::badge_semver(
+                &package,
+                args.no_network,
+                args.timeout,
+                args.cargo,
+                cache,
+                &base_url,
+            )
+            .await?,
+        )],
+        BadgeSubcommand::Serialization => vec![BadgeOutcome::single(
+            "serialization",
+            serialization::badge_serialization(&package, &base_url).await?,
+        )],
+    };
+
+    let badges = all::emitted_badges(&outcomes, args.link_base.as_deref());
+
+    // Buffer all badge output to avoid mixing with stderr status lines
+    let mut buffer = Vec::new();
+    match args.format {
+        BadgeOutputFormat::Markdown => {
+            common::render_badges(&mut buffer, &badges, BadgeFormat::Markdown)?;
         }
-        BadgeSubcommand::License => license::badge_license(&mut buffer, &package).await,
-        BadgeSubcommand::RustEdition => {
-            rust_edition::badge_rust_edition(&mut buffer, &package).await
+        BadgeOutputFormat::Asciidoc => {
+            common::render_badges(&mut buffer, &badges, BadgeFormat::Asciidoc)?;
         }
-        BadgeSubcommand::Runtime => runtime::badge_runtime(&mut buffer, &package).await,
-        BadgeSubcommand::Framework => framework::badge_framework(&mut buffer, &package).await,
-        BadgeSubcommand::Platform => platform::badge_platform(&mut buffer, &package).await,
-        BadgeSubcommand::ADRs => adrs::badge_adrs(&mut buffer, &package).await,
-        BadgeSubcommand::Coverage => coverage::badge_coverage(&mut buffer, &package).await,
-        BadgeSubcommand::NumberOfTests => {
-            number_of_tests::badge_number_of_tests(&mut buffer, &package).await
+        BadgeOutputFormat::Json => write_badges_json(&mut buffer, &badges, args.with_kind)?,
+    }
+
+    // Now write all buffered output at once
+    writer.write_all(&buffer)?;
+
+    if let Some(failure) = coverage_failure {
+        return Err(failure);
+    }
+
+    if args.fail_on_missing {
+        let reasons: Vec<_> = outcomes
+            .iter()
+            .filter_map(|outcome| {
+                let reason = outcome.result.as_ref().err()?;
+                Some(format!("  - {}: {}", outcome.name, reason))
+            })
+            .collect();
+        if !reasons.is_empty() {
+            anyhow::bail!("Missing badges:\n{}", reasons.join("\n"));
         }
-    }?;
+    }
 
-    // Now write all buffered output to stdout at once
-    std::io::stdout().write_all(&buffer)?;
+    Ok(())
+}
+
+/// A badge in `--format json` output.
+#[derive(Serialize, JsonSchema)]
+pub(crate) struct BadgeJson<'a> {
+    /// Stable badge type identifier (with `--with-kind`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<&'a str>,
+    #[serde(flatten)]
+    badge: &'a Badge,
+    /// The badge rendered as Markdown (with `--with-kind`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    markdown: Option<String>,
+}
+
+/// Write badges as a JSON array, optionally with `kind` and `markdown`.
+fn write_badges_json(writer: &mut dyn Write, badges: &[Badge], with_kind: bool) -> Result<()> {
+    let mut entries = Vec::new();
+    for badge in badges {
+        let (kind, markdown) = if with_kind {
+            let mut markdown = Vec::new();
+            badge.render(&mut markdown, BadgeFormat::Markdown)?;
+            let markdown = String::from_utf8_lossy(&markdown).trim_end().to_string();
+            (Some(badge.name.as_str()), Some(markdown))
+        } else {
+            (None, None)
+        };
+        entries.push(BadgeJson {
+            kind,
+            badge,
+            markdown,
+        });
+    }
+
+    let json = serde_json::to_string_pretty(&entries).context("Failed to serialize badges")?;
+    writeln!(writer, "{}", json)?;
 
     Ok(())
 }
@@ -192,12 +745,10 @@ async fn badge_async(args: BadgeArgs) -> Result<()> {
 /// 2. Package whose manifest path matches `current_dir/Cargo.toml`
 /// 3. Root package (if workspace has a root package)
 /// 4. First default-member (if workspace has default-members configured)
-/// 5. Error if no package can be determined
-pub async fn find_package() -> Result<cargo_metadata::Package> {
-    use cargo_metadata::MetadataCommand;
-
+/// 5. Error listing the workspace members if no package can be determined
+pub async fn find_package(cargo: CargoOptions) -> Result<cargo_metadata::Package> {
     // Use cargo_metadata which automatically respects --manifest-path
-    let metadata = tokio::task::spawn_blocking(|| MetadataCommand::new().exec())
+    let metadata = tokio::task::spawn_blocking(move || cargo.metadata_command().exec())
         .await
         .context("Failed to spawn blocking task")?
         .context("Failed to get cargo metadata")?;
@@ -290,9 +841,112 @@ pub async fn find_package() -> Result<cargo_metadata::Package> {
         return Ok(default_package.clone());
     }
 
-    // If no default-members, we need to be in a package directory
+    // If no default-members, we need to be in a package directory. List the
+    // members so the user knows what to pass to --manifest-path.
+    let members: Vec<(String, String)> = metadata
+        .workspace_packages()
+        .into_iter()
+        .map(|pkg| {
+            let manifest = pkg
+                .manifest_path
+                .strip_prefix(&metadata.workspace_root)
+                .unwrap_or(pkg.manifest_path.as_path());
+            (pkg.name.to_string(), manifest.to_string())
+        })
+        .collect();
     anyhow::bail!(
         "No package found in current directory. Run this command from a package directory, \
-         or use --manifest-path to specify a package."
+         or use --manifest-path to specify a package.{}",
+        format_workspace_members(&members)
     )
 }
+
+/// Describe workspace members as `(name, manifest path)` pairs for an error
+/// message, or an empty string when there are none.
+fn format_workspace_members(members: &[(String, String)]) -> String {
+    if members.is_empty() {
+        return String::new();
+    }
+    let mut message = String::from("\n\nWorkspace members:");
+    for (name, manifest) in members {
+        message.push_str(&format!("\n  {} (--manifest-path {})", name, manifest));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_workspace_members() {
+        assert_eq!(format_workspace_members(&[]), "");
+        let members = [
+            ("core".to_string(), "crates/core/Cargo.toml".to_string()),
+            ("cli".to_string(), "crates/cli/Cargo.toml".to_string()),
+        ];
+        assert_eq!(
+            format_workspace_members(&members),
+            "\n\nWorkspace members:\n  core (--manifest-path crates/core/Cargo.toml)\n  cli \
+             (--manifest-path crates/cli/Cargo.toml)"
+        );
+    }
+
+    #[test]
+    fn test_write_badges_json_with_kind() {
+        let badges = [Badge::new(
+            "license",
+            "License",
+            "https://img.shields.io/badge/license-MIT-blue",
+            "LICENSE",
+        )];
+
+        let mut plain = Vec::new();
+        write_badges_json(&mut plain, &badges, false).unwrap();
+        let plain: serde_json::Value = serde_json::from_slice(&plain).unwrap();
+        assert_eq!(
+            plain,
+            serde_json::json!([{
+                "name": "license",
+                "alt": "License",
+                "image_url": "https://img.shields.io/badge/license-MIT-blue",
+                "link": "LICENSE",
+            }])
+        );
+
+        let mut with_kind = Vec::new();
+        write_badges_json(&mut with_kind, &badges, true).unwrap();
+        let with_kind: serde_json::Value = serde_json::from_slice(&with_kind).unwrap();
+        assert_eq!(with_kind[0]["kind"], "license");
+        assert_eq!(
+            with_kind[0]["markdown"],
+            "[![License](https://img.shields.io/badge/license-MIT-blue)](LICENSE)"
+        );
+    }
+
+    #[test]
+    fn test_badge_list() {
+        let args = BadgeArgs::try_parse_from(["badge", "--list"]).unwrap();
+        assert!(args.list && args.subcommand.is_none());
+
+        let mut output = Vec::new();
+        badge_to_writer(args, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), BADGE_NAMES.len() + 1);
+        assert!(lines[0].starts_with("BADGE"));
+
+        let line = |name: &str| {
+            *lines
+                .iter()
+                .find(|line| line.split_whitespace().next() == Some(name))
+                .unwrap()
+        };
+        assert!(line("coverage").contains("cargo-llvm-cov"));
+        assert!(line("cratesio").contains("network"));
+        assert!(line("semver").contains("network, cargo-semver-checks, opt-in"));
+        assert!(line("docs-coverage").contains("nightly rustdoc, opt-in"));
+        assert!(line("adrs").contains("ADRs badge"), "{}", line("adrs"));
+        assert!(lines.iter().skip(1).all(|line| line.contains("Show ")));
+    }
+}
@@ -1,68 +1,121 @@
 //! Generate number of tests badge.
+//!
+//! Doctests are not counted by default; with `--include-doctests` the tests
+//! listed by `cargo test --doc -- --list` are added and the badge label
+//! reads "tests (incl. doc)".
 
 use anyhow::{
     Context,
     Result,
 };
-use portable_pty::CommandBuilder;
-use serde::{
-    Deserialize,
-    Serialize,
+
+use super::common::{
+    self,
+    Badge,
+    CacheOptions,
+    SkipReason,
 };
+use crate::commands::common::CargoOptions;
 
-use super::common;
+/// Maximum number of lines of cargo output shown when the build fails.
+const MAX_FAILURE_LINES: usize = 20;
 
 /// Show the number of tests badge.
+///
+/// If the tests fail to build, the badge is skipped with a warning that
+/// includes cargo's error output. With `verbose`, the failure is returned as
+/// an error instead.
 pub async fn badge_number_of_tests(
-    writer: &mut dyn std::io::Write,
     package: &cargo_metadata::Package,
-) -> Result<()> {
+    verbose: bool,
+    include_doctests: bool,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
     // Use ephemeral status (cyan) for subprocess operations
     logger.status("Generating", "test count badge");
 
-    let test_count = get_test_count(&mut logger, package).await?;
-
-    if let Some(count) = test_count {
-        let badge_url = format!("https://img.shields.io/badge/tests-{}-blue", count);
-        let badge_markdown = format!("[![Tests]({})](tests/)", badge_url);
-        writeln!(writer, "{}", badge_markdown)?;
-    }
-
-    Ok(())
-}
+    let test_count = get_test_count(
+        &mut logger,
+        package,
+        verbose,
+        include_doctests,
+        cargo,
+        cache,
+    )
+    .await?;
+    let count = match test_count {
+        Ok(count) => count,
+        Err(reason) => return Ok(Err(reason)),
+    };
 
-/// Cache entry for test count results.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct TestCountCache {
-    /// Package name
-    package: String,
-    /// Cache key (git commit hash or file mtime)
-    cache_key: String,
-    /// Test count
-    test_count: u32,
+    let label = if include_doctests {
+        "tests%20(incl.%20doc)"
+    } else {
+        "tests"
+    };
+    let badge_url = common::badge_url(base_url, &format!("badge/{}-{}-blue", label, count));
+    Ok(Ok(Badge::new(
+        "number-of-tests",
+        "Tests",
+        &badge_url,
+        "tests/",
+    )))
 }
 
-/// Get the number of tests in the package.
+/// Get the number of tests in the package, optionally including doctests.
 /// Uses cache if available and valid.
 async fn get_test_count(
     logger: &mut cargo_plugin_utils::logger::Logger,
     package: &cargo_metadata::Package,
-) -> Result<Option<u32>> {
+    verbose: bool,
+    include_doctests: bool,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+) -> Result<Result<u32, SkipReason>> {
+    // Counts with and without doctests are cached separately
+    let cache_name = if include_doctests {
+        "test-count-with-doctests"
+    } else {
+        "test-count"
+    };
+
     // Try to load from cache first
-    if let Some(cached) = load_test_count_cache(package).await? {
-        let current_key = common::compute_cache_key(package).await?;
-        if cached.cache_key == current_key && package.name == cached.package {
-            return Ok(Some(cached.test_count));
-        }
+    if let Some(cached) =
+        common::load_cache_if_fresh::<u32>(cache_name, package, common::LOCAL_CACHE_TTL, cache)
+            .await?
+    {
+        return Ok(Ok(cached));
+    }
+
+    let mut test_count = count_tests(logger, package, verbose, cargo).await?;
+    if include_doctests && let Some(doctests) = count_doctests(logger, package, cargo).await? {
+        test_count = Ok(test_count.unwrap_or(0) + doctests);
+    }
+
+    if let Ok(count) = test_count {
+        // Save to cache
+        common::save_cache(cache_name, package, &count).await?;
     }
 
+    Ok(test_count)
+}
+
+/// Count the unit and integration tests in the package (without doctests).
+async fn count_tests(
+    logger: &mut cargo_plugin_utils::logger::Logger,
+    package: &cargo_metadata::Package,
+    verbose: bool,
+    cargo: CargoOptions,
+) -> Result<Result<u32, SkipReason>> {
     // Use cargo test --no-run --message-format=json to count tests
     let package_name = package.name.clone();
     let output = cargo_plugin_utils::logger::run_subprocess(
         logger,
         move || {
-            let mut cmd = CommandBuilder::new("cargo");
+            let mut cmd = common::cargo_command(cargo);
             cmd.arg("test");
             cmd.arg("--package");
             cmd.arg(package_name.as_str());
@@ -75,15 +128,15 @@ async fn get_test_count(
     )
     .await?;
 
-    if !output.success() {
-        return Ok(None);
-    }
-
     // Parse JSON messages to count test artifacts
     let stdout = output
         .stdout_str()
         .context("Failed to parse cargo test output")?;
 
+    if !output.success() {
+        return report_build_failure(logger, &stdout, verbose);
+    }
+
     let mut test_count = 0;
     let package_id_prefix = format!("{}@", package.name);
     for line in stdout.lines() {
@@ -129,9 +182,7 @@ async fn get_test_count(
 
     // If we got a count from JSON parsing, use it
     if test_count > 0 {
-        // Save to cache
-        save_test_count_cache(package, test_count).await?;
-        return Ok(Some(test_count));
+        return Ok(Ok(test_count));
     }
 
     // Alternative: count by running test binaries with --list flag
@@ -142,7 +193,7 @@ async fn get_test_count(
         {
             let package_name = package_name.clone();
             move || {
-                let mut cmd = CommandBuilder::new("cargo");
+                let mut cmd = common::cargo_command(cargo);
                 cmd.arg("test");
                 cmd.arg("--package");
                 cmd.arg(package_name.as_str());
@@ -155,14 +206,17 @@ async fn get_test_count(
     .await?;
 
     if !compile_output.success() {
-        return Ok(None);
+        let compile_stdout = compile_output
+            .stdout_str()
+            .context("Failed to parse cargo test output")?;
+        return report_build_failure(logger, &compile_stdout, verbose);
     }
 
     // Then run with --list to get test names
     let list_output = cargo_plugin_utils::logger::run_subprocess(
         logger,
         move || {
-            let mut cmd = CommandBuilder::new("cargo");
+            let mut cmd = common::cargo_command(cargo);
             cmd.arg("test");
             cmd.arg("--package");
             cmd.arg(package_name.as_str());
@@ -174,70 +228,174 @@ async fn get_test_count(
     )
     .await?;
 
-    if list_output.success() {
-        let list_stdout = list_output
-            .stdout_str()
-            .context("Failed to parse cargo test --list output")?;
-
-        // Count lines that are test names (format: "test_name: test")
-        let count = list_stdout
-            .lines()
-            .filter(|line| line.contains(": test"))
-            .count() as u32;
-
-        if count > 0 {
-            // Save to cache
-            save_test_count_cache(package, count).await?;
-            return Ok(Some(count));
-        }
+    if !list_output.success() {
+        return Ok(Err(SkipReason::new("`cargo test -- --list` failed")));
+    }
+
+    let list_stdout = list_output
+        .stdout_str()
+        .context("Failed to parse cargo test --list output")?;
+
+    let count = count_listed_tests(&list_stdout);
+    if count > 0 {
+        return Ok(Ok(count));
     }
 
-    Ok(None)
+    Ok(Err(SkipReason::new("no tests found")))
 }
 
-/// Load test count from cache.
-async fn load_test_count_cache(
-    _package: &cargo_metadata::Package,
-) -> Result<Option<TestCountCache>> {
-    let cache_path = common::get_badge_cache_path("test-count")?;
+/// Count the doctests in the package with `cargo test --doc -- --list`.
+///
+/// Returns `None` if the package has no library target or the doctests
+/// cannot be listed.
+async fn count_doctests(
+    logger: &mut cargo_plugin_utils::logger::Logger,
+    package: &cargo_metadata::Package,
+    cargo: CargoOptions,
+) -> Result<Option<u32>> {
+    let package_name = package.name.clone();
+    let output = cargo_plugin_utils::logger::run_subprocess(
+        logger,
+        move || {
+            let mut cmd = common::cargo_command(cargo);
+            cmd.arg("test");
+            cmd.arg("--package");
+            cmd.arg(package_name.as_str());
+            cmd.arg("--doc");
+            cmd.arg("--");
+            cmd.arg("--list");
+            cmd
+        },
+        None,
+    )
+    .await?;
 
-    if !cache_path.exists() {
+    if !output.success() {
+        logger.warning("Skipping", "doctests: failed to list them");
         return Ok(None);
     }
 
-    let contents = tokio::fs::read_to_string(&cache_path)
-        .await
-        .context("Failed to read cache file")?;
+    let stdout = output
+        .stdout_str()
+        .context("Failed to parse cargo test --doc --list output")?;
+    Ok(Some(count_listed_tests(&stdout)))
+}
 
-    let cache: TestCountCache =
-        serde_json::from_str(&contents).context("Failed to parse cache file")?;
+/// Count test names in `cargo test -- --list` output.
+///
+/// Tests are listed as `name: test` (doctests as
+/// `src/lib.rs - item (line 10): test`); benchmarks and summary lines are
+/// ignored.
+fn count_listed_tests(output: &str) -> u32 {
+    output
+        .lines()
+        .filter(|line| line.trim_end().ends_with(": test"))
+        .count() as u32
+}
+
+/// Report a failed `cargo test --no-run`.
+///
+/// Logs a warning with the relevant part of cargo's output and returns the
+/// skip reason so `badge all` can continue, or fails when `verbose` is set.
+fn report_build_failure(
+    logger: &mut cargo_plugin_utils::logger::Logger,
+    output: &str,
+    verbose: bool,
+) -> Result<Result<u32, SkipReason>> {
+    let summary = failure_summary(output);
+    if verbose {
+        anyhow::bail!("Failed to build tests:\n{}", summary);
+    }
 
-    Ok(Some(cache))
+    logger.warning(
+        "Skipping",
+        &format!("test count badge: failed to build tests:\n{}", summary),
+    );
+    Ok(Err(SkipReason::new("the tests failed to build")))
 }
 
-/// Save test count to cache.
-async fn save_test_count_cache(package: &cargo_metadata::Package, test_count: u32) -> Result<()> {
-    let cache_key = common::compute_cache_key(package).await?;
-    let cache = TestCountCache {
-        package: package.name.to_string(),
-        cache_key,
-        test_count,
-    };
+/// Extract the error output from a failed cargo build.
+///
+/// The subprocess runs in a PTY, so cargo's stderr arrives interleaved with
+/// stdout. With `--message-format json`, compiler errors are JSON messages
+/// whose `rendered` text is used; other lines (cargo's own `error: ...`
+/// output) are kept as-is. Only the last [`MAX_FAILURE_LINES`] lines are
+/// returned.
+fn failure_summary(output: &str) -> String {
+    let mut lines = Vec::new();
+    for line in output.lines() {
+        match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(json) => {
+                let message = json.get("message");
+                let is_error = message
+                    .and_then(|m| m.get("level"))
+                    .and_then(|level| level.as_str())
+                    .is_some_and(|level| level == "error");
+                if is_error
+                    && let Some(rendered) = message
+                        .and_then(|m| m.get("rendered"))
+                        .and_then(|rendered| rendered.as_str())
+                {
+                    lines.extend(rendered.lines().map(str::to_string));
+                }
+            }
+            Err(_) => {
+                let line = line.trim_end();
+                if !line.trim().is_empty() {
+                    lines.push(line.to_string());
+                }
+            }
+        }
+    }
 
-    let cache_path = common::get_badge_cache_path("test-count")?;
+    if lines.is_empty() {
+        return "(no output)".to_string();
+    }
+    let start = lines.len().saturating_sub(MAX_FAILURE_LINES);
+    lines[start..].join("\n")
+}
 
-    // Create parent directory if it doesn't exist
-    if let Some(parent) = cache_path.parent() {
-        tokio::fs::create_dir_all(parent)
-            .await
-            .context("Failed to create cache directory")?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_summary() {
+        let output = concat!(
+            r#"{"reason":"compiler-message","message":{"level":"warning","rendered":"warning: unused"}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"error","rendered":"error[E0425]: cannot find value `x`\n --> src/lib.rs:1:1\n"}}"#,
+            "\n",
+            "error: could not compile `foo` (lib test) due to 1 previous error\n",
+        );
+
+        assert_eq!(
+            failure_summary(output),
+            "error[E0425]: cannot find value `x`\n --> src/lib.rs:1:1\nerror: could not compile `foo` (lib test) due to 1 previous error"
+        );
     }
 
-    let json = serde_json::to_string_pretty(&cache).context("Failed to serialize cache")?;
+    #[test]
+    fn test_count_listed_tests() {
+        let output = "\
+tests::test_one: test
+tests::test_two: test
+benches::bench_one: benchmark
+
+2 tests, 1 benchmark
+src/lib.rs - parse (line 12): test
+";
+        assert_eq!(count_listed_tests(output), 3);
+        assert_eq!(count_listed_tests(""), 0);
+    }
 
-    tokio::fs::write(&cache_path, json)
-        .await
-        .context("Failed to write cache file")?;
+    #[test]
+    fn test_failure_summary_truncates() {
+        let output: String = (0..50).map(|i| format!("line {}\n", i)).collect();
+        let summary = failure_summary(&output);
 
-    Ok(())
+        assert_eq!(summary.lines().count(), MAX_FAILURE_LINES);
+        assert!(summary.ends_with("line 49"));
+        assert_eq!(failure_summary(""), "(no output)");
+    }
 }
@@ -1,17 +1,39 @@
 //! Generate platform badge.
 
-use std::io::Write;
-
 use anyhow::Result;
 
+use super::common::{
+    self,
+    Badge,
+    SkipReason,
+};
+
 /// Show the platform badge.
+///
+/// An explicit `platform = "..."` in `[package.metadata.version-info]` wins
+/// over the filesystem heuristics below.
 pub async fn badge_platform(
-    writer: &mut dyn Write,
     package: &cargo_metadata::Package,
-) -> Result<()> {
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
     logger.status("Generating", "platform badge");
 
+    if let Some(platform) = common::version_info_setting(&package.metadata, "platform")
+        .and_then(|platform| platform.as_str())
+    {
+        let badge_url = common::badge_url(
+            base_url,
+            &format!("badge/platform-{}-blue", common::shields_escape(platform)),
+        );
+        return Ok(Ok(Badge::new(
+            "platform",
+            "Platform",
+            badge_url,
+            "Cargo.toml",
+        )));
+    }
+
     let manifest_dir = package
         .manifest_path
         .as_std_path()
@@ -39,18 +61,26 @@ pub async fn badge_platform(
             .is_ok();
 
     if has_fly {
-        let badge_url = "https://img.shields.io/badge/platform-Fly.io-8A2BE2";
-        let badge_markdown = format!(
-            "[![Platform]({})](docs/adr/0002-flyio-oxigraph-provisioning-strategy.typ)",
-            badge_url
-        );
-        writeln!(writer, "{}", badge_markdown)?;
+        let badge_url = common::badge_url(base_url, "badge/platform-Fly.io-8A2BE2");
+        return Ok(Ok(Badge::new(
+            "platform",
+            "Platform",
+            badge_url,
+            "docs/adr/0002-flyio-oxigraph-provisioning-strategy.typ",
+        )));
     } else if has_vercel {
-        let badge_url = "https://img.shields.io/badge/platform-Vercel-black";
-        let badge_markdown = format!("[![Platform]({})](docs/adr/)", badge_url);
-        writeln!(writer, "{}", badge_markdown)?;
+        let badge_url = common::badge_url(base_url, "badge/platform-Vercel-black");
+        return Ok(Ok(Badge::new(
+            "platform",
+            "Platform",
+            badge_url,
+            "docs/adr/",
+        )));
     }
     // Future: add other platforms (AWS, GCP, Azure, etc.)
 
-    Ok(())
+    Ok(Err(SkipReason::new(
+        "no platform detected (fly.toml, vercel.json) and no `platform` in \
+         [package.metadata.version-info]",
+    )))
 }
@@ -1,14 +1,18 @@
 //! Generate runtime badge.
 
-use std::io::Write;
-
 use anyhow::Result;
 
+use super::common::{
+    self,
+    Badge,
+    SkipReason,
+};
+
 /// Show the runtime badge.
 pub async fn badge_runtime(
-    writer: &mut dyn Write,
     package: &cargo_metadata::Package,
-) -> Result<()> {
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
     logger.status("Generating", "runtime badge");
 
@@ -16,14 +20,17 @@ pub async fn badge_runtime(
     let has_tokio = package.dependencies.iter().any(|dep| dep.name == "tokio");
 
     if has_tokio {
-        let badge_url = "https://img.shields.io/badge/runtime-Tokio-blue";
-        let badge_markdown = format!(
-            "[![Runtime]({})](docs/adr/0007-async-runtime-tokio.typ)",
-            badge_url
-        );
-        writeln!(writer, "{}", badge_markdown)?;
+        let badge_url = common::badge_url(base_url, "badge/runtime-Tokio-blue");
+        return Ok(Ok(Badge::new(
+            "runtime",
+            "Runtime",
+            badge_url,
+            "docs/adr/0007-async-runtime-tokio.typ",
+        )));
     }
     // Future: add other runtimes (async-std, smol, etc.)
 
-    Ok(())
+    Ok(Err(SkipReason::new(
+        "no supported async runtime dependency (tokio)",
+    )))
 }
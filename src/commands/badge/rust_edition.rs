@@ -1,24 +1,58 @@
 //! Generate Rust edition badge.
 
-use std::io::Write;
-
 use anyhow::Result;
 
+use super::common::{
+    self,
+    Badge,
+    SkipReason,
+};
+
 /// Show the Rust edition badge.
 pub async fn badge_rust_edition(
-    writer: &mut dyn Write,
     package: &cargo_metadata::Package,
-) -> Result<()> {
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
     logger.status("Generating", "Rust edition badge");
 
     let edition_str = package.edition.as_str();
-    let badge_url = format!(
-        "https://img.shields.io/badge/rust%20edition-{}-orange",
-        edition_str
+    let badge_url = common::badge_url(
+        base_url,
+        &format!(
+            "badge/rust%20edition-{}-{}",
+            edition_str,
+            edition_color(edition_str)
+        ),
     );
-    let badge_markdown = format!("[![Rust Edition]({})](Cargo.toml)", badge_url);
-    writeln!(writer, "{}", badge_markdown)?;
+    Ok(Ok(Badge::new(
+        "rust-edition",
+        "Rust Edition",
+        &badge_url,
+        "Cargo.toml",
+    )))
+}
+
+/// Badge color for an edition, so newer editions stand out.
+///
+/// Editions newer than 2024 are green as well.
+fn edition_color(edition: &str) -> &'static str {
+    match edition {
+        "2015" => "lightgrey",
+        "2018" | "2021" => "orange",
+        _ => "green",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
+    #[test]
+    fn test_edition_color() {
+        assert_eq!(edition_color("2015"), "lightgrey");
+        assert_eq!(edition_color("2018"), "orange");
+        assert_eq!(edition_color("2021"), "orange");
+        assert_eq!(edition_color("2024"), "green");
+    }
 }
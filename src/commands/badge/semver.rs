@@ -0,0 +1,175 @@
+//! Generate semver compatibility badge.
+//!
+//! Runs `cargo semver-checks check-release` against the newest version
+//! published on crates.io. The badge reads `semver-compatible` (green) when
+//! the version in Cargo.toml is a large enough bump for the API changes,
+//! and `semver-breaking` (red) when it is not.
+//!
+//! The badge is skipped when cargo-semver-checks is not installed, the crate
+//! is unpublished, or `--no-network` is set (the baseline cannot be looked
+//! up). Results are cached per baseline version.
+
+use std::time::Duration;
+
+use anyhow::{
+    Context,
+    Result,
+};
+
+use super::common::{
+    self,
+    Badge,
+    CacheOptions,
+    SkipReason,
+};
+use super::crates_io;
+use crate::commands::common::CargoOptions;
+
+/// Show the semver compatibility badge.
+pub async fn badge_semver(
+    package: &cargo_metadata::Package,
+    no_network: bool,
+    timeout: Option<Duration>,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+    // Use ephemeral status (cyan) for subprocess operations
+    logger.status("Generating", "semver badge");
+
+    if no_network {
+        return Ok(Err(SkipReason::new(
+            "the published baseline cannot be looked up with --no-network",
+        )));
+    }
+    let baseline = match crates_io::latest_published_version(&package.name, timeout).await {
+        Ok(Some(baseline)) => baseline,
+        Ok(None) => {
+            return Ok(Err(SkipReason::new(
+                "the crate is not published on crates.io",
+            )));
+        }
+        Err(error) if common::is_network_error(&error) => {
+            logger.warning("Skipping", &format!("semver badge: {:#}", error));
+            return Ok(Err(SkipReason::new(format!(
+                "crates.io could not be reached: {:#}",
+                error
+            ))));
+        }
+        Err(error) => return Err(error),
+    };
+
+    let compatible = match check_release(&mut logger, package, &baseline, cargo, cache).await? {
+        Ok(compatible) => compatible,
+        Err(reason) => return Ok(Err(reason)),
+    };
+
+    let (status, color) = if compatible {
+        ("compatible", "brightgreen")
+    } else {
+        ("breaking", "red")
+    };
+    let badge_url = common::badge_url(base_url, &format!("badge/semver-{}-{}", status, color));
+    let link = format!("https://crates.io/crates/{}/versions", package.name);
+    Ok(Ok(Badge::new("semver", "Semver", &badge_url, &link)))
+}
+
+/// Run `cargo semver-checks check-release` against `baseline`.
+///
+/// Returns whether the release is semver compatible, or the skip reason if
+/// the tool is missing or failed for another reason. Uses cache if available
+/// and valid.
+async fn check_release(
+    logger: &mut cargo_plugin_utils::logger::Logger,
+    package: &cargo_metadata::Package,
+    baseline: &str,
+    cargo: CargoOptions,
+    cache: CacheOptions,
+) -> Result<Result<bool, SkipReason>> {
+    // The result depends on the baseline as well as the local code
+    let cache_name = format!("semver-{}", baseline);
+    if let Some(cached) =
+        common::load_cache_if_fresh::<bool>(&cache_name, package, common::LOCAL_CACHE_TTL, cache)
+            .await?
+    {
+        return Ok(Ok(cached));
+    }
+
+    let version_output = cargo_plugin_utils::logger::run_subprocess(
+        logger,
+        || {
+            let mut cmd = common::cargo_command(cargo);
+            cmd.arg("semver-checks");
+            cmd.arg("--version");
+            cmd
+        },
+        None,
+    )
+    .await?;
+    if !version_output.success() {
+        return Ok(Err(SkipReason::new("cargo-semver-checks is not installed")));
+    }
+
+    let package_name = package.name.clone();
+    let baseline_version = baseline.to_string();
+    let output = cargo_plugin_utils::logger::run_subprocess(
+        logger,
+        move || {
+            let mut cmd = common::cargo_command(cargo);
+            cmd.arg("semver-checks");
+            cmd.arg("check-release");
+            cmd.arg("--package");
+            cmd.arg(package_name.as_str());
+            cmd.arg("--baseline-version");
+            cmd.arg(baseline_version.as_str());
+            cmd
+        },
+        None,
+    )
+    .await?;
+
+    let stdout = output
+        .stdout_str()
+        .context("Failed to parse cargo semver-checks output")?;
+    let compatible = if output.success() {
+        true
+    } else if reports_semver_violation(&stdout) {
+        false
+    } else {
+        logger.warning(
+            "Skipping",
+            "semver badge: cargo semver-checks failed without a verdict",
+        );
+        return Ok(Err(SkipReason::new(
+            "cargo semver-checks failed without a verdict",
+        )));
+    };
+
+    common::save_cache(&cache_name, package, &compatible).await?;
+    Ok(Ok(compatible))
+}
+
+/// Whether `cargo semver-checks` output reports failed checks, as opposed
+/// to failing for another reason (build error, missing baseline, ...).
+///
+/// Failed checks end with a summary such as `Summary semver requires new
+/// major version: 1 major and 0 minor checks failed`.
+fn reports_semver_violation(output: &str) -> bool {
+    output.contains("semver requires new")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_semver_violation() {
+        assert!(reports_semver_violation(
+            "     Summary semver requires new major version: 1 major and 0 minor checks failed\n"
+        ));
+        assert!(!reports_semver_violation(
+            "error: package `foo` not found in registry\n"
+        ));
+    }
+}
@@ -0,0 +1,85 @@
+//! Generate serialization badge.
+
+use anyhow::Result;
+
+use super::common::{
+    self,
+    Badge,
+    SkipReason,
+};
+
+/// Serialization formats detected in `[dependencies]`, with their labels.
+const FORMAT_CRATES: &[(&str, &str)] = &[
+    ("serde_json", "JSON"),
+    ("toml", "TOML"),
+    ("serde_yaml", "YAML"),
+    ("bincode", "bincode"),
+];
+
+/// Show the serialization badge if the package depends on serde.
+///
+/// The badge reads `Serde`, followed by the formats found among the
+/// dependencies, e.g. `Serde (JSON, TOML)`.
+pub async fn badge_serialization(
+    package: &cargo_metadata::Package,
+    base_url: &str,
+) -> Result<Result<Badge, SkipReason>> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+    logger.status("Generating", "serialization badge");
+
+    let dependencies: Vec<&str> = package
+        .dependencies
+        .iter()
+        .map(|dep| dep.name.as_str())
+        .collect();
+    let Some(message) = serialization_message(&dependencies) else {
+        return Ok(Err(SkipReason::new("no serde dependency found")));
+    };
+
+    let badge_url = common::badge_url(
+        base_url,
+        &format!(
+            "badge/serialization-{}-blue",
+            common::shields_escape(&message)
+        ),
+    );
+    Ok(Ok(Badge::new(
+        "serialization",
+        "Serialization",
+        badge_url,
+        "Cargo.toml",
+    )))
+}
+
+/// Badge message for the given dependencies, or `None` without serde.
+fn serialization_message(dependencies: &[&str]) -> Option<String> {
+    if !dependencies.contains(&"serde") {
+        return None;
+    }
+
+    let formats: Vec<&str> = FORMAT_CRATES
+        .iter()
+        .filter(|(name, _)| dependencies.contains(name))
+        .map(|(_, label)| *label)
+        .collect();
+    if formats.is_empty() {
+        Some("Serde".to_string())
+    } else {
+        Some(format!("Serde ({})", formats.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization_message() {
+        assert_eq!(serialization_message(&["serde"]), Some("Serde".to_string()));
+        assert_eq!(
+            serialization_message(&["toml", "serde", "serde_json"]),
+            Some("Serde (JSON, TOML)".to_string())
+        );
+        assert_eq!(serialization_message(&["serde_json"]), None);
+    }
+}
@@ -0,0 +1,171 @@
+//! Generate badges by type (alias of `badge`).
+//!
+//! `badges` is a flat front end to the nested `badge` command: `--type`
+//! selects the badge subcommand and the badges are generated by the same
+//! code. Unlike `badge`, the output can be written to a file.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Same as `cargo version-info badge all`
+//! cargo version-info badges
+//!
+//! # Same as `cargo version-info badge number-of-tests`
+//! cargo version-info badges --type tests
+//!
+//! # Coverage, test count, documentation coverage and lines of code
+//! cargo version-info badges --type quality
+//!
+//! # Write the coverage badge to a file
+//! cargo version-info badges --type coverage --output coverage-badge.md
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::{
+    Context,
+    Result,
+};
+use clap::Parser;
+
+use super::badge::{
+    BadgeArgs,
+    badge_to_writer,
+};
+use super::common::CargoOptions;
+
+/// Values accepted by `badges --type`.
+pub const BADGE_TYPES: &[&str] = &["all", "tests", "coverage", "quality"];
+
+/// Badges generated by `badges --type quality`.
+pub const QUALITY_BADGES: &[&str] = &[
+    "coverage",
+    "number-of-tests",
+    "docs-coverage",
+    "lines-of-code",
+];
+
+/// Arguments for the `badges` command.
+#[derive(Parser, Debug)]
+pub struct BadgesArgs {
+    /// Badges to generate: `all`, `tests`, `coverage` or `quality` (see
+    /// [`QUALITY_BADGES`]).
+    #[arg(long = "type", default_value = "all")]
+    pub badge_type: String,
+
+    /// Output file path (default: stdout).
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Output format for the badges (`markdown`, `asciidoc` or `json`).
+    #[arg(long, default_value = "markdown")]
+    pub format: String,
+
+    /// Skip network requests and use heuristics to guess if crate is published.
+    #[arg(long)]
+    pub no_network: bool,
+
+    /// Network access for cargo subprocesses, from the global `--offline`
+    /// and `--frozen` flags.
+    #[arg(skip)]
+    pub cargo: CargoOptions,
+
+    /// Timeout of each crates.io/docs.rs request, from the global
+    /// `--timeout` flag.
+    #[arg(skip)]
+    pub timeout: Option<std::time::Duration>,
+}
+
+/// Generate badges by type, forwarding to the `badge` command.
+///
+/// # Errors
+///
+/// Returns an error if the type is unknown, badge generation fails, or the
+/// output file cannot be written.
+pub fn badges(args: BadgesArgs) -> Result<()> {
+    let badge_args = badge_args(&args)?;
+
+    match &args.output {
+        Some(path) => {
+            let mut output = Vec::new();
+            badge_to_writer(badge_args, &mut output)?;
+            std::fs::write(path, output)
+                .with_context(|| format!("Failed to write badges to {}", path.display()))?;
+            let mut logger = cargo_plugin_utils::logger::Logger::new();
+            logger.status("Written", &path.display().to_string());
+            Ok(())
+        }
+        None => badge_to_writer(badge_args, &mut std::io::stdout()),
+    }
+}
+
+/// Translate `badges` arguments into the equivalent `badge` command line.
+fn badge_args(args: &BadgesArgs) -> Result<BadgeArgs> {
+    let quality = QUALITY_BADGES.join(",");
+    let mut command_line = vec!["badge", "--format", args.format.as_str()];
+    if args.no_network {
+        command_line.push("--no-network");
+    }
+    match args.badge_type.as_str() {
+        "all" => command_line.push("all"),
+        "tests" => command_line.push("number-of-tests"),
+        "coverage" => command_line.push("coverage"),
+        "quality" => command_line.extend(["all", "--only", quality.as_str()]),
+        other => anyhow::bail!(
+            "Invalid badge type: {} (expected one of {})",
+            other,
+            BADGE_TYPES.join(", ")
+        ),
+    }
+
+    let mut badge_args = BadgeArgs::try_parse_from(command_line)
+        .context("Failed to forward to the badge command")?;
+    badge_args.cargo = args.cargo;
+    badge_args.timeout = args.timeout;
+    Ok(badge_args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::badge::{
+        BadgeOutputFormat,
+        BadgeSubcommand,
+    };
+
+    fn args(badge_type: &str) -> BadgesArgs {
+        BadgesArgs {
+            badge_type: badge_type.to_string(),
+            output: None,
+            format: "asciidoc".to_string(),
+            no_network: true,
+            cargo: CargoOptions::default(),
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn test_badge_args() {
+        let all = badge_args(&args("all")).unwrap();
+        assert!(
+            matches!(all.subcommand, Some(BadgeSubcommand::All { ref only, .. }) if only.is_empty())
+        );
+        assert_eq!(all.format, BadgeOutputFormat::Asciidoc);
+        assert!(all.no_network);
+
+        let tests = badge_args(&args("tests")).unwrap();
+        assert!(matches!(
+            tests.subcommand,
+            Some(BadgeSubcommand::NumberOfTests { .. })
+        ));
+
+        let quality = badge_args(&args("quality")).unwrap();
+        let Some(BadgeSubcommand::All { only, .. }) = quality.subcommand else {
+            panic!("quality should map to badge all");
+        };
+        assert_eq!(only, QUALITY_BADGES);
+
+        let error = badge_args(&args("bogus")).unwrap_err().to_string();
+        assert!(error.contains("all, tests, coverage, quality"), "{}", error);
+    }
+}
@@ -10,9 +10,13 @@
 //! 2. **CARGO_PKG_VERSION_OVERRIDE** (environment variable) - Legacy override
 //! 3. **GitHub API** - Query and calculate next version (only in GitHub
 //!    Actions)
-//! 4. **CARGO_PKG_VERSION** (environment variable) - From Cargo.toml at build
+//! 4. **Version file** - Read from `--version-file` (e.g. `version.txt`) when
+//!    given
+//! 5. **CARGO_PKG_VERSION** (environment variable) - From Cargo.toml at build
 //!    time
-//! 5. **Git SHA** - Fallback: `0.0.0-dev-<short-sha>` for local development
+//! 6. **Manifest version** - Parsed from Cargo.toml, with the git SHA appended
+//!    when available
+//! 7. **Git SHA** - Fallback: `0.0.0-dev-<short-sha>` for local development
 //!
 //! # Examples
 //!
@@ -23,11 +27,26 @@
 //! # Get JSON output with source information
 //! cargo version-info build-version --format json
 //!
+//! # Write version and source to GITHUB_OUTPUT
+//! cargo version-info build-version --format github-actions
+//!
 //! # With BUILD_VERSION set (highest priority)
 //! BUILD_VERSION=1.2.3 cargo version-info build-version
+//!
+//! # Print the version as `v1.2.3`
+//! cargo version-info build-version --prefix v
+//!
+//! # Use 12-character SHAs in git-based versions
+//! cargo version-info build-version --sha-length 12
+//!
+//! # Treat version.txt as the source of truth
+//! cargo version-info build-version --version-file version.txt
 //! ```
 
-use std::path::PathBuf;
+use std::path::{
+    Path,
+    PathBuf,
+};
 use std::{
     env,
     fs,
@@ -37,10 +56,17 @@ use anyhow::{
     Context,
     Result,
 };
-use cargo_plugin_utils::common::get_owner_repo;
 use clap::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
 
+use super::common::{
+    abbreviate_sha,
+    read_version_file,
+    write_github_outputs,
+};
 use crate::github;
+use crate::version::apply_prefix;
 
 /// Arguments for the `build-version` command.
 #[derive(Parser, Debug)]
@@ -68,27 +94,70 @@ pub struct BuildVersionArgs {
     #[arg(long, env = "GITHUB_TOKEN")]
     github_token: Option<String>,
 
+    /// Tag prefix for per-crate release tags (e.g., `foo-v` for `foo-v1.2.3`).
+    ///
+    /// Only tags starting with the prefix are considered, and the prefix is
+    /// stripped before parsing. Defaults to stripping a leading `v`/`V`.
+    #[arg(long)]
+    tag_prefix: Option<String>,
+
     /// Path to the Cargo.toml manifest file.
     ///
-    /// Currently unused but reserved for future use. Defaults to
+    /// Used for the manifest version fallback (priority 6). Defaults to
     /// `./Cargo.toml`.
     #[arg(long, default_value = "./Cargo.toml")]
     manifest: PathBuf,
 
+    /// Authoritative version file (e.g. `version.txt`) (priority 4).
+    ///
+    /// When given, its trimmed content is used instead of CARGO_PKG_VERSION
+    /// and the manifest version. It must be a valid semantic version.
+    #[arg(long, value_name = "PATH")]
+    version_file: Option<PathBuf>,
+
     /// Path to the git repository.
     ///
-    /// Used for the git SHA fallback (priority 5). Defaults to the current
-    /// directory.
+    /// Used for the git SHA fallbacks (priorities 6 and 7). Defaults to the
+    /// current directory.
     #[arg(long, default_value = ".")]
     repo_path: PathBuf,
 
+    /// Number of hex characters in git SHAs (clamped to 4..=40).
+    ///
+    /// Used by the git SHA fallbacks. Defaults to gix's automatic length,
+    /// the shortest unambiguous abbreviation.
+    #[arg(long, value_name = "N")]
+    sha_length: Option<usize>,
+
+    /// Prefix to prepend to printed versions (e.g. `v` for `v1.2.3`).
+    ///
+    /// A `v`/`V` already on the version is replaced, never doubled.
+    #[arg(long, value_name = "PREFIX")]
+    prefix: Option<String>,
+
+    /// Print bare versions without any prefix (the default).
+    #[arg(long, conflicts_with = "prefix")]
+    no_prefix: bool,
+
     /// Output format for the build version.
     ///
     /// - `version`: Print just the version number
     /// - `json`: Print JSON with version and source fields indicating where the
-    ///   version came from (environment, github_api, cargo_toml, or git)
+    ///   version came from (environment, github_api, version_file, cargo_toml,
+    ///   or git)
+    /// - `github-actions`: Write `version` and `source` (plus `sha` for the git
+    ///   fallback) to the GITHUB_OUTPUT file in GitHub Actions format, so a
+    ///   workflow can tell a release version (`source != 'git'`) from a dev
+    ///   build
     #[arg(long, default_value = "version")]
     format: String,
+
+    /// Path to GitHub Actions output file.
+    ///
+    /// Only used when `--format github-actions` is specified.
+    /// Defaults to the `GITHUB_OUTPUT` environment variable or stdout.
+    #[arg(long, env = "GITHUB_OUTPUT")]
+    github_output: Option<String>,
 }
 
 /// Determine the build version using a priority-based fallback system.
@@ -105,9 +174,14 @@ pub struct BuildVersionArgs {
 ///    override mechanism
 /// 3. **GitHub API** - Only checked if running in GitHub Actions (detected via
 ///    `GITHUB_ACTIONS` env var). Queries the API to calculate the next version.
-/// 4. **CARGO_PKG_VERSION** environment variable - Set by Cargo at build time
-///    from Cargo.toml. Usually "0.0.0" for placeholder versions.
-/// 5. **Git SHA** - Final fallback for local development:
+/// 4. **Version file** - The trimmed content of `--version-file`, for projects
+///    where Cargo.toml is synced from a `version.txt`.
+/// 5. **CARGO_PKG_VERSION** environment variable - Set by Cargo at build time
+///    from Cargo.toml. Skipped when empty or "0.0.0" (placeholder versions).
+/// 6. **Manifest version** - Parsed from the `--manifest` file when
+///    CARGO_PKG_VERSION is not available, with `-<short-sha>` appended when a
+///    git repository is available. Skipped for "0.0.0".
+/// 7. **Git SHA** - Final fallback for local development:
 ///    `0.0.0-dev-<short-sha>`
 ///
 /// # Errors
@@ -115,6 +189,7 @@ pub struct BuildVersionArgs {
 /// Returns an error if:
 /// - GitHub API fallback is attempted but fails (network error, auth failure,
 ///   etc.)
+/// - The version file cannot be read or is not a valid semantic version
 /// - Git repository cannot be discovered (for SHA fallback)
 /// - HEAD does not point to a valid commit (for SHA fallback)
 ///
@@ -155,8 +230,104 @@ pub struct BuildVersionArgs {
 /// ```json
 /// {"version":"0.0.0-dev-a1b2c3d","sha":"a1b2c3d","source":"git"}
 /// ```
-#[allow(clippy::disallowed_methods)] // CLI tool needs direct env access
+///
+/// With `--format github-actions` (appends to GITHUB_OUTPUT):
+/// ```text
+/// version=0.1.2
+/// source=cargo_toml
+/// ```
+///
+/// With `--format github-actions` (from git SHA fallback):
+/// ```text
+/// version=0.0.0-dev-a1b2c3d
+/// source=git
+/// sha=a1b2c3d
+/// ```
 pub fn build_version(args: BuildVersionArgs) -> Result<()> {
+    let mut output = resolve_build_version(&args)?;
+    let prefix = if args.no_prefix {
+        None
+    } else {
+        args.prefix.as_deref()
+    };
+    output.version = apply_prefix(&output.version, prefix);
+
+    match args.format.as_str() {
+        "version" => println!("{}", output.version),
+        "json" => println!(
+            "{}",
+            serde_json::to_string(&output).context("Failed to serialize build version")?
+        ),
+        "github-actions" => {
+            let mut outputs = vec![
+                ("version", output.version.as_str()),
+                ("source", output.source.as_str()),
+            ];
+            if let Some(sha) = &output.sha {
+                outputs.push(("sha", sha.as_str()));
+            }
+            write_github_outputs(args.github_output.as_deref(), &outputs)?;
+        }
+        _ => anyhow::bail!("Invalid format: {}", args.format),
+    }
+
+    Ok(())
+}
+
+/// Where `build-version` got its version from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionSource {
+    /// `BUILD_VERSION` or `CARGO_PKG_VERSION_OVERRIDE`.
+    Environment,
+    /// Next version calculated from GitHub releases.
+    GithubApi,
+    /// The `--version-file`.
+    VersionFile,
+    /// `CARGO_PKG_VERSION` or the manifest version.
+    CargoToml,
+    /// `0.0.0-dev-<short-sha>` from the git HEAD.
+    Git,
+}
+
+impl VersionSource {
+    /// The source name as used in JSON and GitHub Actions output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Environment => "environment",
+            Self::GithubApi => "github_api",
+            Self::VersionFile => "version_file",
+            Self::CargoToml => "cargo_toml",
+            Self::Git => "git",
+        }
+    }
+}
+
+/// Build version with its source, printed with `--format json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct BuildVersionOutput {
+    /// The build version.
+    pub version: String,
+    /// Short SHA of HEAD (only for the git fallback).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha: Option<String>,
+    /// Where the version came from.
+    pub source: VersionSource,
+}
+
+impl BuildVersionOutput {
+    fn new(version: impl Into<String>, source: VersionSource) -> Self {
+        Self {
+            version: version.into(),
+            sha: None,
+            source,
+        }
+    }
+}
+
+/// Apply the priority logic of [`build_version`] without printing.
+#[allow(clippy::disallowed_methods)] // CLI tool needs direct env access
+fn resolve_build_version(args: &BuildVersionArgs) -> Result<BuildVersionOutput> {
     // Try explicit overrides first (CI workflow should set BUILD_VERSION)
     let env_version = ["BUILD_VERSION", "CARGO_PKG_VERSION_OVERRIDE"]
         .into_iter()
@@ -164,51 +335,50 @@ pub fn build_version(args: BuildVersionArgs) -> Result<()> {
         .filter(|v| !v.trim().is_empty());
 
     if let Some(version) = env_version {
-        match args.format.as_str() {
-            "version" => println!("{}", version),
-            "json" => println!("{{\"version\":\"{}\",\"source\":\"environment\"}}", version),
-            _ => anyhow::bail!("Invalid format: {}", args.format),
-        }
-        return Ok(());
+        return Ok(BuildVersionOutput::new(version, VersionSource::Environment));
     }
 
     // Fallback: Try to query GitHub API via octocrab
     let is_github_actions = env::var("GITHUB_ACTIONS").is_ok();
     if is_github_actions {
-        let (owner, repo) = get_owner_repo(args.owner, args.repo)?;
+        let (owner, repo) = github::resolve_owner_repo(args.owner.clone(), args.repo.clone())?;
         let github_token = args.github_token.as_deref();
 
         let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
-        if let Ok((_, next)) =
-            rt.block_on(github::calculate_next_version(&owner, &repo, github_token))
-        {
-            match args.format.as_str() {
-                "version" => println!("{}", next),
-                "json" => println!("{{\"version\":\"{}\",\"source\":\"github_api\"}}", next),
-                _ => anyhow::bail!("Invalid format: {}", args.format),
-            }
-            return Ok(());
+        if let Ok((_, next)) = rt.block_on(github::calculate_next_version(
+            &owner,
+            &repo,
+            github_token,
+            args.tag_prefix.as_deref(),
+            false,
+        )) {
+            return Ok(BuildVersionOutput::new(next, VersionSource::GithubApi));
         }
     }
 
+    // An explicit version file is authoritative over the manifest
+    if let Some(version_file) = &args.version_file {
+        let version = read_version_file(version_file)?;
+        return Ok(BuildVersionOutput::new(version, VersionSource::VersionFile));
+    }
+
+    // Cargo sets CARGO_PKG_VERSION for build scripts and `cargo run`
+    if let Some(version) = cargo_pkg_version() {
+        return Ok(BuildVersionOutput::new(version, VersionSource::CargoToml));
+    }
+
     // Fall back to manifest version (from Cargo.toml), optionally append SHA if
     // available
-    if let Some(manifest_version) = read_manifest_version(&args.manifest) {
+    if let Some(manifest_version) = read_manifest_version(&args.manifest)? {
         let trimmed = manifest_version.trim();
         if !trimmed.is_empty() && trimmed != "0.0.0" {
-            let version_with_sha = short_sha(&args.repo_path)
+            let version_with_sha = short_sha(&args.repo_path, args.sha_length)
                 .map(|sha| format!("{trimmed}-{sha}"))
                 .unwrap_or_else(|| trimmed.to_string());
-
-            match args.format.as_str() {
-                "version" => println!("{version_with_sha}"),
-                "json" => println!(
-                    "{{\"version\":\"{}\",\"source\":\"cargo_toml\"}}",
-                    version_with_sha
-                ),
-                _ => anyhow::bail!("Invalid format: {}", args.format),
-            }
-            return Ok(());
+            return Ok(BuildVersionOutput::new(
+                version_with_sha,
+                VersionSource::CargoToml,
+            ));
         }
     }
 
@@ -222,22 +392,13 @@ pub fn build_version(args: BuildVersionArgs) -> Result<()> {
 
     let head = repo.head().context("Failed to read HEAD")?;
     let commit_id = head.id().context("HEAD does not point to a commit")?;
-    let short_sha = commit_id
-        .shorten()
-        .context("Failed to shorten commit SHA")?;
-
-    let dev_version = format!("0.0.0-dev-{}", short_sha);
-
-    match args.format.as_str() {
-        "version" => println!("{}", dev_version),
-        "json" => println!(
-            "{{\"version\":\"{}\",\"sha\":\"{}\",\"source\":\"git\"}}",
-            dev_version, short_sha
-        ),
-        _ => anyhow::bail!("Invalid format: {}", args.format),
-    }
+    let short_sha = abbreviate_sha(commit_id, args.sha_length)?;
 
-    Ok(())
+    Ok(BuildVersionOutput {
+        version: format!("0.0.0-dev-{}", short_sha),
+        sha: Some(short_sha),
+        source: VersionSource::Git,
+    })
 }
 
 /// Compute the build version using default arguments (local repo, version
@@ -255,9 +416,15 @@ pub fn build_version_for_repo(repo_path: impl Into<PathBuf>) -> Result<()> {
         owner: None,
         repo: None,
         github_token: None,
+        tag_prefix: None,
         manifest,
+        version_file: None,
         repo_path: repo_root,
+        sha_length: None,
+        prefix: None,
+        no_prefix: false,
         format: "version".to_string(),
+        github_output: None,
     })
 }
 
@@ -275,6 +442,8 @@ pub fn build_version_for_repo(repo_path: impl Into<PathBuf>) -> Result<()> {
 /// }
 /// ```
 ///
+/// [`emit_build_version`] does this (plus the rerun directives) in one call.
+///
 /// # Priority Order
 ///
 /// 1. **BUILD_VERSION** environment variable
@@ -282,6 +451,10 @@ pub fn build_version_for_repo(repo_path: impl Into<PathBuf>) -> Result<()> {
 /// 3. **GitHub API** (only in GitHub Actions)
 /// 4. **Manifest version** (from Cargo.toml) + git SHA if available
 /// 5. **Git SHA** fallback: `0.0.0-dev-<short-sha>`
+///
+/// Unlike the `build-version` command, `CARGO_PKG_VERSION` is not consulted:
+/// inside a build script it is always the plain manifest version, which would
+/// hide the git SHA this function is meant to add.
 pub fn compute_version_string(repo_path: impl Into<PathBuf>) -> Result<String> {
     let repo_root: PathBuf = repo_path.into();
     let manifest = repo_root.join("Cargo.toml");
@@ -299,7 +472,7 @@ pub fn compute_version_string(repo_path: impl Into<PathBuf>) -> Result<String> {
     // Fallback: Try to query GitHub API via octocrab
     let is_github_actions = env::var("GITHUB_ACTIONS").is_ok();
     if is_github_actions {
-        let (owner, repo) = get_owner_repo(None, None)?;
+        let (owner, repo) = github::resolve_owner_repo(None, None)?;
         let github_token = None::<String>;
 
         let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
@@ -307,6 +480,8 @@ pub fn compute_version_string(repo_path: impl Into<PathBuf>) -> Result<String> {
             &owner,
             &repo,
             github_token.as_deref(),
+            None,
+            false,
         )) {
             return Ok(next);
         }
@@ -314,10 +489,10 @@ pub fn compute_version_string(repo_path: impl Into<PathBuf>) -> Result<String> {
 
     // Fall back to manifest version (from Cargo.toml), optionally append SHA if
     // available
-    if let Some(manifest_version) = read_manifest_version(&manifest) {
+    if let Some(manifest_version) = read_manifest_version(&manifest)? {
         let trimmed = manifest_version.trim();
         if !trimmed.is_empty() && trimmed != "0.0.0" {
-            let version_with_sha = short_sha(&repo_root)
+            let version_with_sha = short_sha(&repo_root, None)
                 .map(|sha| format!("{trimmed}-{sha}"))
                 .unwrap_or_else(|| trimmed.to_string());
             return Ok(version_with_sha);
@@ -341,22 +516,157 @@ pub fn compute_version_string(repo_path: impl Into<PathBuf>) -> Result<String> {
     Ok(format!("0.0.0-dev-{}", short_sha))
 }
 
-fn short_sha(repo_path: &PathBuf) -> Option<String> {
+/// Compute the build version and print the build script directives for it.
+///
+/// Makes a `build.rs` a one-liner:
+///
+/// ```no_run
+/// fn main() {
+///     cargo_version_info::commands::emit_build_version(".").unwrap();
+/// }
+/// ```
+///
+/// The version is computed with [`compute_version_string`] and the following
+/// directives are printed to stdout, in this order (see
+/// [`build_env_directives`]):
+///
+/// ```text
+/// cargo:rustc-env=CARGO_PKG_VERSION=<version>
+/// cargo:rerun-if-changed=<repo_path>/.git/HEAD
+/// cargo:rerun-if-changed=<repo_path>/.git/refs
+/// cargo:rerun-if-env-changed=BUILD_VERSION
+/// cargo:rerun-if-env-changed=CARGO_PKG_VERSION_OVERRIDE
+/// ```
+///
+/// Returns the computed version.
+///
+/// # Errors
+///
+/// Returns an error if the version cannot be computed (see
+/// [`compute_version_string`]).
+pub fn emit_build_version(repo_path: impl Into<PathBuf>) -> Result<String> {
+    let repo_root: PathBuf = repo_path.into();
+    let version = compute_version_string(&repo_root)?;
+
+    for directive in build_env_directives(&version, &repo_root) {
+        println!("{}", directive);
+    }
+
+    Ok(version)
+}
+
+/// The build script directives printed by [`emit_build_version`].
+pub fn build_env_directives(version: &str, repo_path: &Path) -> Vec<String> {
+    let git_dir = repo_path.join(".git");
+    vec![
+        format!("cargo:rustc-env=CARGO_PKG_VERSION={}", version),
+        format!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display()),
+        format!("cargo:rerun-if-changed={}", git_dir.join("refs").display()),
+        "cargo:rerun-if-env-changed=BUILD_VERSION".to_string(),
+        "cargo:rerun-if-env-changed=CARGO_PKG_VERSION_OVERRIDE".to_string(),
+    ]
+}
+
+/// Read `CARGO_PKG_VERSION`, ignoring empty and placeholder ("0.0.0") values.
+fn cargo_pkg_version() -> Option<String> {
+    env::var("CARGO_PKG_VERSION")
+        .ok()
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty() && version != "0.0.0")
+}
+
+fn short_sha(repo_path: &PathBuf, sha_length: Option<usize>) -> Option<String> {
     let repo = gix::discover(repo_path).ok()?;
     let head = repo.head().ok()?;
     let commit_id = head.id()?;
-    let short = commit_id.shorten().ok()?;
-    Some(short.to_string())
+    abbreviate_sha(commit_id, sha_length).ok()
+}
+
+/// Read `package.version` from a manifest.
+///
+/// An inherited version (`version.workspace = true`) is resolved from
+/// `workspace.package.version` in the workspace root manifest: the one named
+/// by `package.workspace`, else the nearest ancestor with a `[workspace]`
+/// table.
+///
+/// Returns `None` when the manifest cannot be read or has no version, so
+/// callers can fall back to other sources.
+///
+/// # Errors
+///
+/// Returns an error if `version` is neither a string nor the workspace
+/// inheritance marker, or if an inherited version cannot be found in the
+/// workspace root.
+fn read_manifest_version(manifest: &Path) -> Result<Option<String>> {
+    let Some(value) = read_toml(manifest) else {
+        return Ok(None);
+    };
+    let Some(package) = value.get("package") else {
+        return Ok(None);
+    };
+    let Some(version) = package.get("version") else {
+        return Ok(None);
+    };
+    if let Some(version) = version.as_str() {
+        return Ok(Some(version.to_string()));
+    }
+
+    let inherited = version
+        .get("workspace")
+        .and_then(toml::Value::as_bool)
+        .unwrap_or(false);
+    if !inherited {
+        anyhow::bail!(
+            "Expected `version` in [package] of {} to be a string or `{{ workspace = true }}`, \
+             found {}",
+            manifest.display(),
+            version.type_str()
+        );
+    }
+
+    let manifest_dir = manifest
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", manifest.display()))?
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let root_manifest = match package.get("workspace").and_then(toml::Value::as_str) {
+        Some(root) => Some(manifest_dir.join(root).join("Cargo.toml")),
+        None => manifest_dir
+            .ancestors()
+            .map(|dir| dir.join("Cargo.toml"))
+            .find(|candidate| {
+                read_toml(candidate).is_some_and(|root| root.get("workspace").is_some())
+            }),
+    };
+    let Some(root_manifest) = root_manifest else {
+        anyhow::bail!(
+            "The version in {} is inherited from the workspace, but no workspace root was found",
+            manifest.display()
+        );
+    };
+    let version = read_toml(&root_manifest)
+        .and_then(|root| {
+            root.get("workspace")
+                .and_then(|workspace| workspace.get("package"))
+                .and_then(|package| package.get("version"))
+                .and_then(toml::Value::as_str)
+                .map(ToString::to_string)
+        })
+        .with_context(|| {
+            format!(
+                "The version in {} is inherited from the workspace, but {} has no \
+                 [workspace.package] version",
+                manifest.display(),
+                root_manifest.display()
+            )
+        })?;
+    Ok(Some(version))
 }
 
-fn read_manifest_version(manifest: &PathBuf) -> Option<String> {
-    let contents = fs::read_to_string(manifest).ok()?;
-    let value: toml::Value = toml::from_str(&contents).ok()?;
-    value
-        .get("package")
-        .and_then(|pkg| pkg.get("version"))
-        .and_then(|v| v.as_str())
-        .map(ToString::to_string)
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    let contents = fs::read_to_string(path).ok()?;
+    toml::from_str(&contents).ok()
 }
 
 #[cfg(test)]
@@ -375,9 +685,15 @@ mod tests {
             owner: None,
             repo: None,
             github_token: None,
+            tag_prefix: None,
             manifest: "./Cargo.toml".into(),
+            version_file: None,
             repo_path: ".".into(),
+            sha_length: None,
+            prefix: None,
+            no_prefix: false,
             format: "version".to_string(),
+            github_output: None,
         };
         let result = build_version(args);
         unsafe {
@@ -395,9 +711,15 @@ mod tests {
             owner: None,
             repo: None,
             github_token: None,
+            tag_prefix: None,
             manifest: "./Cargo.toml".into(),
+            version_file: None,
             repo_path: ".".into(),
+            sha_length: None,
+            prefix: None,
+            no_prefix: false,
             format: "json".to_string(),
+            github_output: None,
         };
         let result = build_version(args);
         unsafe {
@@ -406,6 +728,38 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_build_version_github_actions() {
+        unsafe {
+            env::set_var("BUILD_VERSION", "3.1.0");
+        }
+        let output_file = tempfile::NamedTempFile::new().unwrap();
+        let args = BuildVersionArgs {
+            owner: None,
+            repo: None,
+            github_token: None,
+            tag_prefix: None,
+            manifest: "./Cargo.toml".into(),
+            version_file: None,
+            repo_path: ".".into(),
+            sha_length: None,
+            prefix: None,
+            no_prefix: false,
+            format: "github-actions".to_string(),
+            github_output: Some(output_file.path().to_string_lossy().to_string()),
+        };
+        let result = build_version(args);
+        unsafe {
+            env::remove_var("BUILD_VERSION");
+        }
+        assert!(result.is_ok());
+
+        let content = std::fs::read_to_string(output_file.path()).unwrap();
+        assert!(content.contains("version=3.1.0"));
+        assert!(content.contains("source=environment"));
+        assert!(!content.contains("sha="));
+    }
+
     #[test]
     fn test_build_version_cargo_pkg_version() {
         // Clear BUILD_VERSION if set
@@ -418,16 +772,59 @@ mod tests {
             owner: None,
             repo: None,
             github_token: None,
+            tag_prefix: None,
             manifest: "./Cargo.toml".into(),
+            version_file: None,
             repo_path: ".".into(),
+            sha_length: None,
+            prefix: None,
+            no_prefix: false,
             format: "version".to_string(),
+            github_output: None,
         };
         let result = build_version(args);
         unsafe {
             env::remove_var("CARGO_PKG_VERSION");
         }
-        // May succeed if CARGO_PKG_VERSION is set and not 0.0.0
-        let _ = result;
+        // CARGO_PKG_VERSION is honored before manifest parsing
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_build_version_version_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join("version.txt");
+        std::fs::write(&version_file, "  2.4.6\n").unwrap();
+
+        let args = BuildVersionArgs {
+            owner: None,
+            repo: None,
+            github_token: None,
+            tag_prefix: None,
+            manifest: "./Cargo.toml".into(),
+            version_file: Some(version_file),
+            repo_path: ".".into(),
+            sha_length: None,
+            prefix: None,
+            no_prefix: false,
+            format: "json".to_string(),
+            github_output: None,
+        };
+        assert!(build_version(args).is_ok());
+    }
+
+    #[test]
+    fn test_build_env_directives() {
+        assert_eq!(
+            build_env_directives("1.2.3-abc1234", Path::new("repo")),
+            [
+                "cargo:rustc-env=CARGO_PKG_VERSION=1.2.3-abc1234",
+                "cargo:rerun-if-changed=repo/.git/HEAD",
+                "cargo:rerun-if-changed=repo/.git/refs",
+                "cargo:rerun-if-env-changed=BUILD_VERSION",
+                "cargo:rerun-if-env-changed=CARGO_PKG_VERSION_OVERRIDE",
+            ]
+        );
     }
 
     #[test]
@@ -439,9 +836,15 @@ mod tests {
             owner: None,
             repo: None,
             github_token: None,
+            tag_prefix: None,
             manifest: "./Cargo.toml".into(),
+            version_file: None,
             repo_path: ".".into(),
+            sha_length: None,
+            prefix: None,
+            no_prefix: false,
             format: "invalid".to_string(),
+            github_output: None,
         };
         let result = build_version(args);
         unsafe {
@@ -459,9 +862,15 @@ mod tests {
             owner: None,
             repo: None,
             github_token: None,
+            tag_prefix: None,
             manifest: "./Cargo.toml".into(),
+            version_file: None,
             repo_path: ".".into(),
+            sha_length: None,
+            prefix: None,
+            no_prefix: false,
             format: "version".to_string(),
+            github_output: None,
         };
         let result = build_version(args);
         unsafe {
@@ -481,9 +890,15 @@ mod tests {
             owner: None,
             repo: None,
             github_token: None,
+            tag_prefix: None,
             manifest: "./Cargo.toml".into(),
+            version_file: None,
             repo_path: ".".into(),
+            sha_length: None,
+            prefix: None,
+            no_prefix: false,
             format: "version".to_string(),
+            github_output: None,
         };
         let result = build_version(args);
         unsafe {
@@ -493,4 +908,91 @@ mod tests {
         // BUILD_VERSION should take priority
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_build_version_output_json() {
+        let output = BuildVersionOutput::new("0.1.2", VersionSource::CargoToml);
+        assert_eq!(
+            serde_json::to_string(&output).unwrap(),
+            r#"{"version":"0.1.2","source":"cargo_toml"}"#
+        );
+
+        let output = BuildVersionOutput {
+            version: "0.0.0-dev-a1b2c3d".to_string(),
+            sha: Some("a1b2c3d".to_string()),
+            source: VersionSource::Git,
+        };
+        assert_eq!(
+            serde_json::to_string(&output).unwrap(),
+            r#"{"version":"0.0.0-dev-a1b2c3d","sha":"a1b2c3d","source":"git"}"#
+        );
+    }
+
+    #[test]
+    fn test_read_manifest_version_inherited() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            r#"
+[workspace]
+members = ["member"]
+
+[workspace.package]
+version = "1.4.0"
+"#,
+        )
+        .unwrap();
+        let member_dir = dir.path().join("member");
+        std::fs::create_dir_all(&member_dir).unwrap();
+        let member_manifest = member_dir.join("Cargo.toml");
+        std::fs::write(
+            &member_manifest,
+            r#"
+[package]
+name = "member"
+version.workspace = true
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            read_manifest_version(&member_manifest).unwrap(),
+            Some("1.4.0".to_string())
+        );
+
+        // An explicit `package.workspace` path is followed
+        std::fs::write(
+            &member_manifest,
+            r#"
+[package]
+name = "member"
+workspace = ".."
+version = { workspace = true }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            read_manifest_version(&member_manifest).unwrap(),
+            Some("1.4.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_read_manifest_version_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        assert_eq!(read_manifest_version(&manifest).unwrap(), None);
+
+        std::fs::write(&manifest, "[package]\nname = \"a\"\nversion = [1, 2]\n").unwrap();
+        let error = read_manifest_version(&manifest).unwrap_err().to_string();
+        assert!(error.contains("found array"), "{}", error);
+
+        // Inherited, but the root has no [workspace.package] version
+        std::fs::write(
+            &manifest,
+            "[workspace]\n\n[package]\nname = \"a\"\nversion.workspace = true\n",
+        )
+        .unwrap();
+        let error = read_manifest_version(&manifest).unwrap_err().to_string();
+        assert!(error.contains("inherited from the workspace"), "{}", error);
+    }
 }
@@ -31,12 +31,35 @@
 //!
 //! # Auto-suggest from GitHub releases
 //! cargo version-info bump --auto --github-token $TOKEN
+//!
+//! # Refuse to commit if Cargo.toml has other uncommitted edits
+//! cargo version-info bump --patch --strict
+//!
+//! # Update both [package] and [workspace.package] versions
+//! cargo version-info bump --patch --location both
+//!
+//! # Also update sibling crates' version requirements on this crate
+//! cargo version-info bump --minor --update-dependents
+//!
+//! # Commit on a detached HEAD anyway
+//! cargo version-info bump --patch --force
+//!
+//! # Review the planned change before anything is written
+//! cargo version-info bump --minor --interactive
+//!
+//! # Commit as a bot identity
+//! cargo version-info bump --patch --author "release-bot <bot@example.com>"
+//!
+//! # Machine-readable result with the new commit SHA
+//! cargo version-info bump --patch --format json
 //! ```
 
 use std::path::PathBuf;
 
 use clap::Parser;
 
+use crate::commands::common::CargoOptions;
+
 /// Arguments for the `bump` command.
 ///
 /// This struct uses `clap`'s derive macros to automatically parse command-line
@@ -57,6 +80,20 @@ pub struct BumpArgs {
     #[arg(long)]
     pub manifest_path: Option<PathBuf>,
 
+    /// Workspace member to bump (standard cargo flag).
+    ///
+    /// Selects the member by `package.name` instead of by manifest path.
+    /// Unlike cargo there is no `-p` short form, which is taken by
+    /// `--patch`.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// cargo version-info bump --package my-crate --patch
+    /// ```
+    #[arg(long, value_name = "NAME")]
+    pub package: Option<String>,
+
     /// Manually set the target version.
     ///
     /// Use this to set an explicit version number. The version must be a valid
@@ -77,6 +114,21 @@ pub struct BumpArgs {
     #[arg(short = 'a', long, conflicts_with_all = ["version", "major", "minor", "patch"])]
     pub auto: bool,
 
+    /// Consider pre-release tags when looking up the latest version (for
+    /// --auto).
+    ///
+    /// When the latest tag is a pre-release, the pre-release counter is
+    /// advanced instead of bumping the patch version.
+    ///
+    /// # Examples
+    ///
+    /// ```text
+    /// v1.0.0-rc.1 -> 1.0.0-rc.2
+    /// v1.0.0-beta -> 1.0.0-beta.1
+    /// ```
+    #[arg(long, requires = "auto")]
+    pub include_prereleases: bool,
+
     /// Increment the major version (X.0.0).
     ///
     /// This resets minor and patch to 0. Use for breaking changes.
@@ -138,6 +190,14 @@ pub struct BumpArgs {
     #[arg(long, env = "GITHUB_TOKEN")]
     pub github_token: Option<String>,
 
+    /// Tag prefix for per-crate release tags (e.g., `foo-v` for `foo-v1.2.3`,
+    /// for --auto).
+    ///
+    /// Only tags starting with the prefix are considered, and the prefix is
+    /// stripped before parsing. Defaults to stripping a leading `v`/`V`.
+    #[arg(long)]
+    pub tag_prefix: Option<String>,
+
     /// Don't commit changes, just update files.
     ///
     /// When this flag is set, the version will be updated in Cargo.toml but
@@ -145,4 +205,132 @@ pub struct BumpArgs {
     /// committing separately.
     #[arg(long)]
     pub no_commit: bool,
+
+    /// Abort instead of hunk-filtering when the manifest has non-version
+    /// changes.
+    ///
+    /// By default, uncommitted non-version edits in the manifest are left out
+    /// of the bump commit via hunk-level staging. With `--strict`, the bump
+    /// fails and lists those changes so they can be stashed or committed
+    /// first.
+    #[arg(long, conflicts_with = "no_commit")]
+    pub strict: bool,
+
+    /// Fold the version change into HEAD's commit instead of creating a new
+    /// one.
+    ///
+    /// The amended commit keeps HEAD's message, author and parents. Bump
+    /// refuses to amend a commit that a remote-tracking branch already
+    /// contains, since that rewrites published history, unless `--force` is
+    /// given.
+    #[arg(long, conflicts_with = "no_commit")]
+    pub amend: bool,
+
+    /// Which version field(s) to update in the manifest.
+    ///
+    /// - `auto`: `[package]` if present, otherwise `[workspace.package]`
+    ///   (default)
+    /// - `package`: only `[package] version`
+    /// - `workspace`: only `[workspace.package] version`
+    /// - `both`: every version field found, for workspace roots that keep both
+    ///   in sync
+    #[arg(long, default_value = "auto")]
+    pub location: String,
+
+    /// Update version requirements in workspace crates that depend on the
+    /// bumped crate.
+    ///
+    /// Path dependencies with a `version` requirement (e.g.
+    /// `foo = { path = "../foo", version = "0.1" }`) in workspace members and
+    /// `[workspace.dependencies]` are rewritten to match the new version.
+    /// Changed manifests are included in the bump commit.
+    #[arg(long)]
+    pub update_dependents: bool,
+
+    /// Allow bumping to a version lower than the current one.
+    ///
+    /// By default, a target version with lower semver precedence than the
+    /// current version (e.g. `--version 0.1.0` on a crate at `0.2.0`) is
+    /// rejected, since it is usually a mistake.
+    #[arg(long)]
+    pub allow_downgrade: bool,
+
+    /// Rewrite the version even when the target is the same version, written
+    /// differently (e.g. `1.0` -> `1.0.0`).
+    ///
+    /// Versions are compared by their parsed semver. Without this flag, a
+    /// target equal to the current version is rejected, however it is
+    /// written. With it, the version is written in canonical
+    /// `x.y.z[-pre][+build]` form.
+    #[arg(long, alias = "allow-empty-version-change")]
+    pub normalize: bool,
+
+    /// Commit even when HEAD is detached, and allow `--amend` on a pushed
+    /// commit.
+    ///
+    /// By default, bump refuses to commit on a detached HEAD (e.g. during a
+    /// rebase or in a CI checkout of a tag), since the commit would not be on
+    /// any branch and is easily lost. With `--force`, HEAD is moved to the new
+    /// commit anyway.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Show the planned change and ask for confirmation before writing.
+    ///
+    /// Prints the version change and the files that will be modified, then
+    /// prompts `Proceed? [y/N]`. The prompt is skipped when stdin is not a
+    /// terminal, so scripts are unaffected.
+    #[arg(long)]
+    pub interactive: bool,
+
+    /// Proceed without asking, even with `--interactive`.
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+
+    /// Author of the bump commit as `Name <email>`.
+    ///
+    /// Overrides `user.name`/`user.email` from git config, e.g. to commit as
+    /// a bot in automation.
+    #[arg(long, value_name = "NAME <EMAIL>", conflicts_with = "no_commit")]
+    pub author: Option<String>,
+
+    /// Committer of the bump commit as `Name <email>`.
+    ///
+    /// Overrides `user.name`/`user.email` from git config. Independent of
+    /// `--author`.
+    #[arg(long, value_name = "NAME <EMAIL>", conflicts_with = "no_commit")]
+    pub committer: Option<String>,
+
+    /// Attach a git note to the bump commit (e.g. `released by CI run 123`).
+    ///
+    /// The note is written to `refs/notes/commits`, where `git log` shows it,
+    /// recording provenance without changing the commit message. Notes are
+    /// not pushed with branches: push them with an explicit refspec, e.g.
+    /// `git push origin refs/notes/commits`.
+    #[arg(long, value_name = "TEXT", conflicts_with = "no_commit")]
+    pub note: Option<String>,
+
+    /// Shell command to run after a successful bump (e.g. `cargo update -w`
+    /// to refresh the lockfile).
+    ///
+    /// Runs with `sh -c` (`cmd /C` on Windows) in the current directory, with
+    /// `OLD_VERSION` and `NEW_VERSION` set in its environment. The bump (and
+    /// its commit) is not rolled back when the hook fails; bump reports the
+    /// failure and exits with an error after printing its result.
+    #[arg(long, value_name = "COMMAND")]
+    pub post_bump: Option<String>,
+
+    /// Output format for the result.
+    ///
+    /// - `text`: Human-readable progress and result (default)
+    /// - `json`: A single `{"old", "new", "commit", "committed"}` object on
+    ///   stdout for scripting; `commit` is the short SHA, or `null` with
+    ///   `--no-commit`
+    #[arg(long, default_value = "text")]
+    pub format: String,
+
+    /// Network access for `cargo metadata`, from the global `--offline` and
+    /// `--frozen` flags.
+    #[arg(skip)]
+    pub cargo: CargoOptions,
 }
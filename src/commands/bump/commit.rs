@@ -29,7 +29,8 @@
 //!
 //! 1. **Discover Repository**: Find the `.git` directory
 //! 2. **Verify Changes**: Ensure version actually changed
-//! 3. **Detect Other Changes**: Warn if non-version changes exist
+//! 3. **Detect Other Changes**: Warn if non-version changes exist (or abort
+//!    with `--strict`)
 //! 4. **Stage File**: Add file to git index
 //! 5. **Build Tree**: Convert index to tree object
 //! 6. **Create Commit**: Write commit object
@@ -97,16 +98,80 @@
 //! The git command `git add -p` (interactive patch mode) does this, but
 //! implementing it programmatically is non-trivial.
 
-use std::path::Path;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::str::FromStr;
 
 use anyhow::{
     Context,
     Result,
 };
-use bstr::ByteSlice;
+use bstr::{
+    BStr,
+    ByteSlice,
+};
 use smallvec::SmallVec;
 
-use super::diff;
+use super::{
+    diff,
+    index,
+};
+
+/// Options controlling how the version commit is created.
+#[derive(Debug, Clone, Default)]
+pub struct CommitOptions {
+    /// Fail instead of hunk-filtering when the manifest has non-version
+    /// changes.
+    pub strict: bool,
+    /// Other manifests to include in the same commit (e.g. workspace siblings
+    /// whose dependency requirements were updated).
+    pub additional_manifests: Vec<PathBuf>,
+    /// Author of the commit, instead of the git config identity.
+    pub author: Option<Identity>,
+    /// Committer of the commit, instead of the git config identity.
+    pub committer: Option<Identity>,
+    /// Replace HEAD's commit (keeping its message, parents and, unless
+    /// `author` is set, author) instead of adding a new one.
+    pub amend: bool,
+}
+
+/// A commit identity given as `Name <email>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    /// Display name.
+    pub name: String,
+    /// Email address.
+    pub email: String,
+}
+
+impl FromStr for Identity {
+    type Err = anyhow::Error;
+
+    fn from_str(identity: &str) -> Result<Self> {
+        let invalid =
+            || anyhow::anyhow!("Invalid identity '{}': expected \"Name <email>\"", identity);
+
+        let rest = identity.trim().strip_suffix('>').ok_or_else(invalid)?;
+        let (name, email) = rest.rsplit_once('<').ok_or_else(invalid)?;
+        let name = name.trim();
+        let email = email.trim();
+        if name.is_empty()
+            || email.is_empty()
+            || [name, email]
+                .iter()
+                .any(|part| part.contains(['<', '>', '\n']))
+        {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            email: email.to_string(),
+        })
+    }
+}
 
 /// Commit version-related changes using pure gix (no git binary).
 ///
@@ -124,12 +189,18 @@ use super::diff;
 /// * `manifest_path` - Path to the Cargo.toml file (absolute or relative)
 /// * `old_version` - The previous version (for verification and commit message)
 /// * `new_version` - The new version (for verification and commit message)
+/// * `options` - Commit behavior (see [`CommitOptions`])
+///
+/// # Returns
+///
+/// Returns the object ID of the new commit.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - Not in a git repository
 /// - File doesn't have version changes
+/// - File has non-version changes and `options.strict` is set
 /// - Git operations fail (staging, tree building, commit creation)
 /// - HEAD cannot be updated
 ///
@@ -139,10 +210,13 @@ use super::diff;
 /// # use std::path::Path;
 /// # use anyhow::Result;
 /// # fn example() -> Result<()> {
-/// use cargo_version_info::commands::bump::commit::commit_version_changes;
+/// use cargo_version_info::commands::bump::commit::{
+///     CommitOptions,
+///     commit_version_changes,
+/// };
 ///
 /// let manifest = Path::new("./Cargo.toml");
-/// commit_version_changes(manifest, "0.1.0", "0.2.0")?;
+/// commit_version_changes(manifest, "0.1.0", "0.2.0", &CommitOptions::default())?;
 /// # Ok(())
 /// # }
 /// ```
@@ -193,9 +267,13 @@ use super::diff;
 /// Creates a commit object with:
 /// - Tree: Built from the staged index
 /// - Parents: Current HEAD commit
-/// - Author/Committer: From git config or defaults
+/// - Author/Committer: From `options`, falling back to git config
 /// - Message: Conventional commit format "chore(version): bump X -> Y"
 ///
+/// With `options.amend`, the commit replaces HEAD's instead (like `git commit
+/// --amend`): it has HEAD's parents, message and author, and HEAD's tree plus
+/// the version change.
+///
 /// ## HEAD Update
 ///
 /// Updates the current branch reference to point to the new commit. This is
@@ -204,34 +282,42 @@ pub fn commit_version_changes(
     manifest_path: &Path,
     old_version: &str,
     new_version: &str,
-) -> Result<()> {
+    options: &CommitOptions,
+) -> Result<gix::ObjectId> {
     // Discover git repository by walking up from the manifest's directory
-    let repo = gix::discover(manifest_path.parent().unwrap_or_else(|| Path::new(".")))
-        .context("Not in a git repository")?;
+    let repo = discover_repo(manifest_path)?;
 
     // Calculate relative path from repository root
-    // This is needed for index entries which use repo-relative paths
-    let repo_path = repo.path().parent().context("Invalid repository path")?;
-    let relative_path = manifest_path
-        .strip_prefix(repo_path)
-        .or_else(|_| manifest_path.strip_prefix("."))
-        .unwrap_or(manifest_path);
+    // This is needed for tree entries which use repo-relative paths
+    let relative_path = &repo_relative_path(&repo, manifest_path)?;
 
     // Read current working directory content
     let current_content = std::fs::read_to_string(manifest_path)
         .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
 
-    // Get HEAD commit to compare against
+    // Get HEAD commit to compare against; in a repository without commits
+    // yet (unborn HEAD), the bump becomes the first commit
     let head = repo.head().context("Failed to read HEAD")?;
-    let head_commit_id = head.id().context("HEAD does not point to a commit")?;
-    let head_commit = repo
-        .find_object(head_commit_id)
-        .context("Failed to find HEAD commit")?
-        .try_into_commit()
-        .context("HEAD is not a commit")?;
+    let head_commit = if head.is_unborn() {
+        if options.amend {
+            anyhow::bail!("HEAD has no commit to amend");
+        }
+        None
+    } else {
+        let head_commit_id = head.id().context("HEAD does not point to a commit")?;
+        let head_commit = repo
+            .find_object(head_commit_id)
+            .context("Failed to find HEAD commit")?
+            .try_into_commit()
+            .context("HEAD is not a commit")?;
+        Some(head_commit)
+    };
 
     // Get the tree from HEAD (what's currently committed)
-    let head_tree = head_commit.tree().context("Failed to get HEAD tree")?;
+    let head_tree = match &head_commit {
+        Some(head_commit) => head_commit.tree().context("Failed to get HEAD tree")?,
+        None => repo.empty_tree(),
+    };
 
     // Verify that version changes exist
     verify_version_changes(
@@ -242,39 +328,263 @@ pub fn commit_version_changes(
         new_version,
     )?;
 
-    // Get HEAD content for comparison
-    let head_content = get_head_content(&head_tree, relative_path)?;
+    // Stage the manifest, plus any additional manifests, using only their
+    // version-related hunks
+    let mut staged_files = vec![(
+        relative_path.to_path_buf(),
+        stage_version_changes(
+            &repo,
+            &head_tree,
+            relative_path,
+            &current_content,
+            old_version,
+            new_version,
+            options.strict,
+        )?,
+    )];
+    for additional in &options.additional_manifests {
+        let additional_path = repo_relative_path(&repo, additional)?;
+        let additional_content = std::fs::read_to_string(additional)
+            .with_context(|| format!("Failed to read {}", additional.display()))?;
+        let blob_id = stage_version_changes(
+            &repo,
+            &head_tree,
+            &additional_path,
+            &additional_content,
+            old_version,
+            new_version,
+            options.strict,
+        )?;
+        staged_files.push((additional_path, blob_id));
+    }
+
+    // Build tree by modifying HEAD's tree (not creating minimal tree!)
+    // We need to preserve all other files in the repository
+    let tree_id = update_tree_with_files(&repo, &head_tree, &staged_files)?;
+
+    // Create the commit, following conventional commits format. An amended
+    // commit replaces HEAD's instead, so it takes over its parents, message
+    // and author
+    let committer = get_signature(&repo, options.committer.as_ref())?;
+    let (parents, message, author) = if let Some(head_commit) = &head_commit
+        && options.amend
+    {
+        let author = match &options.author {
+            Some(author) => get_signature(&repo, Some(author))?,
+            None => head_commit
+                .author()
+                .context("Failed to read HEAD commit author")?
+                .to_owned()
+                .context("Invalid HEAD commit author")?,
+        };
+        let message = head_commit
+            .message_raw()
+            .context("Failed to read HEAD commit message")?
+            .to_str_lossy()
+            .into_owned();
+        let parents = head_commit.parent_ids().map(|id| id.detach()).collect();
+        (parents, message, author)
+    } else {
+        let message = format!("chore(version): bump {} -> {}", old_version, new_version);
+        let author = get_signature(&repo, options.author.as_ref())?;
+        let parents = head_commit.iter().map(|commit| commit.id).collect();
+        (parents, message, author)
+    };
+    let commit_id = create_commit(&repo, &tree_id, &parents, &message, author, committer)?;
+
+    // Update HEAD to point to the new commit
+    let reflog_message = if options.amend {
+        "bump version (amend)"
+    } else {
+        "bump version"
+    };
+    update_head(&repo, commit_id, reflog_message)?;
+
+    // Record the committed content in the index, so the files do not show up
+    // as staged changes against the new HEAD
+    update_index(&repo, &staged_files)?;
+
+    Ok(commit_id)
+}
+
+/// Commit the working tree content of whole files on top of HEAD.
+///
+/// Unlike [`commit_version_changes`], no hunk filtering is done: each file is
+/// committed exactly as it is on disk. The repository is discovered from the
+/// first file's directory; all files must belong to it. Used by commands that
+/// edit other manifest fields, such as `set-edition`. Returns the object ID
+/// of the new commit.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - `files` is empty or not in a git repository
+/// - A file cannot be read
+/// - Git operations fail (blob/tree/commit creation)
+/// - HEAD cannot be updated
+pub fn commit_files(files: &[PathBuf], message: &str) -> Result<gix::ObjectId> {
+    let first = files.first().context("No files to commit")?;
+    let repo = discover_repo(first)?;
+
+    let head = repo.head().context("Failed to read HEAD")?;
+    let head_commit_id = head.id().context("HEAD does not point to a commit")?;
+    let head_tree = repo
+        .find_object(head_commit_id)
+        .context("Failed to find HEAD commit")?
+        .try_into_commit()
+        .context("HEAD is not a commit")?
+        .tree()
+        .context("Failed to get HEAD tree")?;
+
+    let mut staged_files = Vec::new();
+    for file in files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        staged_files.push((
+            repo_relative_path(&repo, file)?,
+            write_blob(&repo, &content)?,
+        ));
+    }
+
+    let tree_id = update_tree_with_files(&repo, &head_tree, &staged_files)?;
+    let signature = get_signature_from_config(&repo)?;
+    let commit_id = create_commit(
+        &repo,
+        &tree_id,
+        &[head_commit_id.detach()],
+        message,
+        signature.clone(),
+        signature,
+    )?;
+    update_head(&repo, commit_id, message)?;
+    update_index(&repo, &staged_files)?;
+
+    Ok(commit_id)
+}
+
+/// Stage `files` (repository-relative paths and their committed blob IDs) in
+/// the repository's index.
+fn update_index(repo: &gix::Repository, files: &[(PathBuf, gix::ObjectId)]) -> Result<()> {
+    let index_path = repo.index_path();
+    let mut state = if index_path.exists() {
+        index::load_index_state(&index_path, repo.object_hash())?
+    } else {
+        gix::index::State::new(repo.object_hash())
+    };
+    for (path, blob_id) in files {
+        state = index::stage_file(&index_path, repo, path, *blob_id, state)?;
+    }
+    Ok(())
+}
+
+/// Discover the git repository containing `path` by walking up from its
+/// directory.
+///
+/// A bare file name (such as the default `Cargo.toml`) is looked up from the
+/// current directory.
+///
+/// # Errors
+///
+/// Returns an error if `path` is not inside a git repository.
+pub fn discover_repo(path: &Path) -> Result<gix::Repository> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    gix::discover(dir).context("Not in a git repository")
+}
+
+/// Compute the path of `path` relative to the working directory of `repo`, as
+/// used by tree and index entries.
+///
+/// Both paths are canonicalized first, so relative paths (resolved against
+/// the current directory, which may be a subdirectory of the repository) and
+/// absolute paths through symlinks (as cargo metadata reports them) work
+/// alike.
+///
+/// # Errors
+///
+/// Returns an error if the repository has no working directory, `path` does
+/// not exist, or it is outside the working directory.
+pub fn repo_relative_path(repo: &gix::Repository, path: &Path) -> Result<PathBuf> {
+    let workdir = repo
+        .workdir()
+        .context("Repository has no working directory")?;
+    let canonical_workdir = workdir
+        .canonicalize()
+        .context("Failed to resolve repository working directory")?;
+    let canonical_path = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+
+    canonical_path
+        .strip_prefix(&canonical_workdir)
+        .map(Path::to_path_buf)
+        .with_context(|| {
+            format!(
+                "{} is not inside the repository at {}",
+                path.display(),
+                workdir.display()
+            )
+        })
+}
+
+/// Write the version-related content of a file as a blob.
+///
+/// If the file only has version changes relative to HEAD, the whole file is
+/// staged. Otherwise only version hunks are applied on top of HEAD's content,
+/// or, when `strict` is set, an error listing the other changes is returned.
+///
+/// # Returns
+///
+/// Returns the object ID of the blob to stage.
+fn stage_version_changes(
+    repo: &gix::Repository,
+    head_tree: &gix::Tree,
+    relative_path: &Path,
+    current_content: &str,
+    old_version: &str,
+    new_version: &str,
+    strict: bool,
+) -> Result<gix::ObjectId> {
+    // Get HEAD content for comparison; a file that is new (e.g. in the first
+    // commit) is staged as a whole
+    let Some(head_content) = get_head_content(head_tree, relative_path)? else {
+        return write_blob(repo, current_content);
+    };
 
     // Check if there are non-version changes in the file
     let has_other_changes =
-        diff::has_non_version_changes(&head_content, &current_content, old_version, new_version);
+        diff::has_non_version_changes(&head_content, current_content, old_version, new_version);
+
+    if has_other_changes && strict {
+        let changes =
+            diff::non_version_changes(&head_content, current_content, old_version, new_version);
+        anyhow::bail!(
+            "{} has non-version changes (--strict):\n{}\n\
+             The version was updated but not committed. Stash or commit these changes first.",
+            relative_path.display(),
+            changes.join("\n")
+        );
+    }
 
     // Create the content to stage
     let staged_content = if has_other_changes {
         // File has non-version changes - apply only version hunks
-        eprintln!("⚠️  Using hunk-level staging: only version lines will be committed.");
+        eprintln!(
+            "⚠️  Using hunk-level staging for {}: only version lines will be committed.",
+            relative_path.display()
+        );
 
         // Apply only version-related hunks
-        diff::apply_version_hunks(&head_content, &current_content, old_version, new_version)?
+        diff::apply_version_hunks(&head_content, current_content, old_version, new_version)?
     } else {
         // File only has version changes - stage the whole file
-        current_content.clone()
+        current_content.to_string()
     };
 
     // Create blob for the staged content
-    let blob_id = write_blob(&repo, &staged_content)?;
-
-    // Build tree by modifying HEAD's tree (not creating minimal tree!)
-    // We need to preserve all other files in the repository
-    let tree_id = update_tree_with_file(&repo, &head_tree, relative_path, blob_id)?;
-
-    // Create the commit
-    let commit_id = create_commit(&repo, &tree_id, head_commit_id, old_version, new_version)?;
-
-    // Update HEAD to point to the new commit
-    update_head(&repo, commit_id)?;
-
-    Ok(())
+    write_blob(repo, &staged_content)
 }
 
 /// Get the content of a file from the HEAD tree.
@@ -286,16 +596,19 @@ pub fn commit_version_changes(
 ///
 /// # Returns
 ///
-/// Returns the file content as a string.
+/// Returns the file content as a string, or `None` if the file doesn't exist
+/// in HEAD.
 ///
 /// # Errors
 ///
-/// Returns an error if the file doesn't exist in HEAD or cannot be read.
-fn get_head_content(head_tree: &gix::Tree, relative_path: &Path) -> Result<String> {
-    let entry = head_tree
+/// Returns an error if the file cannot be read.
+fn get_head_content(head_tree: &gix::Tree, relative_path: &Path) -> Result<Option<String>> {
+    let Some(entry) = head_tree
         .lookup_entry_by_path(relative_path)
         .context("Failed to lookup file in HEAD tree")?
-        .context("File does not exist in HEAD")?;
+    else {
+        return Ok(None);
+    };
 
     let blob = entry
         .object()
@@ -303,7 +616,7 @@ fn get_head_content(head_tree: &gix::Tree, relative_path: &Path) -> Result<Strin
         .try_into_blob()
         .context("Tree entry is not a blob")?;
 
-    Ok(blob.data.to_str_lossy().into_owned())
+    Ok(Some(blob.data.to_str_lossy().into_owned()))
 }
 
 /// Verify that the file has version-related changes.
@@ -369,10 +682,11 @@ fn write_blob(repo: &gix::Repository, content: &str) -> Result<gix::ObjectId> {
     Ok(blob_id)
 }
 
-/// Update a tree by replacing a single file's blob.
+/// Update a tree by replacing the blobs of the given files.
 ///
 /// **CRITICAL**: This function takes HEAD's tree and creates a NEW tree with
-/// only ONE file changed. All other files remain exactly as they were in HEAD.
+/// only the given files changed. All other files remain exactly as they were
+/// in HEAD.
 ///
 /// # Why This Is Critical
 ///
@@ -396,32 +710,58 @@ fn write_blob(repo: &gix::Repository, content: &str) -> Result<gix::ObjectId> {
 ///
 /// # Implementation Strategy
 ///
-/// Since we're using a simplified tree builder (single-level), we need to:
-/// 1. Recreate HEAD's tree structure
-/// 2. Replace only the target file's blob
-/// 3. Keep all other entries unchanged
+/// Files may live in subdirectories (e.g. `crates/foo/Cargo.toml` in a
+/// workspace), so the update is recursive:
+/// 1. Recreate this tree's entries from HEAD
+/// 2. Replace the blob of any file that lives directly in this tree
+/// 3. For subtrees containing files to update, recurse with the remaining path
+///    components and use the rewritten subtree's ID
+/// 4. Keep all other entries unchanged
+/// 5. Add files (and their directories) that are not in the tree yet
 ///
-/// For a full implementation with recursive trees, we'd:
-/// 1. Parse the path to identify which subtree to modify
-/// 2. Clone all trees from HEAD to the target file's parent
-/// 3. Replace the blob in the deepest subtree
-/// 4. Rebuild parent trees up to root
+/// Only trees on the path to an updated file are rewritten; every other
+/// subtree keeps its object ID from HEAD.
 ///
 /// # Arguments
 ///
 /// * `repo` - The git repository
-/// * `head_tree` - The tree from HEAD commit
-/// * `file_path` - Path to the file to update (relative to repo root)
-/// * `new_blob_id` - The new blob ID for the file
+/// * `base_tree` - The tree to update (HEAD's root tree at the top level)
+/// * `files` - Paths (relative to `tree`) and their new blob IDs
 ///
 /// # Returns
 ///
-/// Returns the object ID of the new tree with the file updated.
-fn update_tree_with_file(
+/// Returns the object ID of the new tree with the files updated.
+fn update_tree_with_files(
     repo: &gix::Repository,
-    head_tree: &gix::Tree,
-    file_path: &Path,
-    new_blob_id: gix::ObjectId,
+    base_tree: &gix::Tree,
+    files: &[(PathBuf, gix::ObjectId)],
+) -> Result<gix::ObjectId> {
+    // Match paths against tree entries as raw bytes, so file names that are
+    // not valid UTF-8 are found (and kept) exactly
+    let git_files = files
+        .iter()
+        .map(|(path, blob_id)| Ok((index::git_path(path)?, *blob_id)))
+        .collect::<Result<Vec<_>>>()?;
+    let git_files: Vec<(&BStr, gix::ObjectId)> = git_files
+        .iter()
+        .map(|(path, blob_id)| (path.as_ref(), *blob_id))
+        .collect();
+    rewrite_tree(repo, base_tree, &git_files)
+}
+
+/// Split a git path into its first component and the rest, if any.
+fn split_git_path(path: &BStr) -> (&BStr, Option<&BStr>) {
+    match path.find_byte(b'/') {
+        Some(pos) => (&path[..pos], Some(&path[pos + 1..])),
+        None => (path, None),
+    }
+}
+
+/// Recursive part of [`update_tree_with_files`], with `/`-separated git paths.
+fn rewrite_tree(
+    repo: &gix::Repository,
+    base_tree: &gix::Tree,
+    files: &[(&BStr, gix::ObjectId)],
 ) -> Result<gix::ObjectId> {
     use gix::objs::{
         Tree,
@@ -432,27 +772,74 @@ fn update_tree_with_file(
     let mut tree_entries: Vec<tree::Entry> = Vec::new();
 
     // Iterate through HEAD's tree entries
-    for entry in head_tree.iter() {
+    for entry in base_tree.iter() {
         let entry = entry.context("Failed to iterate tree entry")?;
         let entry_path = entry.filename();
 
-        // Check if this is the file we're updating
-        if file_path.as_os_str().as_encoded_bytes() == entry_path {
-            // This is the file we're updating - use the new blob
-            tree_entries.push(tree::Entry {
-                mode: entry.mode(),
-                filename: entry_path.into(),
-                oid: new_blob_id,
-            });
-        } else {
-            // Keep the entry unchanged from HEAD
-            tree_entries.push(tree::Entry {
-                mode: entry.mode(),
-                filename: entry_path.into(),
-                oid: entry.oid().to_owned(),
-            });
+        // Keep the entry unchanged from HEAD unless a file below matches it
+        let mut oid = entry.oid().to_owned();
+        let mut nested_files = Vec::new();
+        for (file_path, new_blob_id) in files {
+            let (first, rest) = split_git_path(file_path);
+            if first != entry_path {
+                continue;
+            }
+
+            match rest {
+                // The file lives in this subtree
+                Some(rest) => nested_files.push((rest, *new_blob_id)),
+                // This is a file we're updating - use the new blob
+                None => oid = *new_blob_id,
+            }
+        }
+
+        if !nested_files.is_empty() {
+            let subtree = repo
+                .find_object(oid)
+                .context("Failed to find subtree")?
+                .try_into_tree()
+                .context("Tree entry is not a tree")?;
+            oid = rewrite_tree(repo, &subtree, &nested_files)?;
+        }
+
+        tree_entries.push(tree::Entry {
+            mode: entry.mode(),
+            filename: entry_path.into(),
+            oid,
+        });
+    }
+
+    // Add files (and directories) that are not in the base tree yet, as in
+    // the first commit of a repository
+    let mut new_dirs: std::collections::BTreeMap<&BStr, Vec<(&BStr, gix::ObjectId)>> =
+        std::collections::BTreeMap::new();
+    for (file_path, new_blob_id) in files {
+        let (first, rest) = split_git_path(file_path);
+        if tree_entries
+            .iter()
+            .any(|entry| entry.filename.as_bstr() == first)
+        {
+            continue;
+        }
+        match rest {
+            Some(rest) => new_dirs
+                .entry(first)
+                .or_default()
+                .push((rest, *new_blob_id)),
+            None => tree_entries.push(tree::Entry {
+                mode: tree::EntryKind::Blob.into(),
+                filename: first.into(),
+                oid: *new_blob_id,
+            }),
         }
     }
+    for (dir, nested_files) in new_dirs {
+        tree_entries.push(tree::Entry {
+            mode: tree::EntryKind::Tree.into(),
+            filename: dir.into(),
+            oid: rewrite_tree(repo, &repo.empty_tree(), &nested_files)?,
+        });
+    }
 
     // Sort entries using git's special sorting rules
     // Git treats directories as if they have a trailing '/' for sorting purposes
@@ -512,9 +899,10 @@ fn update_tree_with_file(
 ///
 /// * `repo` - The git repository
 /// * `tree_id` - The tree object ID (root tree of the commit)
-/// * `parent_id` - The parent commit ID (current HEAD)
-/// * `old_version` - Previous version (for commit message)
-/// * `new_version` - New version (for commit message)
+/// * `parents` - The parent commit IDs (the current HEAD, or HEAD's parents
+///   when amending)
+/// * `message` - The commit message
+/// * `author` / `committer` - Signatures of the commit
 ///
 /// # Returns
 ///
@@ -522,20 +910,13 @@ fn update_tree_with_file(
 fn create_commit(
     repo: &gix::Repository,
     tree_id: &gix::ObjectId,
-    parent_id: gix::Id,
-    old_version: &str,
-    new_version: &str,
+    parents: &[gix::ObjectId],
+    message: &str,
+    author: gix::actor::Signature,
+    committer: gix::actor::Signature,
 ) -> Result<gix::ObjectId> {
-    // Create commit message following conventional commits format
-    let commit_message = format!("chore(version): bump {} -> {}", old_version, new_version);
-
-    // Get author and committer from git config
-    let author = get_signature_from_config(repo)?;
-    let committer = author.clone();
-
-    // Create parent list - commits can have multiple parents (for merges)
-    // We only have one parent (the current HEAD)
-    let parents: SmallVec<[gix::ObjectId; 1]> = SmallVec::from_iter([parent_id.detach()]);
+    // Commits can have multiple parents (for merges); ours usually have one
+    let parents: SmallVec<[gix::ObjectId; 1]> = SmallVec::from_slice(parents);
 
     // Write the commit object to the object database
     let commit_id = repo
@@ -544,7 +925,7 @@ fn create_commit(
             parents,
             author,
             committer,
-            message: commit_message.into(),
+            message: message.into(),
             encoding: None,
             extra_headers: vec![],
         })
@@ -566,36 +947,210 @@ fn create_commit(
 /// - **Detached**: Points directly to a commit SHA
 ///
 /// In normal operation, HEAD is symbolic and points to the current branch.
-/// Updating HEAD in this case means updating the branch reference.
+/// Updating HEAD in this case means updating the branch reference, or creating
+/// it if HEAD is unborn (the repository has no commits yet). A detached
+/// HEAD is moved directly; `bump` only gets here with `--force`.
 ///
 /// # Arguments
 ///
 /// * `repo` - The git repository
 /// * `commit_id` - The object ID of the commit to point HEAD to
+/// * `reflog_message` - The message recorded in the reflog
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - HEAD doesn't exist or is invalid
-/// - HEAD is not a reference (detached HEAD state)
 /// - Reference update fails
-fn update_head(repo: &gix::Repository, commit_id: gix::ObjectId) -> Result<()> {
+fn update_head(
+    repo: &gix::Repository,
+    commit_id: gix::ObjectId,
+    reflog_message: &str,
+) -> Result<()> {
     // Read current HEAD
-    let mut head_ref = repo
-        .head()
-        .context("Failed to read HEAD")?
-        .try_into_referent()
-        .context("HEAD is not a reference (detached HEAD state)")?;
+    let head = repo.head().context("Failed to read HEAD")?;
+
+    // Unborn HEAD (no commits yet): create the branch HEAD points at
+    if let gix::head::Kind::Unborn(branch) = &head.kind {
+        repo.reference(
+            branch.clone(),
+            commit_id,
+            gix::refs::transaction::PreviousValue::MustNotExist,
+            reflog_message,
+        )
+        .context("Failed to create branch for the first commit")?;
+        return Ok(());
+    }
+
+    let Some(mut head_ref) = head.try_into_referent() else {
+        // Detached HEAD (only reached with `bump --force`): move HEAD itself
+        repo.edit_reference(gix::refs::transaction::RefEdit {
+            change: gix::refs::transaction::Change::Update {
+                log: gix::refs::transaction::LogChange {
+                    mode: gix::refs::transaction::RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: reflog_message.into(),
+                },
+                expected: gix::refs::transaction::PreviousValue::Any,
+                new: gix::refs::Target::Object(commit_id),
+            },
+            name: "HEAD".try_into().context("Invalid reference name")?,
+            deref: false,
+        })
+        .context("Failed to update detached HEAD")?;
+        return Ok(());
+    };
 
     // Update the reference to point to the new commit
     // This is an atomic operation - either succeeds completely or fails
     head_ref
-        .set_target_id(commit_id, "bump version")
+        .set_target_id(commit_id, reflog_message)
         .context("Failed to update HEAD reference")?;
 
     Ok(())
 }
 
+/// Check whether HEAD is detached in the repository containing the manifest.
+///
+/// A detached HEAD points directly at a commit instead of a branch, as during
+/// a rebase or in CI checkouts of a tag or SHA.
+///
+/// # Errors
+///
+/// Returns an error if the manifest is not in a git repository or HEAD cannot
+/// be read.
+pub fn is_head_detached(manifest_path: &Path) -> Result<bool> {
+    let repo = discover_repo(manifest_path)?;
+    let head = repo.head().context("Failed to read HEAD")?;
+    Ok(head.is_detached())
+}
+
+/// Find a remote-tracking branch that already contains HEAD's commit.
+///
+/// Returns the branch's reference name (e.g. `refs/remotes/origin/main`) if
+/// there is one, in which case amending HEAD would rewrite published history.
+///
+/// # Errors
+///
+/// Returns an error if the manifest is not in a git repository or HEAD or the
+/// references cannot be read.
+pub fn head_pushed_to(manifest_path: &Path) -> Result<Option<String>> {
+    let repo = discover_repo(manifest_path)?;
+    let head_id = repo.head_id().context("HEAD does not point to a commit")?;
+
+    let refs = repo.references().context("Failed to read git references")?;
+    for reference in refs.prefixed("refs/remotes/")? {
+        let Ok(mut reference) = reference else {
+            continue;
+        };
+        let Ok(tip) = reference.peel_to_id() else {
+            continue;
+        };
+        // The branch contains HEAD if HEAD is an ancestor of (or is) its tip
+        if tip == head_id
+            || repo
+                .merge_base(head_id, tip)
+                .is_ok_and(|base| base == head_id)
+        {
+            return Ok(Some(reference.name().as_bstr().to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Notes ref read by `git log` and `git notes show` by default.
+const NOTES_REF: &str = "refs/notes/commits";
+
+/// Attach `note` to `commit_id` as a git note in `refs/notes/commits`.
+///
+/// Equivalent to `git notes add -f -m <note> <commit>`: the note is stored
+/// as a blob named after the commit's SHA in the notes tree, and a new notes
+/// commit (parented on the previous one, if any) is recorded. An existing
+/// note on the commit is replaced.
+///
+/// Notes are not pushed with branches; use an explicit refspec such as
+/// `git push origin refs/notes/commits`.
+///
+/// # Errors
+///
+/// Returns an error if the repository, the existing notes or the git
+/// identity cannot be read, or the notes ref cannot be updated.
+pub fn add_note(manifest_path: &Path, commit_id: gix::ObjectId, note: &str) -> Result<()> {
+    let repo = discover_repo(manifest_path)?;
+
+    let previous = match repo
+        .try_find_reference(NOTES_REF)
+        .context("Failed to read notes reference")?
+    {
+        Some(mut reference) => Some(
+            reference
+                .peel_to_id()
+                .context("Failed to resolve notes reference")?
+                .detach(),
+        ),
+        None => None,
+    };
+    let base_tree = match previous {
+        Some(notes_commit) => repo
+            .find_object(notes_commit)
+            .context("Failed to find notes commit")?
+            .try_into_commit()
+            .context("Notes reference does not point to a commit")?
+            .tree()
+            .context("Failed to get notes tree")?,
+        None => repo.empty_tree(),
+    };
+
+    // Like `git notes`, store the note with a trailing newline
+    let blob_id = write_blob(&repo, &format!("{}\n", note.trim_end()))?;
+    let name = commit_id.to_hex().to_string();
+    let tree_id = rewrite_tree(&repo, &base_tree, &[(name.as_bytes().as_bstr(), blob_id)])?;
+
+    let signature = get_signature_from_config(&repo)?;
+    let message = "Notes added by 'cargo version-info bump'\n";
+    let parents: Vec<gix::ObjectId> = previous.into_iter().collect();
+    let notes_commit = create_commit(
+        &repo,
+        &tree_id,
+        &parents,
+        message,
+        signature.clone(),
+        signature,
+    )?;
+
+    let expected = match previous {
+        Some(id) => {
+            gix::refs::transaction::PreviousValue::MustExistAndMatch(gix::refs::Target::Object(id))
+        }
+        None => gix::refs::transaction::PreviousValue::MustNotExist,
+    };
+    repo.reference(
+        NOTES_REF,
+        notes_commit,
+        expected,
+        format!("notes: {}", message.trim_end()),
+    )
+    .context("Failed to update notes reference")?;
+
+    Ok(())
+}
+
+/// Get the signature for `identity`, or from git config if it is `None`.
+fn get_signature(
+    repo: &gix::Repository,
+    identity: Option<&Identity>,
+) -> Result<gix::actor::Signature> {
+    match identity {
+        Some(identity) => Ok(gix::actor::Signature {
+            name: identity.name.as_str().into(),
+            email: identity.email.as_str().into(),
+            time: current_time()?,
+        }),
+        None => get_signature_from_config(repo),
+    }
+}
+
 /// Get git signature (author/committer) from repository config.
 ///
 /// Reads the `user.name` and `user.email` from git config and creates a
@@ -667,19 +1222,21 @@ fn get_signature_from_config(repo: &gix::Repository) -> Result<gix::actor::Signa
             )
         })?;
 
-    // Get current time for the commit
+    Ok(gix::actor::Signature {
+        name: name.into(),
+        email: email.into(),
+        time: current_time()?,
+    })
+}
+
+/// Get the current time for a commit signature.
+fn current_time() -> Result<gix::date::Time> {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .context("Failed to get current time")?;
 
-    let time = gix::date::Time {
+    Ok(gix::date::Time {
         seconds: now.as_secs() as i64,
         offset: 0, // UTC
-    };
-
-    Ok(gix::actor::Signature {
-        name: name.into(),
-        email: email.into(),
-        time,
     })
 }
@@ -0,0 +1,346 @@
+//! Dependency requirement updates for workspace siblings.
+//!
+//! When a crate is bumped inside a workspace, sibling crates that depend on it
+//! via `path` usually also pin a `version` requirement (needed for
+//! publishing):
+//!
+//! ```toml
+//! [dependencies]
+//! foo = { path = "../foo", version = "0.1" }
+//! ```
+//!
+//! With `--update-dependents`, these requirements are rewritten to match the
+//! new version, keeping the original operator and precision:
+//!
+//! | Old requirement | New version | New requirement |
+//! |-----------------|-------------|-----------------|
+//! | `0.1`           | `0.2.0`     | `0.2`           |
+//! | `0.1.0`         | `0.2.0`     | `0.2.0`         |
+//! | `=0.1.0`        | `0.2.0`     | `=0.2.0`        |
+//! | `^1`            | `2.0.0`     | `^2`            |
+//! | `~1.2`          | `1.3.0`     | `~1.3`          |
+//! | `0.1.*`         | `0.2.0`     | `0.2.*`         |
+//!
+//! Only dependencies with both `path` and `version` are touched; registry and
+//! git dependencies are left alone, as are requirements that are not a single
+//! comparator (`*`, `>=0.1, <0.3`), which cannot be moved without guessing the
+//! intended range.
+
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use anyhow::{
+    Context,
+    Result,
+};
+use cargo_metadata::semver::{
+    Op,
+    Version,
+    VersionReq,
+};
+use toml_edit::{
+    DocumentMut,
+    TableLike,
+};
+
+use crate::commands::common::CargoOptions;
+
+/// Dependency tables that may reference the bumped crate.
+const DEPENDENCY_TABLES: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Update version requirements on `crate_name` across the workspace.
+///
+/// Scans every workspace member manifest (and `[workspace.dependencies]` in
+/// the workspace root) for path dependencies on `crate_name` and rewrites
+/// their `version` requirement to match `new_version`.
+///
+/// # Returns
+///
+/// Returns the manifests that were modified, excluding `manifest_path` itself
+/// (which is already part of the bump).
+///
+/// # Errors
+///
+/// Returns an error if cargo metadata cannot be read or a manifest cannot be
+/// parsed or written.
+pub fn update_dependents(
+    manifest_path: &Path,
+    crate_name: &str,
+    new_version: &str,
+    cargo: CargoOptions,
+) -> Result<Vec<PathBuf>> {
+    let metadata = cargo
+        .metadata_command()
+        .manifest_path(manifest_path)
+        .no_deps()
+        .exec()
+        .context("Failed to get cargo metadata")?;
+
+    let mut manifests: Vec<PathBuf> = metadata
+        .workspace_packages()
+        .iter()
+        .map(|pkg| pkg.manifest_path.clone().into_std_path_buf())
+        .collect();
+    let root_manifest = metadata
+        .workspace_root
+        .join("Cargo.toml")
+        .into_std_path_buf();
+    if !manifests.contains(&root_manifest) {
+        manifests.push(root_manifest);
+    }
+
+    let bumped_manifest = manifest_path.canonicalize().ok();
+    let mut changed = Vec::new();
+    for manifest in manifests {
+        if update_dependency_requirements(&manifest, crate_name, new_version)?
+            && manifest.canonicalize().ok() != bumped_manifest
+        {
+            changed.push(manifest);
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Update version requirements on `crate_name` in a single manifest.
+///
+/// Returns `true` if the manifest was modified.
+pub fn update_dependency_requirements(
+    manifest_path: &Path,
+    crate_name: &str,
+    new_version: &str,
+) -> Result<bool> {
+    let content = std::fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse TOML in {}", manifest_path.display()))?;
+
+    let mut changed = false;
+
+    // [dependencies], [dev-dependencies], [build-dependencies]
+    for table in DEPENDENCY_TABLES {
+        if let Some(deps) = doc.get_mut(table).and_then(|d| d.as_table_like_mut()) {
+            changed |= update_requirements_in_table(deps, crate_name, new_version);
+        }
+    }
+
+    // [target.'cfg(...)'.dependencies] and friends
+    if let Some(targets) = doc.get_mut("target").and_then(|t| t.as_table_like_mut()) {
+        for (_, target) in targets.iter_mut() {
+            let Some(target) = target.as_table_like_mut() else {
+                continue;
+            };
+            for table in DEPENDENCY_TABLES {
+                if let Some(deps) = target.get_mut(table).and_then(|d| d.as_table_like_mut()) {
+                    changed |= update_requirements_in_table(deps, crate_name, new_version);
+                }
+            }
+        }
+    }
+
+    // [workspace.dependencies]
+    if let Some(deps) = doc
+        .get_mut("workspace")
+        .and_then(|w| w.as_table_like_mut())
+        .and_then(|w| w.get_mut("dependencies"))
+        .and_then(|d| d.as_table_like_mut())
+    {
+        changed |= update_requirements_in_table(deps, crate_name, new_version);
+    }
+
+    if changed {
+        std::fs::write(manifest_path, doc.to_string())
+            .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+    }
+
+    Ok(changed)
+}
+
+/// Update matching path dependencies in one dependency table.
+fn update_requirements_in_table(
+    deps: &mut dyn TableLike,
+    crate_name: &str,
+    new_version: &str,
+) -> bool {
+    let mut changed = false;
+
+    for (key, dep) in deps.iter_mut() {
+        let Some(dep) = dep.as_table_like_mut() else {
+            // `foo = "0.1"` - a plain registry dependency
+            continue;
+        };
+
+        // Renamed dependencies use `package = "real-name"`
+        let name = dep
+            .get("package")
+            .and_then(|p| p.as_str())
+            .unwrap_or(key.get())
+            .to_string();
+        if name != crate_name || !dep.contains_key("path") {
+            continue;
+        }
+
+        let Some(version) = dep.get_mut("version").and_then(|v| v.as_value_mut()) else {
+            continue;
+        };
+        let Some(old_req) = version.as_str() else {
+            continue;
+        };
+
+        let Some(new_req) = bump_requirement(old_req, new_version) else {
+            continue;
+        };
+        if new_req != old_req {
+            let decor = version.decor().clone();
+            *version = new_req.into();
+            *version.decor_mut() = decor;
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Rewrite a version requirement for a new version.
+///
+/// Keeps the requirement's operator as written (`^`, `~`, `=`, a bare
+/// version, `>= `, ...), the number of version components it specifies, and
+/// its wildcard (`0.1.*`, `1.x`). A pre-release of the new version is only
+/// kept when the requirement pins all three components, as `semver` ignores
+/// it otherwise.
+///
+/// Returns `None` for requirements that cannot be parsed or do not consist of
+/// exactly one comparator (`*`, `>=0.1, <0.3`), and when `new_version` is
+/// not a valid version.
+pub fn bump_requirement(requirement: &str, new_version: &str) -> Option<String> {
+    let parsed = VersionReq::parse(requirement).ok()?;
+    let [comparator] = parsed.comparators.as_slice() else {
+        return None;
+    };
+    let new_version = Version::parse(new_version).ok()?;
+
+    let requirement = requirement.trim();
+    let version_start = requirement.find(|c: char| c.is_ascii_digit())?;
+    let operator = &requirement[..version_start];
+
+    let mut components = vec![new_version.major.to_string()];
+    if comparator.minor.is_some() {
+        components.push(new_version.minor.to_string());
+    }
+    if comparator.patch.is_some() {
+        components.push(new_version.patch.to_string());
+    }
+    let mut new_requirement = format!("{}{}", operator, components.join("."));
+    if comparator.patch.is_some() && !new_version.pre.is_empty() {
+        new_requirement = format!("{}-{}", new_requirement, new_version.pre);
+    }
+    if comparator.op == Op::Wildcard {
+        // Keep the spelling of the wildcard (`*`, `x` or `X`)
+        new_requirement = format!(
+            "{}.{}",
+            new_requirement,
+            &requirement[requirement.len() - 1..]
+        );
+    }
+
+    Some(new_requirement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_requirement() {
+        assert_eq!(bump_requirement("0.1", "0.2.0").unwrap(), "0.2");
+        assert_eq!(bump_requirement("0.1.0", "0.2.0").unwrap(), "0.2.0");
+        assert_eq!(bump_requirement(">= 0.1", "0.2.0").unwrap(), ">= 0.2");
+    }
+
+    #[test]
+    fn test_bump_requirement_caret() {
+        assert_eq!(bump_requirement("^1", "2.0.0").unwrap(), "^2");
+        assert_eq!(bump_requirement("^0.1", "0.2.0").unwrap(), "^0.2");
+        assert_eq!(bump_requirement("^0.1.3", "0.2.0").unwrap(), "^0.2.0");
+    }
+
+    #[test]
+    fn test_bump_requirement_tilde() {
+        assert_eq!(bump_requirement("~1.2", "1.3.0-rc.1").unwrap(), "~1.3");
+        assert_eq!(bump_requirement("~1.2.0", "1.3.0").unwrap(), "~1.3.0");
+    }
+
+    #[test]
+    fn test_bump_requirement_exact() {
+        assert_eq!(bump_requirement("=0.1.0", "0.2.0").unwrap(), "=0.2.0");
+        assert_eq!(
+            bump_requirement("=0.1.0", "0.2.0-rc.1").unwrap(),
+            "=0.2.0-rc.1"
+        );
+        assert_eq!(bump_requirement("=0.1", "0.2.0").unwrap(), "=0.2");
+    }
+
+    #[test]
+    fn test_bump_requirement_wildcard() {
+        assert_eq!(bump_requirement("0.1.*", "0.2.0").unwrap(), "0.2.*");
+        assert_eq!(bump_requirement("1.x", "2.0.0").unwrap(), "2.x");
+        assert_eq!(bump_requirement("1.*", "1.4.0").unwrap(), "1.*");
+        assert_eq!(bump_requirement("*", "0.2.0"), None);
+    }
+
+    #[test]
+    fn test_bump_requirement_unsupported() {
+        assert_eq!(bump_requirement(">=0.1, <0.3", "0.2.0"), None);
+        assert_eq!(bump_requirement("not a requirement", "0.2.0"), None);
+        assert_eq!(bump_requirement("0.1", "not a version"), None);
+    }
+
+    #[test]
+    fn test_update_dependency_requirements() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            r#"[package]
+name = "bar"
+version = "0.1.0"
+
+[dependencies]
+foo = { path = "../foo", version = "0.1" } # sibling
+serde = "1"
+
+[dev-dependencies.foo-renamed]
+package = "foo"
+path = "../foo"
+version = "0.1.0"
+
+[build-dependencies]
+foo = "0.1"
+"#,
+        )
+        .unwrap();
+
+        assert!(update_dependency_requirements(&manifest, "foo", "0.2.0").unwrap());
+
+        let content = std::fs::read_to_string(&manifest).unwrap();
+        assert!(content.contains(r#"foo = { path = "../foo", version = "0.2" } # sibling"#));
+        assert!(content.contains("version = \"0.2.0\""));
+        // Registry dependency without a path is untouched
+        assert!(content.contains("foo = \"0.1\""));
+        assert!(content.contains("serde = \"1\""));
+    }
+
+    #[test]
+    fn test_update_dependency_requirements_no_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        let original = "[package]\nname = \"bar\"\n\n[dependencies]\nserde = \"1\"\n";
+        std::fs::write(&manifest, original).unwrap();
+
+        assert!(!update_dependency_requirements(&manifest, "foo", "0.2.0").unwrap());
+        assert_eq!(std::fs::read_to_string(&manifest).unwrap(), original);
+    }
+}
@@ -216,6 +216,36 @@ pub fn has_non_version_changes(
     false
 }
 
+/// List the changed lines that are not version-related.
+///
+/// Each entry is prefixed with `-` (removed from HEAD) or `+` (added in the
+/// working directory), like a unified diff. Used to report why a `--strict`
+/// bump was refused.
+pub fn non_version_changes(
+    head_content: &str,
+    working_content: &str,
+    old_version: &str,
+    new_version: &str,
+) -> Vec<String> {
+    let diff = TextDiff::from_lines(head_content, working_content);
+
+    diff.iter_all_changes()
+        .filter_map(|change| {
+            let sign = match change.tag() {
+                ChangeTag::Delete => '-',
+                ChangeTag::Insert => '+',
+                ChangeTag::Equal => return None,
+            };
+            let line = change.value();
+            let is_version_related = line.contains("version")
+                || line.contains(old_version)
+                || line.contains(new_version);
+
+            (!is_version_related).then(|| format!("{}{}", sign, line.trim_end()))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,6 +284,17 @@ mod tests {
         assert!(has_non_version_changes(head, working, "0.1.0", "0.2.0"));
     }
 
+    #[test]
+    fn test_non_version_changes_lists_lines() {
+        let head = "[package]\nversion = \"0.1.0\"\ndesc = \"old\"\n";
+        let working = "[package]\nversion = \"0.2.0\"\ndesc = \"new\"\n";
+
+        assert_eq!(
+            non_version_changes(head, working, "0.1.0", "0.2.0"),
+            vec!["-desc = \"old\"", "+desc = \"new\""]
+        );
+    }
+
     #[test]
     fn test_has_non_version_changes_false() {
         let head = "[package]\nname = \"test\"\nversion = \"0.1.0\"\n";
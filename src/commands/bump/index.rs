@@ -64,8 +64,10 @@
 //! The gix index API is low-level and requires careful handling:
 //! - Path storage must be managed manually
 //! - Entries must be kept sorted
-//! - The State struct doesn't allow direct mutation of path_backing
-//! - We must rebuild the entire state to add entries with new paths
+//! - Extensions (tree cache, resolve-undo, ...) live on the `State`, so it is
+//!   modified in place rather than rebuilt to keep them
+//! - The tree cache records tree IDs per directory and must not be left stale
+//!   when an entry's content changes
 
 use std::path::Path;
 
@@ -73,7 +75,10 @@ use anyhow::{
     Context,
     Result,
 };
-use bstr::BStr;
+use bstr::{
+    BStr,
+    BString,
+};
 use gix::index::{
     File,
     State,
@@ -84,12 +89,13 @@ use gix::index::{
 ///
 /// This function adds or updates a file entry in the git index, making it
 /// ready to be committed. It handles all the low-level details of:
-/// - Loading the current index state
 /// - Adding the file's path to the path backing storage
 /// - Creating a properly formatted index entry
-/// - Maintaining index invariants (sorted entries)
+/// - Maintaining index invariants (sorted entries, a valid tree cache)
 /// - Writing the updated index back to disk
 ///
+/// The index extensions of `existing_state` are kept.
+///
 /// # Arguments
 ///
 /// * `index_path` - Path to the `.git/index` file
@@ -106,7 +112,8 @@ use gix::index::{
 ///
 /// Returns an error if:
 /// - The index file cannot be read or written
-/// - The path contains invalid UTF-8
+/// - The staged blob cannot be found or the file cannot be stat'ed
+/// - The path cannot be represented in git (see [`git_path`])
 /// - Entries cannot be properly sorted
 ///
 /// # Examples
@@ -133,9 +140,18 @@ use gix::index::{
 ///
 /// Since the index stores paths in a shared backing array, we need to:
 /// 1. Check if the path already exists (for updates)
-/// 2. Create a new State to add the path properly
-/// 3. Copy all existing entries (preserving their path references)
-/// 4. Add the new entry with its path
+/// 2. Remove the old entry; its path bytes stay in the backing array but are no
+///    longer referenced, and are not written out
+/// 3. Add the new entry with its path to the same state
+///
+/// ## Extensions
+///
+/// Modifying the existing state keeps its extensions, most importantly the
+/// tree cache that lets git skip re-hashing unchanged directories. Git
+/// invalidates only the changed path's directories in that cache, but gix has
+/// no API to edit it, so it is dropped when the entry's content changes (a
+/// stale cache would make git commit outdated trees). Re-staging identical
+/// content keeps it.
 ///
 /// ## Entry Creation
 ///
@@ -144,6 +160,12 @@ use gix::index::{
 /// - The "dangerous" name indicates we must call `sort_entries()` afterward
 /// - It handles path storage automatically
 ///
+/// ## Stat Info
+///
+/// The entry records the file's size, timestamps and inode when the blob is
+/// the file's working tree content, so `git status` is clean afterwards
+/// without a refresh.
+///
 /// ## Sorting
 ///
 /// Git requires index entries to be sorted by path. This is critical for:
@@ -157,42 +179,35 @@ pub fn stage_file(
     blob_id: gix::ObjectId,
     mut existing_state: State,
 ) -> Result<State> {
+    let stat = entry_stat(repo, relative_path, blob_id)?;
+
     // Find and remove existing entry for this path (if any)
     // This handles both new files and updates to existing files
-    let path_bytes = relative_path.as_os_str().as_encoded_bytes();
-    if let Some(pos) = existing_state
+    let path_bytes = git_path(relative_path)?;
+    let existing = existing_state
         .entries()
         .iter()
-        .position(|e| e.path(&existing_state) == path_bytes)
-    {
+        .position(|e| e.path(&existing_state) == path_bytes);
+    let content_changed = match existing {
+        Some(pos) => existing_state.entries()[pos].id != blob_id,
+        None => true,
+    };
+    if let Some(pos) = existing {
         // File already exists in index - remove old entry
         existing_state.remove_entry_at_index(pos);
     }
 
-    // Create a new state with all entries including the updated one
-    // We need to create a new state because we can't directly mutate path_backing
-    let mut new_state = State::new(repo.object_hash());
-
-    // Copy all existing entries to the new state
-    // The dangerously_push_entry method handles path storage automatically
-    for existing_entry in existing_state.entries() {
-        let entry_path = existing_entry.path(&existing_state);
-        new_state.dangerously_push_entry(
-            existing_entry.stat,
-            existing_entry.id,
-            existing_entry.flags,
-            existing_entry.mode,
-            entry_path,
-        );
+    // The cached tree IDs of the file's directories no longer match
+    if content_changed {
+        existing_state.remove_tree();
     }
 
-    // Add the new/updated entry
-    // We use default stat since we've already verified the file has version changes
-    // The stat is primarily used by git for optimization (detecting if file
-    // changed)
-    let path_bstr: &BStr = path_bytes.into();
+    // Add the new/updated entry to the same state, keeping its extensions
+    // The dangerously_push_entry method handles path storage automatically
+    let mut new_state = existing_state;
+    let path_bstr: &BStr = path_bytes.as_ref();
     new_state.dangerously_push_entry(
-        entry::Stat::default(),
+        stat,
         blob_id,
         entry::Flags::empty(),
         entry::Mode::FILE,
@@ -214,6 +229,56 @@ pub fn stage_file(
     Ok(new_state)
 }
 
+/// Convert a repository-relative path to how git stores it in the index and
+/// in trees: raw bytes with `/` separators.
+///
+/// No lossy conversion happens, so on Unix a file name that is not valid
+/// UTF-8 keeps its exact bytes.
+///
+/// # Errors
+///
+/// Returns an error if the path is not valid Unicode on Windows, where git
+/// paths must be UTF-8.
+pub fn git_path(relative_path: &Path) -> Result<BString> {
+    let path = gix::path::try_into_bstr(relative_path)
+        .with_context(|| format!("Path cannot be stored in git: {}", relative_path.display()))?;
+    Ok(gix::path::to_unix_separators_on_windows(path).into_owned())
+}
+
+/// Stat info for the index entry of `relative_path` staged as `blob_id`.
+///
+/// Git compares this against the file on disk to tell whether it changed
+/// since it was staged, and only re-reads the file when they differ. A zeroed
+/// stat therefore makes the file look modified until the index is refreshed.
+///
+/// The file's real stat is only recorded when the staged blob is exactly the
+/// working tree content. When only some hunks were staged, the zeroed stat is
+/// kept so git notices the unstaged remainder.
+fn entry_stat(
+    repo: &gix::Repository,
+    relative_path: &Path,
+    blob_id: gix::ObjectId,
+) -> Result<entry::Stat> {
+    let Some(workdir) = repo.workdir() else {
+        return Ok(entry::Stat::default());
+    };
+    let file_path = workdir.join(relative_path);
+    let Ok(content) = std::fs::read(&file_path) else {
+        return Ok(entry::Stat::default());
+    };
+    let blob = repo
+        .find_object(blob_id)
+        .context("Failed to find staged blob")?;
+    if blob.data != content {
+        return Ok(entry::Stat::default());
+    }
+
+    let metadata = gix::index::fs::Metadata::from_path_no_follow(&file_path)
+        .with_context(|| format!("Failed to stat {}", file_path.display()))?;
+    entry::Stat::from_fs(&metadata)
+        .with_context(|| format!("Invalid file times for {}", file_path.display()))
+}
+
 /// Load the current index state from disk.
 ///
 /// This is a convenience wrapper around `gix::index::File::at()` that provides
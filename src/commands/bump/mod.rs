@@ -20,6 +20,7 @@
 //! - [`index`] - Git index (staging area) operations
 //! - [`tree`] - Git tree building from index
 //! - [`commit`] - Commit orchestration and creation
+//! - [`dependents`] - Dependency requirement updates in workspace siblings
 //!
 //! # Usage Examples
 //!
@@ -39,8 +40,35 @@
 //! # Auto-suggest from GitHub releases
 //! cargo version-info bump --auto --github-token $TOKEN
 //!
+//! # Continue a pre-release train (v1.0.0-rc.1 -> 1.0.0-rc.2)
+//! cargo version-info bump --auto --include-prereleases
+//!
 //! # Update but don't commit
 //! cargo version-info bump --patch --no-commit
+//!
+//! # Abort if Cargo.toml has non-version changes
+//! cargo version-info bump --patch --strict
+//!
+//! # Deliberately go back to an older version
+//! cargo version-info bump --version 0.1.0 --allow-downgrade
+//!
+//! # Rewrite the same version in another form
+//! cargo version-info bump --version 1.0.0 --normalize
+//!
+//! # Commit even though HEAD is detached
+//! cargo version-info bump --patch --force
+//!
+//! # Fold the bump into the previous commit
+//! cargo version-info bump --patch --amend
+//!
+//! # Record provenance as a git note (push with `git push origin refs/notes/commits`)
+//! cargo version-info bump --patch --note "released by CI run 123"
+//!
+//! # Confirm the planned change before writing
+//! cargo version-info bump --minor --interactive
+//!
+//! # Refresh the lockfile after bumping
+//! cargo version-info bump --patch --post-bump 'cargo update --workspace'
 //! ```
 //!
 //! # Workflow
@@ -105,6 +133,9 @@
 //! - Regular crates with `[package] version`
 //! - Workspace members with `[workspace.package] version`
 //!
+//! Use `--location` to choose between them (or update both) when a workspace
+//! root manifest has both sections.
+//!
 //! ## Error Handling
 //!
 //! All operations use `anyhow::Result` for consistent error handling with
@@ -113,6 +144,7 @@
 
 pub mod args;
 pub mod commit;
+pub mod dependents;
 pub mod diff;
 pub mod index;
 pub mod tree;
@@ -122,18 +154,30 @@ pub mod version_update;
 mod tests;
 
 // Re-export public API
+use std::cmp::Ordering;
+use std::io::{
+    IsTerminal,
+    Write,
+};
+
 use anyhow::{
     Context,
     Result,
 };
 pub use args::BumpArgs;
-use cargo_plugin_utils::common::{
-    find_package,
-    get_owner_repo,
-};
+use portable_pty::CommandBuilder;
+use schemars::JsonSchema;
+use serde::Serialize;
 
+use super::common::{
+    CargoOptions,
+    find_manifest_package,
+    find_workspace_member,
+};
 use crate::github;
 use crate::version::{
+    canonical_version,
+    cmp_versions,
     format_version,
     increment_major,
     increment_minor,
@@ -183,7 +227,17 @@ use crate::version::{
 /// - Target version calculation fails
 /// - File updates fail
 /// - Git operations fail (when committing)
-/// - Current version equals target version (nothing to bump)
+/// - Current version equals target version (nothing to bump), or is the same
+///   version written differently (without `--normalize`)
+/// - Target version is lower than the current version (without
+///   `--allow-downgrade`)
+/// - HEAD is detached (without `--force` or `--no-commit`)
+/// - `--amend` is given and HEAD is already pushed (without `--force`)
+/// - The change is declined at the `--interactive` prompt
+/// - `--author` or `--committer` is not in `Name <email>` form
+/// - The format is invalid
+/// - The `--post-bump` hook fails (after the bump is complete; it is not rolled
+///   back)
 ///
 /// # Examples
 ///
@@ -239,58 +293,405 @@ use crate::version::{
 /// - You're making multiple related changes
 /// - You prefer manual commit control
 pub fn bump(args: BumpArgs) -> Result<()> {
+    // Validate the output format before touching any files
+    let json = match args.format.as_str() {
+        "text" => false,
+        "json" => true,
+        _ => anyhow::bail!("Invalid format: {}", args.format),
+    };
+
+    let outcome = bump_with_args(&args)?;
+
+    if json {
+        let output = BumpOutput {
+            old: &outcome.old,
+            new: &outcome.new,
+            committed: outcome.commit.is_some(),
+            commit: outcome.commit.clone(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string(&output).context("Failed to serialize bump result")?
+        );
+    } else if let Some(commit) = &outcome.commit {
+        let action = if args.amend {
+            "Amended HEAD with"
+        } else {
+            "Committed"
+        };
+        cargo_plugin_utils::logger::Logger::new().print_message(&format!(
+            "✓ {} version bump: {} -> {} ({})",
+            action, outcome.old, outcome.new, commit
+        ));
+    } else {
+        cargo_plugin_utils::logger::Logger::new().print_message(&format!(
+            "✓ Updated version to {} (not committed)",
+            outcome.new
+        ));
+    }
+
+    // The bump is complete at this point; a failing hook does not undo it
+    if let Some(command) = &args.post_bump {
+        run_post_bump_hook(command, &outcome)?;
+    }
+
+    Ok(())
+}
+
+/// Run the `--post-bump` command with `OLD_VERSION` and `NEW_VERSION` set.
+///
+/// # Errors
+///
+/// Returns an error if the command cannot be started or exits unsuccessfully.
+fn run_post_bump_hook(command: &str, outcome: &BumpOutcome) -> Result<()> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+    logger.status("Running", "post-bump hook");
+
+    let cwd = std::env::current_dir().context("Failed to read current directory")?;
+    let shell_command = command.to_string();
+    let (old_version, new_version) = (outcome.old.clone(), outcome.new.clone());
+    let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
+    let output = rt.block_on(cargo_plugin_utils::logger::run_subprocess(
+        &mut logger,
+        move || {
+            let mut cmd = if cfg!(windows) {
+                let mut cmd = CommandBuilder::new("cmd");
+                cmd.arg("/C");
+                cmd
+            } else {
+                let mut cmd = CommandBuilder::new("sh");
+                cmd.arg("-c");
+                cmd
+            };
+            cmd.arg(shell_command.as_str());
+            cmd.env("OLD_VERSION", old_version.as_str());
+            cmd.env("NEW_VERSION", new_version.as_str());
+            cmd.cwd(&cwd);
+            cmd
+        },
+        None,
+    ))?;
+    logger.finish();
+
+    if !output.success() {
+        anyhow::bail!(
+            "Post-bump hook failed: {} (the version bump was not rolled back)",
+            command
+        );
+    }
+
+    Ok(())
+}
+
+/// Which version [`bump_version`] bumps to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionTarget {
+    /// Exactly this version.
+    Explicit(String),
+    /// The next major version.
+    Major,
+    /// The next minor version.
+    Minor,
+    /// The next patch version.
+    Patch,
+    /// The version after the latest GitHub release. The repository is taken
+    /// from the git remote and the token from `GITHUB_TOKEN`.
+    Auto,
+}
+
+/// Result of a bump.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BumpOutcome {
+    /// Version before the bump.
+    pub old: String,
+    /// Version after the bump.
+    pub new: String,
+    /// Short SHA of the bump commit, if one was created.
+    pub commit: Option<String>,
+}
+
+/// Bump the version in `manifest_path`, without going through command-line
+/// arguments.
+///
+/// This is [`bump`] with default options: the version is updated where
+/// `--location auto` would, and with `commit`, committed on its own (see
+/// [`commit::commit_version_changes`]). Nothing is printed to stdout.
+///
+/// # Errors
+///
+/// Returns an error in the same cases as [`bump`], e.g. if the target is not
+/// a newer version or the commit cannot be created.
+///
+/// # Examples
+///
+/// ```no_run
+/// use cargo_version_info::commands::{
+///     VersionTarget,
+///     bump_version,
+/// };
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let outcome = bump_version("Cargo.toml".as_ref(), VersionTarget::Minor, true)?;
+/// println!("{} -> {} ({:?})", outcome.old, outcome.new, outcome.commit);
+/// # Ok(())
+/// # }
+/// ```
+pub fn bump_version(
+    manifest_path: &std::path::Path,
+    target: VersionTarget,
+    commit: bool,
+) -> Result<BumpOutcome> {
+    let auto = target == VersionTarget::Auto;
+    let args = BumpArgs {
+        manifest_path: Some(manifest_path.to_path_buf()),
+        package: None,
+        version: match &target {
+            VersionTarget::Explicit(version) => Some(version.clone()),
+            _ => None,
+        },
+        auto,
+        include_prereleases: false,
+        major: target == VersionTarget::Major,
+        minor: target == VersionTarget::Minor,
+        patch: target == VersionTarget::Patch,
+        owner: None,
+        repo: None,
+        github_token: if auto {
+            std::env::var("GITHUB_TOKEN").ok()
+        } else {
+            None
+        },
+        tag_prefix: None,
+        no_commit: !commit,
+        strict: false,
+        amend: false,
+        location: "auto".to_string(),
+        update_dependents: false,
+        allow_downgrade: false,
+        normalize: false,
+        force: false,
+        interactive: false,
+        yes: false,
+        author: None,
+        committer: None,
+        note: None,
+        post_bump: None,
+        format: "text".to_string(),
+        cargo: CargoOptions::default(),
+    };
+    bump_with_args(&args)
+}
+
+/// Run the bump described by `args`; shared by [`bump`] and [`bump_version`].
+fn bump_with_args(args: &BumpArgs) -> Result<BumpOutcome> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
 
     // Step 1: Get current version from Cargo.toml
     logger.status("Reading", "current version");
-    let package = find_package(args.manifest_path.as_deref())?;
+    let package = match &args.package {
+        Some(name) => find_workspace_member(name, args.manifest_path.as_deref(), args.cargo)?,
+        None => find_manifest_package(args.manifest_path.as_deref(), args.cargo)?,
+    };
     let current_version = package.version.to_string();
     logger.finish();
 
     // Step 2: Calculate target version based on command args
     logger.status("Calculating", "target version");
-    let target_version = calculate_target_version(&args, &current_version)?;
+    let target_version = calculate_target_version(args, &current_version)?;
     logger.finish();
 
-    // Step 3: Verify version is changing
-    if current_version == target_version {
+    // Validate the identity overrides before touching any files
+    let author = args
+        .author
+        .as_deref()
+        .map(str::parse::<commit::Identity>)
+        .transpose()
+        .context("Invalid --author")?;
+    let committer = args
+        .committer
+        .as_deref()
+        .map(str::parse::<commit::Identity>)
+        .transpose()
+        .context("Invalid --committer")?;
+
+    // Edit the manifest cargo resolved: the selected member with --package,
+    // otherwise the one found from --manifest-path or the current directory
+    // (which may be a parent directory)
+    let manifest_path = package.manifest_path.as_std_path();
+    let location: version_update::VersionLocation = args.location.parse()?;
+
+    // Step 3: Verify version is changing (and not going backwards).
+    // Versions are compared parsed, so a differently written target is still
+    // the same version; only --normalize rewrites it, in canonical form. The
+    // manifest text is checked as written, since cargo metadata has already
+    // normalized `current_version`
+    let written_version = version_update::read_cargo_toml_version(manifest_path, location)?
+        .unwrap_or_else(|| current_version.clone());
+    let same_version = current_version == target_version
+        || cmp_versions(&target_version, &current_version)
+            .is_ok_and(|ordering| ordering == Ordering::Equal);
+    let target_version = if same_version && args.normalize {
+        canonical_version(&target_version)?
+    } else {
+        target_version
+    };
+    if written_version == target_version {
         anyhow::bail!(
             "Current version ({}) is already the target version. Nothing to bump.",
+            written_version
+        );
+    }
+    if same_version && !args.normalize {
+        anyhow::bail!(
+            "Target version ({}) is the current version ({}), written differently. \
+             Pass --normalize to rewrite it anyway.",
+            target_version,
+            written_version
+        );
+    }
+    if !args.allow_downgrade && cmp_versions(&target_version, &current_version)? == Ordering::Less {
+        anyhow::bail!(
+            "Target version ({}) is lower than the current version ({}). \
+             Pass --allow-downgrade to bump anyway.",
+            target_version,
             current_version
         );
     }
 
+    // Refuse to commit onto a detached HEAD, where the commit would not be on
+    // any branch
+    if !args.no_commit && !args.force && commit::is_head_detached(manifest_path)? {
+        anyhow::bail!(
+            "HEAD is detached, so the bump commit would not be on any branch. \
+             Check out a branch first, or pass --force to commit anyway."
+        );
+    }
+
+    // Refuse to rewrite a commit that has been pushed
+    if args.amend
+        && !args.force
+        && let Some(remote_branch) = commit::head_pushed_to(manifest_path)?
+    {
+        anyhow::bail!(
+            "HEAD is already on {}, so amending it would rewrite published history. \
+             Pass --force to amend anyway.",
+            remote_branch
+        );
+    }
+
     logger.print_message(&format!(
         "Bumping version: {} -> {}",
         current_version, target_version
     ));
 
+    // Ask before touching anything when running interactively
+    if args.interactive && !args.yes && std::io::stdin().is_terminal() {
+        let mut files = vec![manifest_path.display().to_string()];
+        if args.update_dependents {
+            files.push(format!("workspace manifests depending on {}", package.name));
+        }
+        let mut stderr = std::io::stderr();
+        writeln!(stderr, "Files to update:")?;
+        for file in &files {
+            writeln!(stderr, "  {}", file)?;
+        }
+        if args.amend {
+            writeln!(stderr, "Commit: amend HEAD")?;
+        } else if !args.no_commit {
+            writeln!(
+                stderr,
+                "Commit: chore(version): bump {} -> {}",
+                current_version, target_version
+            )?;
+        }
+        if !crate::commands::common::confirm(
+            &mut std::io::stdin().lock(),
+            &mut stderr,
+            "Proceed? [y/N] ",
+        )? {
+            anyhow::bail!("Bump cancelled");
+        }
+    }
+
     // Step 4: Update Cargo.toml
     logger.status("Updating", "Cargo.toml");
-    let manifest_path = args
-        .manifest_path
-        .as_deref()
-        .unwrap_or_else(|| std::path::Path::new("./Cargo.toml"));
-    version_update::update_cargo_toml_version(manifest_path, &current_version, &target_version)?;
+    version_update::update_cargo_toml_version_at(
+        manifest_path,
+        &current_version,
+        &target_version,
+        location,
+    )?;
     logger.finish();
 
+    // Step 4b: Update dependency requirements in workspace siblings
+    let dependents = if args.update_dependents {
+        logger.status("Updating", "dependent manifests");
+        let dependents = dependents::update_dependents(
+            manifest_path,
+            &package.name,
+            &target_version,
+            args.cargo,
+        )?;
+        logger.finish();
+        for dependent in &dependents {
+            logger.print_message(&format!(
+                "Updated dependency requirement in {}",
+                dependent.display()
+            ));
+        }
+        dependents
+    } else {
+        Vec::new()
+    };
+
     // Step 5: Commit changes (unless --no-commit)
-    if !args.no_commit {
+    let commit = if !args.no_commit {
         logger.status("Committing", "version changes");
-        commit::commit_version_changes(manifest_path, &current_version, &target_version)?;
+        let options = commit::CommitOptions {
+            strict: args.strict,
+            additional_manifests: dependents,
+            author,
+            committer,
+            amend: args.amend,
+        };
+        let commit_id = commit::commit_version_changes(
+            manifest_path,
+            &current_version,
+            &target_version,
+            &options,
+        )?;
         logger.finish();
-        logger.print_message(&format!(
-            "✓ Committed version bump: {} -> {}",
-            current_version, target_version
-        ));
+        if let Some(note) = &args.note {
+            logger.status("Adding", "git note");
+            commit::add_note(manifest_path, commit_id, note)?;
+            logger.finish();
+        }
+        Some(commit_id.to_hex_with_len(SHORT_SHA_LENGTH).to_string())
     } else {
-        logger.print_message(&format!(
-            "✓ Updated version to {} (not committed)",
-            target_version
-        ));
-    }
+        None
+    };
 
-    Ok(())
+    Ok(BumpOutcome {
+        old: current_version,
+        new: target_version,
+        commit,
+    })
+}
+
+/// Length of the commit SHA shown after bumping.
+const SHORT_SHA_LENGTH: usize = 7;
+
+/// Result of a bump, printed with `--format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct BumpOutput<'a> {
+    /// Version before the bump.
+    old: &'a str,
+    /// Version after the bump.
+    new: &'a str,
+    /// Short SHA of the bump commit, if one was created.
+    commit: Option<String>,
+    /// Whether a commit was created.
+    committed: bool,
 }
 
 /// Calculate the target version based on command arguments.
@@ -322,11 +723,16 @@ fn calculate_target_version(args: &BumpArgs, current_version: &str) -> Result<St
         Ok(version.trim().to_string())
     } else if args.auto {
         // Auto-suggest from GitHub releases
-        let (owner, repo) = get_owner_repo(args.owner.clone(), args.repo.clone())?;
+        let (owner, repo) = github::resolve_owner_repo(args.owner.clone(), args.repo.clone())?;
         let github_token = args.github_token.as_deref();
         let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
-        let (_latest, next) =
-            rt.block_on(github::calculate_next_version(&owner, &repo, github_token))?;
+        let (_latest, next) = rt.block_on(github::calculate_next_version(
+            &owner,
+            &repo,
+            github_token,
+            args.tag_prefix.as_deref(),
+            args.include_prereleases,
+        ))?;
         Ok(next)
     } else {
         // Semantic version increment
@@ -60,6 +60,51 @@ fn init_test_git_repo(dir: &std::path::Path) {
         .unwrap();
 }
 
+/// Arguments for bumping `manifest_path` with every option at its CLI
+/// default. Tests pick the version with struct update syntax, e.g.
+/// `BumpArgs { minor: true, ..bump_args(path) }`.
+fn bump_args(manifest_path: std::path::PathBuf) -> BumpArgs {
+    BumpArgs {
+        manifest_path: Some(manifest_path),
+        package: None,
+        version: None,
+        auto: false,
+        include_prereleases: false,
+        major: false,
+        minor: false,
+        patch: false,
+        owner: None,
+        repo: None,
+        github_token: None,
+        tag_prefix: None,
+        no_commit: false,
+        strict: false,
+        amend: false,
+        location: "auto".to_string(),
+        update_dependents: false,
+        allow_downgrade: false,
+        normalize: false,
+        force: false,
+        interactive: false,
+        yes: false,
+        author: None,
+        committer: None,
+        note: None,
+        post_bump: None,
+        format: "text".to_string(),
+        cargo: CargoOptions::default(),
+    }
+}
+
+/// Arguments for a `--patch` bump of `manifest_path`, committing the result.
+fn patch_bump_args(manifest_path: std::path::PathBuf, force: bool) -> BumpArgs {
+    BumpArgs {
+        patch: true,
+        force,
+        ..bump_args(manifest_path)
+    }
+}
+
 #[test]
 fn test_bump_patch_version() {
     let dir = create_temp_cargo_project(
@@ -74,16 +119,9 @@ version = "0.1.2"
     init_test_git_repo(dir.path());
 
     let args = BumpArgs {
-        manifest_path: Some(manifest_path.clone()),
-        version: None,
-        auto: false,
-        major: false,
-        minor: false,
         patch: true,
-        owner: None,
-        repo: None,
-        github_token: None,
         no_commit: true, // Don't commit in tests
+        ..bump_args(manifest_path.clone())
     };
 
     let result = bump(args);
@@ -106,16 +144,9 @@ version = "0.1.2"
     let manifest_path = dir.path().join("Cargo.toml");
 
     let args = BumpArgs {
-        manifest_path: Some(manifest_path.clone()),
-        version: None,
-        auto: false,
-        major: false,
         minor: true,
-        patch: false,
-        owner: None,
-        repo: None,
-        github_token: None,
         no_commit: true,
+        ..bump_args(manifest_path.clone())
     };
 
     let result = bump(args);
@@ -137,16 +168,9 @@ version = "0.1.2"
     let manifest_path = dir.path().join("Cargo.toml");
 
     let args = BumpArgs {
-        manifest_path: Some(manifest_path.clone()),
-        version: None,
-        auto: false,
         major: true,
-        minor: false,
-        patch: false,
-        owner: None,
-        repo: None,
-        github_token: None,
         no_commit: true,
+        ..bump_args(manifest_path.clone())
     };
 
     let result = bump(args);
@@ -168,16 +192,9 @@ version = "0.1.2"
     let manifest_path = dir.path().join("Cargo.toml");
 
     let args = BumpArgs {
-        manifest_path: Some(manifest_path.clone()),
         version: Some("2.5.10".to_string()),
-        auto: false,
-        major: false,
-        minor: false,
-        patch: false,
-        owner: None,
-        repo: None,
-        github_token: None,
         no_commit: true,
+        ..bump_args(manifest_path.clone())
     };
 
     let result = bump(args);
@@ -199,16 +216,9 @@ version = "0.1.2"
     let manifest_path = dir.path().join("Cargo.toml");
 
     let args = BumpArgs {
-        manifest_path: Some(manifest_path),
         version: Some("0.1.2".to_string()),
-        auto: false,
-        major: false,
-        minor: false,
-        patch: false,
-        owner: None,
-        repo: None,
-        github_token: None,
         no_commit: true,
+        ..bump_args(manifest_path)
     };
 
     let result = bump(args);
@@ -221,6 +231,61 @@ version = "0.1.2"
     );
 }
 
+#[test]
+fn test_bump_downgrade_error() {
+    let dir = create_temp_cargo_project(
+        r#"
+[package]
+name = "test"
+version = "0.2.0"
+"#,
+    );
+    let manifest_path = dir.path().join("Cargo.toml");
+
+    let args = BumpArgs {
+        version: Some("0.1.0".to_string()),
+        no_commit: true,
+        ..bump_args(manifest_path.clone())
+    };
+
+    let result = bump(args);
+    assert!(result.is_err());
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("--allow-downgrade")
+    );
+
+    // The manifest is left untouched
+    let content = std::fs::read_to_string(&manifest_path).unwrap();
+    assert!(content.contains("version = \"0.2.0\""));
+}
+
+#[test]
+fn test_bump_downgrade_allowed() {
+    let dir = create_temp_cargo_project(
+        r#"
+[package]
+name = "test"
+version = "0.2.0"
+"#,
+    );
+    let manifest_path = dir.path().join("Cargo.toml");
+
+    let args = BumpArgs {
+        version: Some("0.1.0".to_string()),
+        no_commit: true,
+        allow_downgrade: true,
+        ..bump_args(manifest_path.clone())
+    };
+
+    assert!(bump(args).is_ok());
+
+    let content = std::fs::read_to_string(&manifest_path).unwrap();
+    assert!(content.contains("version = \"0.1.0\""));
+}
+
 /// Create a test git repository using gix (not git commands).
 ///
 /// This creates a proper git repository with:
@@ -445,16 +510,9 @@ edition = "2021"
 
     // Run bump command
     let args = BumpArgs {
-        manifest_path: Some(manifest_path.clone()),
         version: Some("0.2.0".to_string()),
-        auto: false,
-        major: false,
-        minor: false,
-        patch: false,
-        owner: None,
-        repo: None,
-        github_token: None,
         no_commit: false, // DO commit
+        ..bump_args(manifest_path.clone())
     };
 
     let result = bump(args);
@@ -536,16 +594,8 @@ license = "Apache-2.0"
 
     // Run bump to change version
     let args = BumpArgs {
-        manifest_path: Some(manifest_path.clone()),
         patch: true,
-        version: None,
-        auto: false,
-        major: false,
-        minor: false,
-        owner: None,
-        repo: None,
-        github_token: None,
-        no_commit: false,
+        ..bump_args(manifest_path.clone())
     };
 
     let result = bump(args);
@@ -615,16 +665,8 @@ version = "0.5.0"
 
     // Run bump
     let args = BumpArgs {
-        manifest_path: Some(manifest_path),
         patch: true,
-        version: None,
-        auto: false,
-        major: false,
-        minor: false,
-        owner: None,
-        repo: None,
-        github_token: None,
-        no_commit: false,
+        ..bump_args(manifest_path)
     };
 
     let result = bump(args);
@@ -736,16 +778,8 @@ version = "2.0.0"
     // Now run bump - it should NOT include README.md
     let manifest_path = dir.path().join("Cargo.toml");
     let args = BumpArgs {
-        manifest_path: Some(manifest_path),
         major: true,
-        version: None,
-        auto: false,
-        minor: false,
-        patch: false,
-        owner: None,
-        repo: None,
-        github_token: None,
-        no_commit: false,
+        ..bump_args(manifest_path)
     };
 
     let result = bump(args);
@@ -806,16 +840,8 @@ version = "1.0.0"
     // Run bump
     let manifest_path = dir.path().join("Cargo.toml");
     let args = BumpArgs {
-        manifest_path: Some(manifest_path),
         patch: true,
-        version: None,
-        auto: false,
-        major: false,
-        minor: false,
-        owner: None,
-        repo: None,
-        github_token: None,
-        no_commit: false,
+        ..bump_args(manifest_path)
     };
 
     let result = bump(args);
@@ -922,16 +948,8 @@ version = "0.5.0"
     // Now run bump
     let manifest_path = dir.path().join("Cargo.toml");
     let args = BumpArgs {
-        manifest_path: Some(manifest_path),
         minor: true,
-        version: None,
-        auto: false,
-        major: false,
-        patch: false,
-        owner: None,
-        repo: None,
-        github_token: None,
-        no_commit: false,
+        ..bump_args(manifest_path)
     };
 
     let result = bump(args);
@@ -999,3 +1017,739 @@ version = "0.5.0"
         "Cargo.toml version should be bumped (minor: 0.5.0 -> 0.6.0)"
     );
 }
+
+#[test]
+fn test_strict_aborts_on_non_version_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let initial_content = r#"[package]
+name = "test"
+version = "0.1.0"
+description = "original description"
+"#;
+
+    let repo = create_test_git_repo_with_gix(dir.path(), initial_content);
+    let head_before = repo.head_id().expect("Failed to read HEAD").detach();
+
+    let manifest_path = dir.path().join("Cargo.toml");
+    let modified_content = r#"[package]
+name = "test"
+version = "0.1.0"
+description = "modified description"
+"#;
+    std::fs::write(&manifest_path, modified_content).expect("Failed to modify Cargo.toml");
+
+    let args = BumpArgs {
+        patch: true,
+        strict: true,
+        ..bump_args(manifest_path)
+    };
+
+    let err = bump(args).expect_err("Strict bump should fail with non-version changes");
+    let message = format!("{:#}", err);
+    assert!(message.contains("non-version changes"), "{}", message);
+    assert!(
+        message.contains("+description = \"modified description\""),
+        "{}",
+        message
+    );
+
+    // No commit should have been created
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    assert_eq!(
+        repo.head_id().expect("Failed to read HEAD").detach(),
+        head_before
+    );
+}
+
+/// Detach HEAD at its current commit, as `git checkout --detach` would.
+fn detach_head(dir: &std::path::Path) {
+    let status = std::process::Command::new("git")
+        .args(["checkout", "-q", "--detach"])
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git checkout --detach failed");
+}
+
+#[test]
+fn test_bump_refuses_detached_head() {
+    let dir = tempfile::tempdir().unwrap();
+    let initial_content = "[package]\nname = \"test\"\nversion = \"0.1.0\"\n";
+    let repo = create_test_git_repo_with_gix(dir.path(), initial_content);
+    let head_before = repo.head_id().expect("Failed to read HEAD").detach();
+    detach_head(dir.path());
+
+    let manifest_path = dir.path().join("Cargo.toml");
+    let err = bump(patch_bump_args(manifest_path.clone(), false))
+        .expect_err("Bump should refuse a detached HEAD");
+    assert!(format!("{:#}", err).contains("--force"), "{:#}", err);
+
+    // Nothing was modified or committed
+    assert_eq!(
+        std::fs::read_to_string(&manifest_path).unwrap(),
+        initial_content
+    );
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    assert_eq!(
+        repo.head_id().expect("Failed to read HEAD").detach(),
+        head_before
+    );
+}
+
+#[test]
+fn test_bump_detached_head_with_force() {
+    let dir = tempfile::tempdir().unwrap();
+    let initial_content = "[package]\nname = \"test\"\nversion = \"0.1.0\"\n";
+    let repo = create_test_git_repo_with_gix(dir.path(), initial_content);
+    let head_before = repo.head_id().expect("Failed to read HEAD").detach();
+    detach_head(dir.path());
+
+    let manifest_path = dir.path().join("Cargo.toml");
+    let result = bump(patch_bump_args(manifest_path, true));
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+
+    // HEAD is still detached and now points at the bump commit
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    let head = repo.head().expect("Failed to read HEAD");
+    assert!(head.is_detached());
+    let commit = repo.head_commit().expect("Failed to read HEAD commit");
+    assert_eq!(
+        commit.parent_ids().next().map(|id| id.detach()),
+        Some(head_before)
+    );
+    let content = std::fs::read_to_string(dir.path().join("Cargo.toml")).unwrap();
+    assert!(content.contains("version = \"0.1.1\""));
+}
+
+#[test]
+fn test_update_dependents_in_workspace() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"foo\", \"bar\"]\nresolver = \"2\"\n",
+    )
+    .unwrap();
+    for (name, manifest) in [
+        (
+            "foo",
+            "[package]\nname = \"foo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        ),
+        (
+            "bar",
+            "[package]\nname = \"bar\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\nfoo = { path = \"../foo\", version = \"0.1\" }\n",
+        ),
+    ] {
+        std::fs::create_dir_all(root.join(name).join("src")).unwrap();
+        std::fs::write(root.join(name).join("Cargo.toml"), manifest).unwrap();
+        std::fs::write(root.join(name).join("src/lib.rs"), "").unwrap();
+    }
+    for args in [
+        vec!["init"],
+        vec!["config", "user.email", "test@example.com"],
+        vec!["config", "user.name", "Test User"],
+        vec!["add", "-A"],
+        vec!["commit", "-m", "Initial commit"],
+    ] {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .unwrap();
+    }
+
+    let args = BumpArgs {
+        minor: true,
+        update_dependents: true,
+        ..bump_args(root.join("foo/Cargo.toml"))
+    };
+
+    let result = bump(args);
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+
+    // Both manifests are part of the bump commit
+    let repo = gix::open(root).expect("Failed to open repo");
+    let commit = repo.head_commit().expect("Failed to read HEAD commit");
+    let tree = commit.tree().expect("Failed to get tree");
+    let read_committed = |path: &str| {
+        let entry = tree
+            .lookup_entry_by_path(path)
+            .expect("Failed to lookup file")
+            .expect("File not in commit");
+        entry
+            .object()
+            .expect("Failed to get blob")
+            .try_into_blob()
+            .expect("Not a blob")
+            .data
+            .to_str_lossy()
+            .into_owned()
+    };
+
+    assert!(read_committed("foo/Cargo.toml").contains("version = \"0.2.0\""));
+    assert!(read_committed("bar/Cargo.toml").contains("version = \"0.2\" }"));
+    // Untouched files are preserved
+    assert!(
+        tree.lookup_entry_by_path("bar/src/lib.rs")
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[test]
+fn test_commit_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let initial_content = "[package]\nname = \"test\"\nversion = \"0.1.0\"\nedition = \"2021\"\n";
+    create_test_git_repo_with_gix(dir.path(), initial_content);
+
+    let manifest_path = dir.path().join("Cargo.toml");
+    let updated_content = initial_content.replace("2021", "2024");
+    std::fs::write(&manifest_path, &updated_content).unwrap();
+
+    commit::commit_files(&[manifest_path], "chore(edition): set edition to 2024").unwrap();
+
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    let commit = repo.head_commit().expect("Failed to read HEAD commit");
+    let message = commit.message_raw().expect("Failed to read message");
+    assert_eq!(
+        message.to_str_lossy(),
+        "chore(edition): set edition to 2024"
+    );
+    let committed = commit
+        .tree()
+        .expect("Failed to get tree")
+        .lookup_entry_by_path("Cargo.toml")
+        .expect("Failed to lookup file")
+        .expect("File not in commit")
+        .object()
+        .expect("Failed to get blob")
+        .data
+        .to_str_lossy()
+        .into_owned();
+    assert_eq!(committed, updated_content);
+}
+
+#[test]
+fn test_identity_from_str() {
+    let identity: commit::Identity = "Release Bot <bot@example.com>".parse().unwrap();
+    assert_eq!(identity.name, "Release Bot");
+    assert_eq!(identity.email, "bot@example.com");
+
+    for invalid in [
+        "Release Bot",
+        "<bot@example.com>",
+        "Bot <>",
+        "Bot <a> <b>",
+        "Bot bot@example.com>",
+    ] {
+        assert!(invalid.parse::<commit::Identity>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_bump_with_author_and_committer() {
+    let dir = tempfile::tempdir().unwrap();
+    let initial_content = "[package]\nname = \"test\"\nversion = \"0.1.0\"\n";
+    create_test_git_repo_with_gix(dir.path(), initial_content);
+
+    let mut args = patch_bump_args(dir.path().join("Cargo.toml"), false);
+    args.author = Some("Release Bot <bot@example.com>".to_string());
+    args.committer = Some("CI <ci@example.com>".to_string());
+    let result = bump(args);
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    let commit = repo.head_commit().expect("Failed to read HEAD commit");
+    let author = commit.author().expect("Failed to read author");
+    assert_eq!(author.name, "Release Bot");
+    assert_eq!(author.email, "bot@example.com");
+    let committer = commit.committer().expect("Failed to read committer");
+    assert_eq!(committer.name, "CI");
+    assert_eq!(committer.email, "ci@example.com");
+}
+
+#[test]
+fn test_bump_rejects_malformed_author() {
+    let dir = tempfile::tempdir().unwrap();
+    let initial_content = "[package]\nname = \"test\"\nversion = \"0.1.0\"\n";
+    create_test_git_repo_with_gix(dir.path(), initial_content);
+
+    let manifest_path = dir.path().join("Cargo.toml");
+    let mut args = patch_bump_args(manifest_path.clone(), false);
+    args.author = Some("bot@example.com".to_string());
+    let err = bump(args).expect_err("Malformed --author should be rejected");
+    assert!(
+        format!("{:#}", err).contains("Invalid --author"),
+        "{:#}",
+        err
+    );
+
+    // Rejected before the manifest was touched
+    assert_eq!(
+        std::fs::read_to_string(&manifest_path).unwrap(),
+        initial_content
+    );
+}
+
+#[test]
+fn test_commit_version_changes_returns_commit_id() {
+    let dir = tempfile::tempdir().unwrap();
+    let initial_content = "[package]\nname = \"test\"\nversion = \"0.1.0\"\n";
+    create_test_git_repo_with_gix(dir.path(), initial_content);
+
+    let manifest_path = dir.path().join("Cargo.toml");
+    std::fs::write(&manifest_path, initial_content.replace("0.1.0", "0.1.1")).unwrap();
+
+    let commit_id = commit::commit_version_changes(
+        &manifest_path,
+        "0.1.0",
+        "0.1.1",
+        &commit::CommitOptions::default(),
+    )
+    .unwrap();
+
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    assert_eq!(
+        repo.head_id().expect("Failed to read HEAD").detach(),
+        commit_id
+    );
+}
+
+#[test]
+fn test_bump_invalid_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let initial_content = "[package]\nname = \"test\"\nversion = \"0.1.0\"\n";
+    create_test_git_repo_with_gix(dir.path(), initial_content);
+
+    let manifest_path = dir.path().join("Cargo.toml");
+    let mut args = patch_bump_args(manifest_path.clone(), false);
+    args.format = "yaml".to_string();
+    assert!(bump(args).is_err());
+    assert_eq!(
+        std::fs::read_to_string(&manifest_path).unwrap(),
+        initial_content
+    );
+}
+
+#[test]
+fn test_bump_from_nested_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let crate_dir = dir.path().join("crates").join("foo");
+    std::fs::create_dir_all(crate_dir.join("src")).unwrap();
+    std::fs::write(
+        crate_dir.join("Cargo.toml"),
+        "[package]\nname = \"foo\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(crate_dir.join("src").join("lib.rs"), "// Test library\n").unwrap();
+    for git_args in [
+        &["init", "-q"][..],
+        &["config", "user.email", "test@example.com"],
+        &["config", "user.name", "Test User"],
+        &["add", "."],
+        &["commit", "-q", "-m", "Initial commit"],
+    ] {
+        let status = std::process::Command::new("git")
+            .args(git_args)
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", git_args);
+    }
+
+    // The manifest lives below the repository root
+    let result = bump(patch_bump_args(crate_dir.join("Cargo.toml"), false));
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    let tree = repo
+        .head_commit()
+        .expect("Failed to read HEAD commit")
+        .tree()
+        .expect("Failed to get tree");
+    let committed = tree
+        .lookup_entry_by_path("crates/foo/Cargo.toml")
+        .expect("Failed to lookup file")
+        .expect("Manifest not in commit")
+        .object()
+        .expect("Failed to get blob")
+        .data
+        .to_str_lossy()
+        .into_owned();
+    assert!(committed.contains("version = \"0.1.1\""), "{}", committed);
+    assert!(
+        tree.lookup_entry_by_path("Cargo.toml")
+            .expect("Failed to lookup")
+            .is_none(),
+        "Manifest must not be committed at the repository root"
+    );
+}
+
+#[test]
+fn test_git_status_clean_after_bump() {
+    let dir = create_temp_cargo_project("[package]\nname = \"test\"\nversion = \"0.1.0\"\n");
+    init_test_git_repo(dir.path());
+
+    let result = bump(patch_bump_args(dir.path().join("Cargo.toml"), false));
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+
+    // The index matches the new HEAD and the working tree
+    let output = std::process::Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(output.stdout.to_str_lossy(), "");
+}
+
+#[test]
+fn test_stage_file_keeps_valid_tree_cache() {
+    let dir = create_temp_cargo_project("[package]\nname = \"test\"\nversion = \"0.1.0\"\n");
+    init_test_git_repo(dir.path());
+
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    let index_path = repo.index_path();
+    let relative_path = std::path::Path::new("Cargo.toml");
+    let state = index::load_index_state(&index_path, repo.object_hash()).unwrap();
+    // `git commit` records the tree cache
+    assert!(state.tree().is_some());
+    let entry_count = state.entries().len();
+    let head_blob = repo
+        .head_commit()
+        .expect("Failed to read HEAD commit")
+        .tree()
+        .expect("Failed to get tree")
+        .lookup_entry_by_path("Cargo.toml")
+        .expect("Failed to lookup file")
+        .expect("Manifest not in HEAD")
+        .oid()
+        .to_owned();
+
+    // Re-staging the same content keeps the cache
+    index::stage_file(&index_path, &repo, relative_path, head_blob, state).unwrap();
+    let state = index::load_index_state(&index_path, repo.object_hash()).unwrap();
+    assert!(state.tree().is_some());
+    assert_eq!(state.entries().len(), entry_count);
+
+    // New content invalidates it
+    let new_blob = repo
+        .write_object(gix::objs::Blob {
+            data: b"[package]\nname = \"test\"\nversion = \"0.1.1\"\n".into(),
+        })
+        .expect("Failed to write blob")
+        .detach();
+    index::stage_file(&index_path, &repo, relative_path, new_blob, state).unwrap();
+    let state = index::load_index_state(&index_path, repo.object_hash()).unwrap();
+    assert!(state.tree().is_none());
+    assert_eq!(state.entries().len(), entry_count);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_commit_files_with_non_utf8_path() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = create_temp_cargo_project("[package]\nname = \"test\"\nversion = \"0.1.0\"\n");
+    // "caf\xe9" is Latin-1, not UTF-8
+    let file_name = std::ffi::OsStr::from_bytes(b"caf\xe9.toml");
+    let nested_dir = dir.path().join(std::ffi::OsStr::from_bytes(b"d\xefr"));
+    std::fs::create_dir_all(&nested_dir).unwrap();
+    let file_path = nested_dir.join(file_name);
+    std::fs::write(&file_path, "version = \"0.1.0\"\n").unwrap();
+    init_test_git_repo(dir.path());
+    for git_args in [
+        &["add", "--"][..],
+        &["commit", "-q", "-m", "Add file", "--"],
+    ] {
+        let status = std::process::Command::new("git")
+            .args(git_args)
+            .arg(&file_path)
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", git_args);
+    }
+
+    std::fs::write(&file_path, "version = \"0.2.0\"\n").unwrap();
+    commit::commit_files(std::slice::from_ref(&file_path), "chore: update").unwrap();
+
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    let committed = repo
+        .head_commit()
+        .expect("Failed to read HEAD commit")
+        .tree()
+        .expect("Failed to get tree")
+        .lookup_entry_by_path(file_path.strip_prefix(dir.path()).unwrap())
+        .expect("Failed to lookup file")
+        .expect("File not in commit")
+        .object()
+        .expect("Failed to get blob")
+        .data
+        .to_str_lossy()
+        .into_owned();
+    assert_eq!(committed, "version = \"0.2.0\"\n");
+
+    // The index entry has the same raw bytes as the tree entry
+    let output = std::process::Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.stdout.to_str_lossy(), "");
+}
+
+/// Run a git command in `dir`, asserting that it succeeds.
+fn run_git(dir: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn test_bump_amend() {
+    let dir = create_temp_cargo_project("[package]\nname = \"test\"\nversion = \"0.1.0\"\n");
+    init_test_git_repo(dir.path());
+    std::fs::write(dir.path().join("NOTES.md"), "notes\n").unwrap();
+    run_git(dir.path(), &["add", "NOTES.md"]);
+    run_git(dir.path(), &["commit", "-q", "-m", "docs: add notes"]);
+
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    let original = repo.head_commit().expect("Failed to read HEAD commit");
+    let original_parents: Vec<_> = original.parent_ids().map(|id| id.detach()).collect();
+
+    let result = bump(BumpArgs {
+        amend: true,
+        ..patch_bump_args(dir.path().join("Cargo.toml"), false)
+    });
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    let amended = repo.head_commit().expect("Failed to read HEAD commit");
+    assert_ne!(amended.id, original.id);
+    assert_eq!(
+        amended.message_raw().expect("Failed to read message"),
+        "docs: add notes\n"
+    );
+    let amended_parents: Vec<_> = amended.parent_ids().map(|id| id.detach()).collect();
+    assert_eq!(amended_parents, original_parents);
+
+    // The amended commit has both the original change and the bump
+    let tree = amended.tree().expect("Failed to get tree");
+    assert!(
+        tree.lookup_entry_by_path("NOTES.md")
+            .expect("Failed to lookup")
+            .is_some()
+    );
+    let manifest = tree
+        .lookup_entry_by_path("Cargo.toml")
+        .expect("Failed to lookup file")
+        .expect("Manifest not in commit")
+        .object()
+        .expect("Failed to get blob")
+        .data
+        .to_str_lossy()
+        .into_owned();
+    assert!(manifest.contains("version = \"0.1.1\""), "{}", manifest);
+}
+
+#[test]
+fn test_bump_amend_refuses_pushed_commit() {
+    let dir = create_temp_cargo_project("[package]\nname = \"test\"\nversion = \"0.1.0\"\n");
+    init_test_git_repo(dir.path());
+    // Pretend HEAD has been pushed
+    run_git(
+        dir.path(),
+        &["update-ref", "refs/remotes/origin/main", "HEAD"],
+    );
+
+    let manifest_path = dir.path().join("Cargo.toml");
+    let result = bump(BumpArgs {
+        amend: true,
+        ..patch_bump_args(manifest_path.clone(), false)
+    });
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("refs/remotes/origin/main"), "{}", error);
+    assert!(
+        std::fs::read_to_string(&manifest_path)
+            .unwrap()
+            .contains("0.1.0")
+    );
+
+    let result = bump(BumpArgs {
+        amend: true,
+        ..patch_bump_args(manifest_path, true)
+    });
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+}
+
+#[test]
+fn test_bump_without_commits() {
+    let dir = create_temp_cargo_project("[package]\nname = \"test\"\nversion = \"0.1.0\"\n");
+    run_git(dir.path(), &["init", "-q"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+
+    let result = bump(patch_bump_args(dir.path().join("Cargo.toml"), false));
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+
+    // The bump is the first commit, on the branch HEAD points at
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    let head = repo.head().expect("Failed to read HEAD");
+    assert!(!head.is_detached());
+    let commit = repo.head_commit().expect("Failed to read HEAD commit");
+    assert_eq!(commit.parent_ids().count(), 0);
+    assert_eq!(
+        commit.message_raw().expect("Failed to read message"),
+        "chore(version): bump 0.1.0 -> 0.1.1"
+    );
+    let tree = commit.tree().expect("Failed to get tree");
+    let manifest = tree
+        .lookup_entry_by_path("Cargo.toml")
+        .expect("Failed to lookup file")
+        .expect("Manifest not in commit")
+        .object()
+        .expect("Failed to get blob")
+        .data
+        .to_str_lossy()
+        .into_owned();
+    assert!(manifest.contains("version = \"0.1.1\""), "{}", manifest);
+    // Only the manifest is committed
+    assert_eq!(tree.iter().count(), 1);
+}
+
+#[test]
+fn test_bump_same_version_written_differently() {
+    let dir = create_temp_cargo_project("[package]\nname = \"test\"\nversion = \"1.0.0\"\n");
+    let manifest_path = dir.path().join("Cargo.toml");
+    let args = |version: &str, normalize| BumpArgs {
+        version: Some(version.to_string()),
+        no_commit: true,
+        normalize,
+        ..bump_args(manifest_path.clone())
+    };
+    let written_version = || {
+        let content = std::fs::read_to_string(&manifest_path).unwrap();
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("version = "))
+            .unwrap()
+            .to_string()
+    };
+
+    let error = bump(args("v1.0.0+build.1", false)).unwrap_err().to_string();
+    assert!(error.contains("--normalize"), "{}", error);
+    assert_eq!(written_version(), "\"1.0.0\"");
+
+    // The target is written in canonical form, without the `v`
+    let result = bump(args("v1.0.0+build.1", true));
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+    assert_eq!(written_version(), "\"1.0.0+build.1\"");
+
+    // Normalizing to what is already written is a no-op
+    let error = bump(args("V1.0.0+build.1", true)).unwrap_err().to_string();
+    assert!(error.contains("Nothing to bump"), "{}", error);
+    assert_eq!(written_version(), "\"1.0.0+build.1\"");
+}
+
+#[test]
+fn test_bump_version() {
+    let dir = create_temp_cargo_project("[package]\nname = \"test\"\nversion = \"0.1.0\"\n");
+    init_test_git_repo(dir.path());
+    let manifest_path = dir.path().join("Cargo.toml");
+
+    let outcome = bump_version(&manifest_path, VersionTarget::Patch, true).unwrap();
+    assert_eq!(outcome.old, "0.1.0");
+    assert_eq!(outcome.new, "0.1.1");
+    let repo = gix::open(dir.path()).expect("Failed to open repo");
+    let head = repo.head_id().expect("Failed to read HEAD");
+    assert_eq!(
+        outcome.commit,
+        Some(head.to_hex_with_len(SHORT_SHA_LENGTH).to_string())
+    );
+
+    let outcome = bump_version(&manifest_path, VersionTarget::Minor, false).unwrap();
+    assert_eq!(
+        outcome,
+        BumpOutcome {
+            old: "0.1.1".to_string(),
+            new: "0.2.0".to_string(),
+            commit: None,
+        }
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_bump_post_bump_hook() {
+    let dir = create_temp_cargo_project("[package]\nname = \"test\"\nversion = \"0.1.0\"\n");
+    let manifest_path = dir.path().join("Cargo.toml");
+    let hook_output = dir.path().join("hook.txt");
+
+    let result = bump(BumpArgs {
+        no_commit: true,
+        post_bump: Some(format!(
+            "echo \"$OLD_VERSION -> $NEW_VERSION\" > '{}'",
+            hook_output.display()
+        )),
+        ..patch_bump_args(manifest_path.clone(), false)
+    });
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+    assert_eq!(
+        std::fs::read_to_string(&hook_output).unwrap(),
+        "0.1.0 -> 0.1.1\n"
+    );
+
+    // A failing hook is reported, but the bump is kept
+    let result = bump(BumpArgs {
+        no_commit: true,
+        post_bump: Some("exit 3".to_string()),
+        ..patch_bump_args(manifest_path.clone(), false)
+    });
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("Post-bump hook failed"), "{}", error);
+    assert!(
+        std::fs::read_to_string(&manifest_path)
+            .unwrap()
+            .contains("version = \"0.1.2\"")
+    );
+}
+
+#[test]
+fn test_bump_note() {
+    let dir = create_temp_cargo_project("[package]\nname = \"test\"\nversion = \"0.1.0\"\n");
+    init_test_git_repo(dir.path());
+    let manifest_path = dir.path().join("Cargo.toml");
+
+    let result = bump(BumpArgs {
+        note: Some("released by CI run 123".to_string()),
+        ..patch_bump_args(manifest_path.clone(), false)
+    });
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+    let result = bump(BumpArgs {
+        note: Some("released by CI run 124".to_string()),
+        ..patch_bump_args(manifest_path, false)
+    });
+    assert!(result.is_ok(), "Bump failed: {:?}", result.err());
+
+    let note = |rev: &str| {
+        let output = std::process::Command::new("git")
+            .args(["notes", "show", rev])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git notes show {} failed", rev);
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert_eq!(note("HEAD"), "released by CI run 124\n");
+    assert_eq!(note("HEAD~1"), "released by CI run 123\n");
+    // The earlier note is kept in the history of the notes ref
+    run_git(
+        dir.path(),
+        &["rev-parse", "--verify", "-q", "refs/notes/commits~1"],
+    );
+}
@@ -40,9 +40,12 @@
 //! 1. **Package section**: `[package] version = "X.Y.Z"`
 //! 2. **Workspace section**: `[workspace.package] version = "X.Y.Z"`
 //!
-//! We check both locations and update whichever is found.
+//! By default we check `[package]` first and fall back to
+//! `[workspace.package]`. [`update_cargo_toml_version_at`] takes a
+//! [`VersionLocation`] to update only one of them, or both.
 
 use std::path::Path;
+use std::str::FromStr;
 
 use anyhow::{
     Context,
@@ -50,9 +53,39 @@ use anyhow::{
 };
 use toml_edit::{
     DocumentMut,
+    Item,
+    Table,
     value,
 };
 
+/// Which version field(s) to update in a manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VersionLocation {
+    /// `[package]` if present, otherwise `[workspace.package]`.
+    #[default]
+    Auto,
+    /// Only `[package] version`.
+    Package,
+    /// Only `[workspace.package] version`.
+    Workspace,
+    /// Every version field found (`[package]` and `[workspace.package]`).
+    Both,
+}
+
+impl FromStr for VersionLocation {
+    type Err = anyhow::Error;
+
+    fn from_str(location: &str) -> Result<Self> {
+        match location {
+            "auto" => Ok(Self::Auto),
+            "package" => Ok(Self::Package),
+            "workspace" => Ok(Self::Workspace),
+            "both" => Ok(Self::Both),
+            _ => anyhow::bail!("Invalid location: {}", location),
+        }
+    }
+}
+
 /// Update the version field in a Cargo.toml file.
 ///
 /// This function parses the TOML file, locates the version field (in either
@@ -62,7 +95,7 @@ use toml_edit::{
 /// # Arguments
 ///
 /// * `manifest_path` - Path to the Cargo.toml file
-/// * `_old_version` - The current version (unused, kept for API consistency)
+/// * `old_version` - The current version (unused, kept for API consistency)
 /// * `new_version` - The target version to set
 ///
 /// # Errors
@@ -71,6 +104,9 @@ use toml_edit::{
 /// - The file cannot be read
 /// - The TOML is invalid
 /// - No `[package]` or `[workspace.package]` section is found
+/// - `[package]` inherits its version from the workspace and there is no
+///   `[workspace.package]` in the same file to update instead
+/// - `version` is not a string
 /// - The file cannot be written
 ///
 /// # Examples
@@ -111,9 +147,35 @@ use toml_edit::{
 /// edition = "2021"
 /// ```
 pub fn update_cargo_toml_version(
+    manifest_path: &Path,
+    old_version: &str,
+    new_version: &str,
+) -> Result<()> {
+    update_cargo_toml_version_at(
+        manifest_path,
+        old_version,
+        new_version,
+        VersionLocation::Auto,
+    )
+}
+
+/// Update the version field(s) at the given location in a Cargo.toml file.
+///
+/// Like [`update_cargo_toml_version`], but lets the caller choose which
+/// version field(s) to update. This matters for workspace roots that are also
+/// crates, where both `[package]` and `[workspace.package]` may carry a
+/// version.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, parsed, or written, if none
+/// of the requested sections exist (or `[package]` only inherits the
+/// workspace version), or if `version` is not a string.
+pub fn update_cargo_toml_version_at(
     manifest_path: &Path,
     _old_version: &str,
     new_version: &str,
+    location: VersionLocation,
 ) -> Result<()> {
     // Read the current content
     let content = std::fs::read_to_string(manifest_path)
@@ -125,44 +187,191 @@ pub fn update_cargo_toml_version(
         .parse::<DocumentMut>()
         .with_context(|| format!("Failed to parse TOML in {}", manifest_path.display()))?;
 
+    let update_package = matches!(
+        location,
+        VersionLocation::Auto | VersionLocation::Package | VersionLocation::Both
+    );
+    let update_workspace = matches!(
+        location,
+        VersionLocation::Auto | VersionLocation::Workspace | VersionLocation::Both
+    );
+
     // Try to update version in [package] section first
     // The as_table_mut() method returns None if the item isn't a table
-    let updated = if let Some(package) = doc.get_mut("package").and_then(|p| p.as_table_mut()) {
-        // Found [package] section - update version
-        // The value() function creates a properly formatted TOML value
-        package.insert("version", value(new_version));
-        true
-    } else if let Some(workspace_package) = doc
-        .get_mut("workspace")
-        .and_then(|w| w.as_table_mut())
-        .and_then(|w| w.get_mut("package"))
-        .and_then(|p| p.as_table_mut())
+    let mut updated = false;
+    let mut inherited = false;
+    if update_package && let Some(package) = doc.get_mut("package").and_then(|p| p.as_table_mut()) {
+        // An inherited version (`version.workspace = true`) lives in the
+        // workspace root; it must not be replaced by a literal version here
+        if package.get("version").is_some_and(is_inherited) {
+            inherited = true;
+        } else {
+            set_version(package, "[package]", manifest_path, new_version)?;
+            updated = true;
+        }
+    }
+
+    // In auto mode, [workspace.package] is only a fallback
+    let update_workspace = update_workspace && !(location == VersionLocation::Auto && updated);
+    if update_workspace
+        && let Some(workspace_package) = doc
+            .get_mut("workspace")
+            .and_then(|w| w.as_table_mut())
+            .and_then(|w| w.get_mut("package"))
+            .and_then(|p| p.as_table_mut())
     {
         // Found [workspace.package] section - update version
         // This is used in workspace crates that inherit version from the workspace
-        workspace_package.insert("version", value(new_version));
-        true
-    } else {
-        // Neither [package] nor [workspace.package] found
-        false
-    };
+        set_version(
+            workspace_package,
+            "[workspace.package]",
+            manifest_path,
+            new_version,
+        )?;
+        updated = true;
+    }
 
+    if !updated && inherited {
+        anyhow::bail!(
+            "The version in {} is inherited from the workspace (`version.workspace = true`); \
+             bump the workspace root manifest instead",
+            manifest_path.display()
+        );
+    }
     if !updated {
+        let sections = match location {
+            VersionLocation::Package => "[package]",
+            VersionLocation::Workspace => "[workspace.package]",
+            VersionLocation::Auto | VersionLocation::Both => "[package] or [workspace.package]",
+        };
         anyhow::bail!(
-            "Could not find [package] or [workspace.package] section in {}",
+            "Could not find {} section in {}",
+            sections,
             manifest_path.display()
         );
     }
 
     // Write back the modified document
     // The to_string() method serializes the document while preserving all
-    // formatting that was tracked during parsing
-    std::fs::write(manifest_path, doc.to_string())
+    // formatting that was tracked during parsing; line endings are restored
+    // separately since inserted values always use `\n`
+    let updated = preserve_line_endings(&content, &doc.to_string());
+    std::fs::write(manifest_path, updated)
         .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
 
     Ok(())
 }
 
+/// Read the version field at the given location of a Cargo.toml file, as
+/// written.
+///
+/// Looks the field up the way [`update_cargo_toml_version_at`] would; with
+/// [`VersionLocation::Both`] the `[package]` version wins. Returns `None` if
+/// there is no literal version string there, e.g. because `[package]` only
+/// inherits the workspace version.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed.
+pub fn read_cargo_toml_version(
+    manifest_path: &Path,
+    location: VersionLocation,
+) -> Result<Option<String>> {
+    let content = std::fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse TOML in {}", manifest_path.display()))?;
+
+    let package = doc
+        .get("package")
+        .and_then(|package| package.get("version"));
+    let workspace = doc
+        .get("workspace")
+        .and_then(|workspace| workspace.get("package"))
+        .and_then(|package| package.get("version"));
+    let version = match location {
+        VersionLocation::Auto | VersionLocation::Both => package
+            .filter(|version| !is_inherited(version))
+            .or(workspace),
+        VersionLocation::Package => package,
+        VersionLocation::Workspace => workspace,
+    };
+
+    Ok(version.and_then(Item::as_str).map(str::to_string))
+}
+
+/// Whether a `version` item inherits the workspace version
+/// (`version.workspace = true` or `version = { workspace = true }`).
+fn is_inherited(version: &Item) -> bool {
+    version
+        .as_table_like()
+        .and_then(|table| table.get("workspace"))
+        .and_then(|workspace| workspace.as_bool())
+        .unwrap_or(false)
+}
+
+/// Set `version` in a manifest section, keeping the comments and whitespace
+/// around an existing value.
+///
+/// A missing field is added. Any value other than a string (an integer, an
+/// array, a table other than the workspace inheritance marker) is rejected
+/// with its TOML type, since cargo would not accept it either.
+fn set_version(
+    section: &mut Table,
+    section_name: &str,
+    manifest_path: &Path,
+    new_version: &str,
+) -> Result<()> {
+    let Some(version) = section.get_mut("version") else {
+        section.insert("version", value(new_version));
+        return Ok(());
+    };
+    match version.as_value_mut() {
+        Some(current) if current.is_str() => {
+            let decor = current.decor().clone();
+            *current = new_version.into();
+            *current.decor_mut() = decor;
+            Ok(())
+        }
+        _ => anyhow::bail!(
+            "Expected `version` in {} of {} to be a string, found {}",
+            section_name,
+            manifest_path.display(),
+            version.type_name()
+        ),
+    }
+}
+
+/// Give `updated` the same line endings as `original`.
+///
+/// Uses CRLF throughout if the first line of `original` ends with CRLF, and
+/// LF otherwise. A trailing newline is kept or dropped to match `original`,
+/// so bumping a version never shows up as a whitespace-only diff.
+pub(crate) fn preserve_line_endings(original: &str, updated: &str) -> String {
+    let crlf = original
+        .find('\n')
+        .is_some_and(|index| original[..index].ends_with('\r'));
+    let trailing_newline = original.ends_with('\n');
+
+    let mut normalized = updated.replace("\r\n", "\n");
+    if trailing_newline {
+        if !normalized.ends_with('\n') {
+            normalized.push('\n');
+        }
+    } else {
+        while normalized.ends_with('\n') {
+            normalized.pop();
+        }
+    }
+
+    if crlf {
+        normalized.replace('\n', "\r\n")
+    } else {
+        normalized
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::TempDir;
@@ -206,6 +415,78 @@ version = "1.0.0"
         assert!(content.contains("version = \"2.0.0\""));
     }
 
+    const PACKAGE_AND_WORKSPACE: &str = r#"[workspace.package]
+version = "1.0.0"
+
+[package]
+name = "test"
+version = "1.0.0"
+"#;
+
+    #[test]
+    fn test_update_location_auto_prefers_package() {
+        let (_dir, manifest_path) = create_temp_manifest(PACKAGE_AND_WORKSPACE);
+
+        update_cargo_toml_version(&manifest_path, "1.0.0", "1.1.0").unwrap();
+
+        let doc = std::fs::read_to_string(&manifest_path)
+            .unwrap()
+            .parse::<DocumentMut>()
+            .unwrap();
+        assert_eq!(doc["package"]["version"].as_str(), Some("1.1.0"));
+        assert_eq!(
+            doc["workspace"]["package"]["version"].as_str(),
+            Some("1.0.0")
+        );
+    }
+
+    #[test]
+    fn test_update_location_workspace() {
+        let (_dir, manifest_path) = create_temp_manifest(PACKAGE_AND_WORKSPACE);
+
+        update_cargo_toml_version_at(&manifest_path, "1.0.0", "1.1.0", VersionLocation::Workspace)
+            .unwrap();
+
+        let doc = std::fs::read_to_string(&manifest_path)
+            .unwrap()
+            .parse::<DocumentMut>()
+            .unwrap();
+        assert_eq!(doc["package"]["version"].as_str(), Some("1.0.0"));
+        assert_eq!(
+            doc["workspace"]["package"]["version"].as_str(),
+            Some("1.1.0")
+        );
+    }
+
+    #[test]
+    fn test_update_location_both() {
+        let (_dir, manifest_path) = create_temp_manifest(PACKAGE_AND_WORKSPACE);
+
+        update_cargo_toml_version_at(&manifest_path, "1.0.0", "1.1.0", VersionLocation::Both)
+            .unwrap();
+
+        let content = std::fs::read_to_string(&manifest_path).unwrap();
+        assert_eq!(content.matches("version = \"1.1.0\"").count(), 2);
+        assert!(!content.contains("1.0.0"));
+    }
+
+    #[test]
+    fn test_update_location_package_missing() {
+        let (_dir, manifest_path) = create_temp_manifest(
+            r#"[workspace.package]
+version = "1.0.0"
+"#,
+        );
+
+        let result = update_cargo_toml_version_at(
+            &manifest_path,
+            "1.0.0",
+            "1.1.0",
+            VersionLocation::Package,
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_preserves_formatting() {
         let (_dir, manifest_path) = create_temp_manifest(
@@ -244,4 +525,95 @@ some-crate = "1.0"
                 .contains("Could not find [package]")
         );
     }
+
+    #[test]
+    fn test_preserves_version_comment() {
+        let (_dir, manifest_path) = create_temp_manifest(
+            "[package]\nname = \"test\"\nversion = \"0.1.0\" # bumped by CI\n",
+        );
+
+        update_cargo_toml_version(&manifest_path, "0.1.0", "0.2.0").unwrap();
+
+        let content = std::fs::read_to_string(&manifest_path).unwrap();
+        assert!(
+            content.contains("version = \"0.2.0\" # bumped by CI\n"),
+            "{}",
+            content
+        );
+    }
+
+    #[test]
+    fn test_inherited_version_error() {
+        let (_dir, manifest_path) =
+            create_temp_manifest("[package]\nname = \"member\"\nversion.workspace = true\n");
+
+        let error = update_cargo_toml_version(&manifest_path, "0.1.0", "0.2.0")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("inherited from the workspace"), "{}", error);
+        // The inheritance marker is left alone
+        let content = std::fs::read_to_string(&manifest_path).unwrap();
+        assert!(content.contains("version.workspace = true"));
+    }
+
+    #[test]
+    fn test_inherited_version_falls_back_to_workspace() {
+        let (_dir, manifest_path) = create_temp_manifest(
+            r#"[workspace.package]
+version = "1.0.0"
+
+[package]
+name = "root"
+version = { workspace = true }
+"#,
+        );
+
+        update_cargo_toml_version(&manifest_path, "1.0.0", "1.1.0").unwrap();
+
+        let content = std::fs::read_to_string(&manifest_path).unwrap();
+        assert!(content.contains("version = \"1.1.0\""));
+        assert!(content.contains("version = { workspace = true }"));
+    }
+
+    #[test]
+    fn test_unexpected_version_type_error() {
+        let (_dir, manifest_path) =
+            create_temp_manifest("[package]\nname = \"test\"\nversion = 1\n");
+
+        let error = update_cargo_toml_version(&manifest_path, "0.1.0", "0.2.0")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Expected `version` in [package]"),
+            "{}",
+            error
+        );
+        assert!(error.contains("found integer"), "{}", error);
+    }
+
+    #[test]
+    fn test_preserves_crlf_line_endings() {
+        let (_dir, manifest_path) = create_temp_manifest(
+            "[package]\r\nname = \"test\"\r\nversion = \"0.1.0\"\r\nedition = \"2021\"\r\n",
+        );
+
+        update_cargo_toml_version(&manifest_path, "0.1.0", "0.2.0").unwrap();
+
+        let content = std::fs::read_to_string(&manifest_path).unwrap();
+        assert_eq!(
+            content,
+            "[package]\r\nname = \"test\"\r\nversion = \"0.2.0\"\r\nedition = \"2021\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_preserves_missing_trailing_newline() {
+        let (_dir, manifest_path) =
+            create_temp_manifest("[package]\nname = \"test\"\nversion = \"0.1.0\"");
+
+        update_cargo_toml_version(&manifest_path, "0.1.0", "0.2.0").unwrap();
+
+        let content = std::fs::read_to_string(&manifest_path).unwrap();
+        assert_eq!(content, "[package]\nname = \"test\"\nversion = \"0.2.0\"");
+    }
 }
@@ -0,0 +1,144 @@
+//! Inspect and clear cached results command.
+//!
+//! Badges and GitHub release lookups cache their results in the cargo target
+//! directory as `.cargo-version-info-<name>-<workspace-id>-cache.json` files,
+//! keyed on the workspace root so checkouts sharing a target directory stay
+//! isolated. This command prints where they live and removes the current
+//! workspace's cache files; the rest of the target directory is left alone.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Print the cache directory
+//! cargo version-info cache path
+//!
+//! # Remove all cache files (asks for confirmation)
+//! cargo version-info cache clear
+//!
+//! # Remove all cache files without asking
+//! cargo version-info cache clear --yes
+//! ```
+
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use anyhow::{
+    Context,
+    Result,
+};
+use clap::{
+    Parser,
+    Subcommand,
+};
+
+use super::common::{
+    cache_dir,
+    cache_files,
+    confirm,
+};
+
+/// Arguments for the `cache` command.
+#[derive(Parser, Debug)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub subcommand: CacheSubcommand,
+}
+
+/// Subcommands for the cache command.
+#[derive(Subcommand, Debug)]
+pub enum CacheSubcommand {
+    /// Remove all cache files.
+    Clear {
+        /// Don't ask for confirmation.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Print the directory holding the cache files.
+    Path,
+}
+
+/// Run a cache subcommand.
+///
+/// # Errors
+///
+/// Returns an error if the cache directory cannot be determined, a cache
+/// file cannot be removed, or `clear` needs confirmation but stdin is not a
+/// terminal (pass `--yes`).
+pub fn cache(args: CacheArgs) -> Result<()> {
+    match args.subcommand {
+        CacheSubcommand::Path => {
+            println!("{}", cache_dir()?.display());
+            Ok(())
+        }
+        CacheSubcommand::Clear { yes } => clear(yes),
+    }
+}
+
+/// Remove every cache file, asking first unless `yes` is set.
+fn clear(yes: bool) -> Result<()> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+
+    let files = cache_files()?;
+    if files.is_empty() {
+        logger.print_message(&format!("No cache files in {}", cache_dir()?.display()));
+        return Ok(());
+    }
+
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!(
+                "Refusing to remove {} cache file(s) without confirmation. Pass --yes to \
+                 skip the prompt.",
+                files.len()
+            );
+        }
+        let mut stderr = std::io::stderr();
+        for file in &files {
+            eprintln!("  {}", file.display());
+        }
+        let prompt = format!("Remove {} cache file(s)? [y/N] ", files.len());
+        if !confirm(&mut std::io::stdin().lock(), &mut stderr, &prompt)? {
+            anyhow::bail!("Cache clear cancelled");
+        }
+    }
+
+    remove_files(&files)?;
+    logger.print_message(&format!("✓ Removed {} cache file(s)", files.len()));
+
+    Ok(())
+}
+
+/// Remove the given files, ignoring ones that are already gone.
+fn remove_files(files: &[PathBuf]) -> Result<()> {
+    for file in files {
+        match std::fs::remove_file(file) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to remove {}", file.display()));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join(".cargo-version-info-coverage-cache.json");
+        let other = dir.path().join("keep.txt");
+        std::fs::write(&cache, "{}").unwrap();
+        std::fs::write(&other, "keep").unwrap();
+
+        // A file removed concurrently is not an error
+        let missing = dir.path().join(".cargo-version-info-gone-cache.json");
+        remove_files(&[cache.clone(), missing]).unwrap();
+
+        assert!(!cache.exists());
+        assert!(other.exists());
+    }
+}
@@ -27,6 +27,10 @@ use anyhow::{
 };
 use cargo_plugin_utils::common::get_package_version_from_manifest;
 use clap::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::common::write_github_outputs;
 
 /// Arguments for the `changed` command.
 #[derive(Parser, Debug)]
@@ -62,6 +66,17 @@ pub struct ChangedArgs {
     github_output: Option<String>,
 }
 
+/// Change check result, printed with `--format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ChangedOutput<'a> {
+    /// Whether the Cargo.toml version differs from the latest tag.
+    changed: bool,
+    /// The version in Cargo.toml.
+    cargo_version: &'a str,
+    /// The latest tag's version, without a leading `v`.
+    latest_tag_version: &'a str,
+}
+
 /// Check if the Cargo.toml version has changed since the last git tag.
 ///
 /// Extracts the version from Cargo.toml (checking `[workspace.package]` first,
@@ -188,10 +203,17 @@ pub fn changed(args: ChangedArgs) -> Result<()> {
 
     match args.format.as_str() {
         "bool" => println!("{}", changed),
-        "json" => println!(
-            "{{\"changed\":{},\"cargo_version\":\"{}\",\"latest_tag_version\":\"{}\"}}",
-            changed, cargo_version, latest_tag_version
-        ),
+        "json" => {
+            let output = ChangedOutput {
+                changed,
+                cargo_version: &cargo_version,
+                latest_tag_version: &latest_tag_version,
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&output).context("Failed to serialize change check")?
+            );
+        }
         "diff" => {
             if changed {
                 println!(
@@ -203,13 +225,14 @@ pub fn changed(args: ChangedArgs) -> Result<()> {
             }
         }
         "github-actions" => {
-            let output_file = args.github_output.as_deref().unwrap_or("/dev/stdout");
-            let output = format!(
-                "changed={}\nversion={}\nlatest_tag_version={}\n",
-                changed, cargo_version, latest_tag_version
-            );
-            std::fs::write(output_file, output)
-                .with_context(|| format!("Failed to write to {}", output_file))?;
+            write_github_outputs(
+                args.github_output.as_deref(),
+                &[
+                    ("changed", changed.to_string().as_str()),
+                    ("version", cargo_version.as_str()),
+                    ("latest_tag_version", latest_tag_version.as_str()),
+                ],
+            )?;
         }
         _ => anyhow::bail!("Invalid format: {}", args.format),
     }
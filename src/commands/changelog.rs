@@ -23,9 +23,31 @@
 //!
 //! # Combined: version in header + output to file
 //! cargo version-info changelog --for-version v0.1.0 --output CHANGELOG.md
+//!
+//! # Use in GitHub Actions (multi-line `changelog` output)
+//! cargo version-info changelog --format github-actions
+//!
+//! # Count feat/fix/breaking/other commits since the latest tag
+//! cargo version-info changelog --count --format json
+//!
+//! # Leave out merge commits and bot commits
+//! cargo version-info changelog --exclude-merges --exclude-author 'dependabot|renovate'
+//!
+//! # Only commits scoped to `api` or `cli` (e.g. `feat(api): ...`)
+//! cargo version-info changelog --scope api --scope cli
+//!
+//! # A section per scope instead of per commit type
+//! cargo version-info changelog --group-by scope
+//!
+//! # Only the 50 newest entries, followed by "…and more"
+//! cargo version-info changelog --max-items 50
+//!
+//! # Entries ordered alphabetically instead of newest first
+//! cargo version-info changelog --sort type
 //! ```
 
 use std::collections::HashMap;
+use std::io::Write;
 
 use anyhow::{
     Context,
@@ -35,10 +57,13 @@ use bstr::{
     BString,
     ByteSlice,
 };
-use cargo_plugin_utils::common::get_owner_repo;
 use clap::Parser;
 use regex::Regex;
+use schemars::JsonSchema;
+use serde::Serialize;
 
+use super::common::write_github_outputs;
+use crate::github;
 use crate::version::parse_version;
 
 /// Arguments for the `changelog` command.
@@ -72,6 +97,77 @@ pub struct ChangelogArgs {
     /// GitHub repository name (for linking commits/PRs).
     #[arg(long)]
     pub repo: Option<String>,
+
+    /// Output format for the changelog.
+    ///
+    /// - `markdown`: Markdown document (default)
+    /// - `github-actions`: Write the changelog as a multi-line `changelog`
+    ///   output to the GITHUB_OUTPUT file
+    /// - `json`: Commit counts as JSON (only with `--count`)
+    #[arg(long, default_value = "markdown")]
+    pub format: String,
+
+    /// Print how many feat, fix, breaking and other commits are in the range
+    /// instead of the changelog.
+    ///
+    /// Written as `category: N` lines, as JSON with `--format json`, or as
+    /// `feat`/`fix`/`breaking`/`other` outputs with `--format github-actions`.
+    #[arg(long)]
+    pub count: bool,
+
+    /// Leave out merge commits (commits with more than one parent).
+    #[arg(long)]
+    pub exclude_merges: bool,
+
+    /// Leave out commits whose author matches this regex.
+    ///
+    /// Matched against `Name <email>`, e.g. `dependabot|renovate`.
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude_author: Option<String>,
+
+    /// Only include commits with this scope (e.g. `api` for `feat(api): ...`).
+    ///
+    /// Can be given multiple times to include several scopes. Commits without
+    /// a scope are left out when any scope is given.
+    #[arg(long)]
+    pub scope: Vec<String>,
+
+    /// How entries are grouped.
+    ///
+    /// - `type`: A section per commit type, with scope sub-sections (default)
+    /// - `scope`: A section per scope, with commit type sub-sections; commits
+    ///   without a scope go under "General"
+    #[arg(long, default_value = "type")]
+    pub group_by: String,
+
+    /// List at most this many entries (the first ones in `--sort` order),
+    /// followed by an "…and more" line if there are others.
+    ///
+    /// Unlimited by default. With the default `--sort date`, history is only
+    /// read up to the first entry left out; other orders read the whole range
+    /// and say how many entries were left out. Does not affect `--count`.
+    #[arg(long, value_name = "N")]
+    pub max_items: Option<usize>,
+
+    /// Order of the entries within each section.
+    ///
+    /// - `date`: Newest first, by the commit author date as recorded by git
+    ///   (default)
+    /// - `type`: Alphabetically by commit type, then scope, then description
+    /// - `scope`: Alphabetically by scope, then commit type, then description
+    ///
+    /// Sections keep their usual order. As each section holds a single type
+    /// (and scope, with `--group-by type`), `type` and `scope` mostly order
+    /// entries by description.
+    #[arg(long, default_value = "date")]
+    pub sort: String,
+
+    /// Path to GitHub Actions output file.
+    ///
+    /// Only used when `--format github-actions` is specified.
+    /// Defaults to the `GITHUB_OUTPUT` environment variable or stdout.
+    #[arg(long, env = "GITHUB_OUTPUT")]
+    pub github_output: Option<String>,
 }
 
 /// Commit information parsed from git log.
@@ -79,6 +175,15 @@ pub struct ChangelogArgs {
 struct Commit {
     sha: String,
     short_sha: String,
+    /// Whether the message follows the conventional commit format. Other
+    /// commits have an empty `commit_type`.
+    conventional: bool,
+    /// Author as `Name <email>`.
+    author: String,
+    /// Whether the commit has more than one parent.
+    merge: bool,
+    /// Author date in seconds since the Unix epoch.
+    date: i64,
     commit_type: String,
     scope: Option<String>,
     breaking: bool,
@@ -118,6 +223,10 @@ fn parse_conventional_commit(message: &str) -> Option<Commit> {
     Some(Commit {
         sha: String::new(),       // Will be filled in later
         short_sha: String::new(), // Will be filled in later
+        conventional: true,
+        author: String::new(), // Will be filled in later
+        merge: false,          // Will be filled in later
+        date: 0,               // Will be filled in later
         commit_type,
         scope,
         breaking,
@@ -175,7 +284,7 @@ fn format_commit_entry(commit: &Commit, owner: &str, repo: &str) -> String {
 }
 
 /// Resolve a reference to a commit OID, following tags iteratively.
-fn resolve_to_commit_oid<'a>(
+pub(crate) fn resolve_to_commit_oid<'a>(
     git_repo: &'a gix::Repository,
     reference: &str,
 ) -> Result<gix::Id<'a>> {
@@ -237,18 +346,60 @@ fn resolve_to_commit_oid<'a>(
     anyhow::bail!("Reference '{}' does not point to a commit", reference);
 }
 
-/// Generate changelog to a writer.
-pub fn generate_changelog_to_writer(
-    writer: &mut dyn std::io::Write,
-    args: ChangelogArgs,
-) -> Result<()> {
-    let (owner, repo) = get_owner_repo(args.owner.clone(), args.repo.clone())?;
+/// Find the commit of the latest version tag.
+///
+/// Tags are parsed as semantic versions (with an optional `v`/`V` prefix)
+/// and the highest version wins. Returns `None` if there are no version tags.
+pub(crate) fn latest_version_tag(git_repo: &gix::Repository) -> Result<Option<gix::Id<'_>>> {
+    // Find the latest version tag by collecting all version tags, parsing them,
+    // sorting by version, and taking the latest one
+    let mut version_tags: Vec<(gix::Id, String, (u32, u32, u32))> = Vec::new();
+
+    let refs = git_repo
+        .references()
+        .context("Failed to read git references")?;
+    for reference_result in refs.all()? {
+        let Ok(reference) = reference_result else {
+            continue;
+        };
+        let name_str = reference.name().as_bstr().to_string();
+        let Some(name) = name_str.strip_prefix("refs/tags/") else {
+            continue;
+        };
 
-    // Discover git repository
-    let git_repo = gix::discover(".").context("Failed to discover git repository")?;
+        // Try to parse as semantic version
+        let version_str = name
+            .strip_prefix('v')
+            .or_else(|| name.strip_prefix('V'))
+            .unwrap_or(name);
+        let Ok((major, minor, patch)) = parse_version(version_str) else {
+            continue;
+        };
+
+        // Resolve tag to commit OID (follows tags recursively)
+        let Ok(commit_oid) = resolve_to_commit_oid(git_repo, name) else {
+            continue;
+        };
+        version_tags.push((commit_oid, name.to_string(), (major, minor, patch)));
+    }
+
+    // Sort tags by semantic version (major, minor, patch)
+    version_tags.sort_by(|a, b| a.2.cmp(&b.2));
 
+    // Get the latest tag's commit OID (if any)
+    Ok(version_tags.last().map(|(oid, _tag_name, _version)| *oid))
+}
+
+/// Resolve the commit range selected by `--range`, `--at`, or the latest
+/// version tag (the default).
+///
+/// Returns the exclusive start commit (if any) and the end commit.
+fn resolve_range<'a>(
+    git_repo: &'a gix::Repository,
+    args: &ChangelogArgs,
+) -> Result<(Option<gix::Id<'a>>, gix::Id<'a>)> {
     // Determine start commit for range
-    let (start_oid, end_oid) = if let Some(range) = &args.range {
+    if let Some(range) = &args.range {
         // Parse range like "v0.1.0..v0.2.0" or "v0.1.0..HEAD"
         let parts: Vec<&str> = range.split("..").collect();
         if parts.len() != 2 {
@@ -259,7 +410,7 @@ pub fn generate_changelog_to_writer(
 
         // Resolve references using rev_parse, following tags to commits
         // If start reference doesn't exist, treat it as if there's no start point
-        let start_oid = match resolve_to_commit_oid(&git_repo, start_ref) {
+        let start_oid = match resolve_to_commit_oid(git_repo, start_ref) {
             Ok(oid) => Some(oid),
             Err(_) => {
                 eprintln!(
@@ -271,67 +422,45 @@ pub fn generate_changelog_to_writer(
             }
         };
 
-        let end_oid = resolve_to_commit_oid(&git_repo, end_ref)
+        let end_oid = resolve_to_commit_oid(git_repo, end_ref)
             .with_context(|| format!("Failed to resolve end reference: {}", end_ref))?;
 
-        (start_oid, end_oid)
+        Ok((start_oid, end_oid))
     } else if let Some(tag) = &args.at {
         // Generate changelog for commits up to this tag
-        let tag_oid = resolve_to_commit_oid(&git_repo, tag)
+        let tag_oid = resolve_to_commit_oid(git_repo, tag)
             .with_context(|| format!("Failed to resolve tag: {}", tag))?;
 
         // Get HEAD for end
         let head = git_repo.head().context("Failed to read HEAD")?;
         let head_oid = head.id().context("HEAD does not point to a commit")?;
 
-        (Some(tag_oid), head_oid)
+        Ok((Some(tag_oid), head_oid))
     } else {
         // Default: since last version tag
-        // Find the latest version tag by collecting all version tags, parsing them,
-        // sorting by version, and taking the latest one
-        let mut version_tags: Vec<(gix::Id, String, (u32, u32, u32))> = Vec::new();
-
-        let refs = git_repo
-            .references()
-            .context("Failed to read git references")?;
-        for reference_result in refs.all()? {
-            let Ok(reference) = reference_result else {
-                continue;
-            };
-            let name_str = reference.name().as_bstr().to_string();
-            let Some(name) = name_str.strip_prefix("refs/tags/") else {
-                continue;
-            };
-
-            // Try to parse as semantic version
-            let version_str = name
-                .strip_prefix('v')
-                .or_else(|| name.strip_prefix('V'))
-                .unwrap_or(name);
-            let Ok((major, minor, patch)) = parse_version(version_str) else {
-                continue;
-            };
-
-            // Resolve tag to commit OID (follows tags recursively)
-            let Ok(commit_oid) = resolve_to_commit_oid(&git_repo, name) else {
-                continue;
-            };
-            version_tags.push((commit_oid, name.to_string(), (major, minor, patch)));
-        }
-
-        // Sort tags by semantic version (major, minor, patch)
-        version_tags.sort_by(|a, b| a.2.cmp(&b.2));
-
-        // Get the latest tag's commit OID (if any)
-        let latest_tag_oid = version_tags.last().map(|(oid, _tag_name, _version)| *oid);
+        let latest_tag_oid = latest_version_tag(git_repo)?;
 
         // Get HEAD for end
         let head = git_repo.head().context("Failed to read HEAD")?;
         let head_oid = head.id().context("HEAD does not point to a commit")?;
 
-        (latest_tag_oid, head_oid)
-    };
+        Ok((latest_tag_oid, head_oid))
+    }
+}
 
+/// Collect the commits in `start..end` that `keep` accepts, newest first.
+///
+/// Commits whose message is not a conventional commit have `conventional`
+/// unset. With a `limit`, the walk stops once one commit more than that has
+/// been kept, so callers can tell that the range goes on without reading all
+/// of it.
+fn collect_commits(
+    git_repo: &gix::Repository,
+    start_oid: Option<gix::Id<'_>>,
+    end_oid: gix::Id<'_>,
+    keep: impl Fn(&Commit) -> bool,
+    limit: Option<usize>,
+) -> Result<Vec<Commit>> {
     // Walk commits using gix rev_walk
     let walk = git_repo.rev_walk([end_oid]);
     let walk_iter = walk.all()?;
@@ -368,27 +497,136 @@ pub fn generate_changelog_to_writer(
         let message_str = String::from_utf8_lossy(message_raw.as_ref()).into_owned();
 
         // Parse conventional commit format
-        if let Some(mut parsed) = parse_conventional_commit(&message_str) {
-            // Only include commits that should be in changelog
-            if include_in_changelog(&parsed.commit_type) {
-                let short_sha = oid.shorten().context("Failed to shorten commit SHA")?;
-                parsed.sha = oid.to_string();
-                parsed.short_sha = short_sha.to_string();
-
-                // Extract body from message (everything after first line)
-                let body_lines: Vec<&str> = message_str.lines().skip(1).collect();
-                let body_text: String = body_lines.join("\n").trim().to_string();
-                parsed.body = if body_text.is_empty() {
-                    None
-                } else {
-                    Some(body_text)
-                };
-
-                commits.push(parsed);
+        let mut parsed = parse_conventional_commit(&message_str).unwrap_or_else(|| Commit {
+            sha: String::new(),
+            short_sha: String::new(),
+            conventional: false,
+            author: String::new(),
+            merge: false,
+            date: 0,
+            commit_type: String::new(),
+            scope: None,
+            breaking: false,
+            subject: message_str.lines().next().unwrap_or_default().to_string(),
+            body: None,
+        });
+        let short_sha = oid.shorten().context("Failed to shorten commit SHA")?;
+        parsed.sha = oid.to_string();
+        parsed.short_sha = short_sha.to_string();
+
+        let author = commit.author().context("Failed to read commit author")?;
+        parsed.author = format!("{} <{}>", author.name, author.email);
+        parsed.merge = commit.parent_ids().count() > 1;
+        parsed.date = author.time().map(|time| time.seconds).unwrap_or(0);
+
+        // Extract body from message (everything after first line)
+        let body_lines: Vec<&str> = message_str.lines().skip(1).collect();
+        let body_text: String = body_lines.join("\n").trim().to_string();
+        parsed.body = if body_text.is_empty() {
+            None
+        } else {
+            Some(body_text)
+        };
+
+        if keep(&parsed) {
+            commits.push(parsed);
+            if limit.is_some_and(|limit| commits.len() > limit) {
+                break;
+            }
+        }
+    }
+
+    Ok(commits)
+}
+
+/// Filter out the commits excluded by `--exclude-merges` and
+/// `--exclude-author`, and those outside the `--scope`s (if any).
+fn commit_filter(args: &ChangelogArgs) -> Result<impl Fn(&Commit) -> bool + '_> {
+    let author_re = args
+        .exclude_author
+        .as_deref()
+        .map(|pattern| {
+            Regex::new(pattern)
+                .with_context(|| format!("Invalid --exclude-author pattern: {}", pattern))
+        })
+        .transpose()?;
+
+    Ok(move |commit: &Commit| {
+        !(args.exclude_merges && commit.merge)
+            && author_re
+                .as_ref()
+                .is_none_or(|re| !re.is_match(&commit.author))
+            && (args.scope.is_empty()
+                || commit
+                    .scope
+                    .as_ref()
+                    .is_some_and(|scope| args.scope.contains(scope)))
+    })
+}
+
+/// Number of commits per category, printed by `--count`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct CommitCounts {
+    /// `feat` commits.
+    pub feat: usize,
+    /// `fix` commits.
+    pub fix: usize,
+    /// Breaking changes (`!` after the type), also counted under their type.
+    pub breaking: usize,
+    /// All other commits, including non-conventional ones.
+    pub other: usize,
+}
+
+impl CommitCounts {
+    fn of(commits: &[Commit]) -> Self {
+        let mut counts = Self::default();
+        for commit in commits {
+            match commit.commit_type.as_str() {
+                "feat" => counts.feat += 1,
+                "fix" => counts.fix += 1,
+                _ => counts.other += 1,
+            }
+            if commit.breaking {
+                counts.breaking += 1;
             }
         }
+        counts
     }
+}
+
+/// Count the commits in the changelog range by category.
+///
+/// # Errors
+///
+/// Returns an error if the git repository or the range cannot be read.
+pub fn changelog_counts(args: &ChangelogArgs) -> Result<CommitCounts> {
+    let git_repo = gix::discover(".").context("Failed to discover git repository")?;
+    let (start_oid, end_oid) = resolve_range(&git_repo, args)?;
+    let commits = collect_commits(&git_repo, start_oid, end_oid, commit_filter(args)?, None)?;
+    Ok(CommitCounts::of(&commits))
+}
+
+/// Write commit counts as `category: N` lines or, with `json`, as JSON.
+fn write_counts(writer: &mut dyn Write, counts: &CommitCounts, json: bool) -> Result<()> {
+    if json {
+        let json = serde_json::to_string(counts).context("Failed to serialize commit counts")?;
+        writeln!(writer, "{}", json)?;
+    } else {
+        writeln!(writer, "feat: {}", counts.feat)?;
+        writeln!(writer, "fix: {}", counts.fix)?;
+        writeln!(writer, "breaking: {}", counts.breaking)?;
+        writeln!(writer, "other: {}", counts.other)?;
+    }
+    Ok(())
+}
+
+/// Commit types listed in the changelog, in section order.
+const TYPE_ORDER: [&str; 11] = [
+    "feat", "fix", "perf", "refactor", "docs", "revert", "build", "ci", "test", "style", "chore",
+];
 
+/// Write a section per commit type, with a sub-section per scope.
+fn write_by_type(output: &mut String, commits: Vec<Commit>, owner: &str, repo: &str) {
     // Group commits by type, then by scope
     let mut by_type: HashMap<String, HashMap<Option<String>, Vec<Commit>>> = HashMap::new();
 
@@ -401,31 +639,7 @@ pub fn generate_changelog_to_writer(
             .push(commit);
     }
 
-    // Generate markdown
-    let mut output = String::new();
-
-    // Header - prioritize for_version, then at, then generic
-    if let Some(version) = &args.for_version {
-        // Normalize version to have v prefix for display
-        let version_display = if version.starts_with('v') || version.starts_with('V') {
-            version.clone()
-        } else {
-            format!("v{}", version)
-        };
-        output.push_str(&format!("# Changelog - {}\n\n", version_display));
-    } else if let Some(tag) = &args.at {
-        output.push_str(&format!("# Changelog - {}\n\n", tag));
-    } else {
-        output.push_str("# Changelog\n\n");
-    }
-
-    // Order commit types
-    let type_order = [
-        "feat", "fix", "perf", "refactor", "docs", "revert", "build", "ci", "test", "style",
-        "chore",
-    ];
-
-    for commit_type in type_order {
+    for commit_type in TYPE_ORDER {
         if let Some(by_scope) = by_type.get(commit_type) {
             output.push_str(&format!("## {}\n\n", commit_type_title(commit_type)));
 
@@ -443,17 +657,160 @@ pub fn generate_changelog_to_writer(
 
                 // List commits
                 for commit in scope_commits {
-                    output.push_str(&format_commit_entry(commit, &owner, &repo));
+                    output.push_str(&format_commit_entry(commit, owner, repo));
+                }
+
+                output.push('\n');
+            }
+        }
+    }
+}
+
+/// Write a section per scope, with a sub-section per commit type.
+///
+/// Commits without a scope come first, under "General".
+fn write_by_scope(output: &mut String, commits: Vec<Commit>, owner: &str, repo: &str) {
+    // Group commits by scope, then by type
+    let mut by_scope: HashMap<Option<String>, HashMap<String, Vec<Commit>>> = HashMap::new();
+
+    for commit in commits {
+        by_scope
+            .entry(commit.scope.clone())
+            .or_default()
+            .entry(commit.commit_type.clone())
+            .or_default()
+            .push(commit);
+    }
+
+    let mut scopes: Vec<_> = by_scope.keys().collect();
+    scopes.sort(); // None (no scope) will come first
+
+    for scope in scopes {
+        let by_type = &by_scope[scope];
+        output.push_str(&format!("## {}\n\n", scope.as_deref().unwrap_or("General")));
+
+        for commit_type in TYPE_ORDER {
+            if let Some(type_commits) = by_type.get(commit_type) {
+                output.push_str(&format!("### {}\n\n", commit_type_title(commit_type)));
+
+                for commit in type_commits {
+                    output.push_str(&format_commit_entry(commit, owner, repo));
                 }
 
                 output.push('\n');
             }
         }
     }
+}
+
+/// Order commits for `--sort`: `date`, `type` or `scope`.
+///
+/// Grouping keeps this order within each section. Ties (and `date`) are
+/// broken newest first.
+fn sort_commits(commits: &mut [Commit], sort: &str) -> Result<()> {
+    // Stable sorts: later keys only reorder commits that compare equal
+    commits.sort_by(|a, b| b.date.cmp(&a.date));
+    match sort {
+        "date" => {}
+        "type" => commits.sort_by(|a, b| {
+            (&a.commit_type, &a.scope, a.subject.to_lowercase()).cmp(&(
+                &b.commit_type,
+                &b.scope,
+                b.subject.to_lowercase(),
+            ))
+        }),
+        "scope" => commits.sort_by(|a, b| {
+            (&a.scope, &a.commit_type, a.subject.to_lowercase()).cmp(&(
+                &b.scope,
+                &b.commit_type,
+                b.subject.to_lowercase(),
+            ))
+        }),
+        other => anyhow::bail!("Invalid sort: {} (expected date, type or scope)", other),
+    }
+    Ok(())
+}
+
+/// Keep at most `max_items` of `items` (the first ones), returning how many
+/// were dropped.
+pub(crate) fn truncate_items<T>(items: &mut Vec<T>, max_items: Option<usize>) -> usize {
+    match max_items {
+        Some(max_items) if items.len() > max_items => {
+            let omitted = items.len() - max_items;
+            items.truncate(max_items);
+            omitted
+        }
+        _ => 0,
+    }
+}
+
+/// Generate changelog to a writer.
+///
+/// With `--count`, only the commit counts are written (see
+/// [`changelog_counts`]).
+pub fn generate_changelog_to_writer(
+    writer: &mut dyn std::io::Write,
+    args: ChangelogArgs,
+) -> Result<()> {
+    if args.count {
+        let counts = changelog_counts(&args)?;
+        return write_counts(writer, &counts, args.format == "json");
+    }
+
+    let (owner, repo) = github::resolve_owner_repo(args.owner.clone(), args.repo.clone())?;
+
+    // Discover git repository
+    let git_repo = gix::discover(".").context("Failed to discover git repository")?;
+    let (start_oid, end_oid) = resolve_range(&git_repo, &args)?;
+
+    // Only some commit types are listed in the changelog. --max-items keeps the
+    // first entries in --sort order; only newest first can the walk stop early
+    // (leaving how many entries were left out unknown), as any other order
+    // needs every commit in the range
+    let keep = commit_filter(&args)?;
+    let walk_limit = args.max_items.filter(|_| args.sort == "date");
+    let mut commits = collect_commits(
+        &git_repo,
+        start_oid,
+        end_oid,
+        |commit| commit.conventional && include_in_changelog(&commit.commit_type) && keep(commit),
+        walk_limit,
+    )?;
+    sort_commits(&mut commits, &args.sort)?;
+    let omitted = truncate_items(&mut commits, args.max_items);
+
+    // Generate markdown
+    let mut output = String::new();
+
+    // Header - prioritize for_version, then at, then generic
+    if let Some(version) = &args.for_version {
+        // Normalize version to have v prefix for display
+        let version_display = if version.starts_with('v') || version.starts_with('V') {
+            version.clone()
+        } else {
+            format!("v{}", version)
+        };
+        output.push_str(&format!("# Changelog - {}\n\n", version_display));
+    } else if let Some(tag) = &args.at {
+        output.push_str(&format!("# Changelog - {}\n\n", tag));
+    } else {
+        output.push_str("# Changelog\n\n");
+    }
+
+    match args.group_by.as_str() {
+        "type" => write_by_type(&mut output, commits, &owner, &repo),
+        "scope" => write_by_scope(&mut output, commits, &owner, &repo),
+        other => anyhow::bail!("Invalid group-by: {} (expected type or scope)", other),
+    }
 
     if output.trim().ends_with("# Changelog\n\n") {
         output.push_str("No changes found.\n");
     }
+    if omitted > 0 && walk_limit.is_some() {
+        output.push_str("…and more\n");
+    } else if omitted > 0 {
+        output.push_str(&format!("…and {} more\n", omitted));
+    }
 
     // Write to the provided writer
     write!(writer, "{}", output)?;
@@ -462,18 +819,58 @@ pub fn generate_changelog_to_writer(
 }
 
 /// Generate changelog from git commits.
+///
+/// The changelog is rendered with [`generate_changelog_to_writer`] and then
+/// written to the GitHub Actions output, the `--output` file, or stdout. The
+/// file is only created once generation has succeeded.
 pub fn changelog(args: ChangelogArgs) -> Result<()> {
-    let output_path = args.output.clone();
+    let github_actions = match args.format.as_str() {
+        "markdown" => false,
+        "json" if args.count => false,
+        "github-actions" => true,
+        "json" => anyhow::bail!("--format json is only supported with --count"),
+        _ => anyhow::bail!("Invalid format: {}", args.format),
+    };
+
+    if args.count && github_actions {
+        let counts = changelog_counts(&args)?;
+        let (feat, fix, breaking, other) = (
+            counts.feat.to_string(),
+            counts.fix.to_string(),
+            counts.breaking.to_string(),
+            counts.other.to_string(),
+        );
+        return write_github_outputs(
+            args.github_output.as_deref(),
+            &[
+                ("feat", feat.as_str()),
+                ("fix", fix.as_str()),
+                ("breaking", breaking.as_str()),
+                ("other", other.as_str()),
+            ],
+        );
+    }
 
-    if let Some(ref path) = output_path {
-        // Write to file
-        let mut file = std::fs::File::create(path)
-            .with_context(|| format!("Failed to create file {}", path))?;
-        generate_changelog_to_writer(&mut file, args)?;
+    let output_path = args.output.clone();
+    let github_output = args.github_output.clone();
+
+    let mut output = Vec::new();
+    generate_changelog_to_writer(&mut output, args)?;
+
+    // Write output to GitHub Actions, a file, or stdout
+    if github_actions {
+        let changelog = String::from_utf8(output).context("Changelog output is not valid UTF-8")?;
+        write_github_outputs(
+            github_output.as_deref(),
+            &[("changelog", changelog.as_str())],
+        )?;
+    } else if let Some(output_path) = output_path {
+        std::fs::write(&output_path, output)
+            .with_context(|| format!("Failed to write changelog to {}", output_path))?;
+        let mut logger = cargo_plugin_utils::logger::Logger::new();
+        logger.status("Written", &output_path);
     } else {
-        // Write to stdout
-        let mut stdout = std::io::stdout();
-        generate_changelog_to_writer(&mut stdout, args)?;
+        std::io::stdout().write_all(&output)?;
     }
 
     Ok(())
@@ -481,12 +878,22 @@ pub fn changelog(args: ChangelogArgs) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
     use std::process::Command;
 
     use tempfile::TempDir;
 
     use super::*;
 
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
     fn create_test_git_repo_with_tags_and_commits(tags: &[&str], commits: &[&str]) -> TempDir {
         let dir = tempfile::tempdir().unwrap();
 
@@ -551,6 +958,28 @@ mod tests {
         dir
     }
 
+    /// Arguments for the `test/repo` repository with every other option at
+    /// its CLI default.
+    fn changelog_args() -> ChangelogArgs {
+        ChangelogArgs {
+            at: None,
+            range: None,
+            for_version: None,
+            output: None,
+            owner: Some("test".to_string()),
+            repo: Some("repo".to_string()),
+            format: "markdown".to_string(),
+            github_output: None,
+            count: false,
+            exclude_merges: false,
+            exclude_author: None,
+            scope: Vec::new(),
+            group_by: "type".to_string(),
+            max_items: None,
+            sort: "date".to_string(),
+        }
+    }
+
     #[test]
     fn test_changelog_finds_latest_tag_not_first() {
         // Test that changelog finds the latest version tag, not just the first one
@@ -568,14 +997,7 @@ mod tests {
         std::env::set_current_dir(&dir_path).unwrap();
 
         // Test changelog with no range - should find latest tag (v0.2.0)
-        let args = ChangelogArgs {
-            at: None,
-            range: None,
-            for_version: None,
-            output: None,
-            owner: Some("test".to_string()),
-            repo: Some("repo".to_string()),
-        };
+        let args = changelog_args();
 
         let mut output = Vec::new();
         let result = generate_changelog_to_writer(&mut output, args);
@@ -597,12 +1019,8 @@ mod tests {
         std::env::set_current_dir(&dir_path).unwrap();
 
         let args = ChangelogArgs {
-            at: None,
-            range: None,
             for_version: Some("v0.2.0".to_string()),
-            output: None,
-            owner: Some("test".to_string()),
-            repo: Some("repo".to_string()),
+            ..changelog_args()
         };
 
         let mut output = Vec::new();
@@ -626,12 +1044,8 @@ mod tests {
         std::env::set_current_dir(&dir_path).unwrap();
 
         let args = ChangelogArgs {
-            at: None,
-            range: None,
             for_version: Some("0.2.0".to_string()), // No v prefix
-            output: None,
-            owner: Some("test".to_string()),
-            repo: Some("repo".to_string()),
+            ..changelog_args()
         };
 
         let mut output = Vec::new();
@@ -658,14 +1072,7 @@ mod tests {
 
         std::env::set_current_dir(&dir_path).unwrap();
 
-        let args = ChangelogArgs {
-            at: None,
-            range: None,
-            for_version: None,
-            output: None,
-            owner: Some("test".to_string()),
-            repo: Some("repo".to_string()),
-        };
+        let args = changelog_args();
 
         let mut output = Vec::new();
         let result = generate_changelog_to_writer(&mut output, args);
@@ -692,12 +1099,8 @@ mod tests {
         std::env::set_current_dir(&dir_path).unwrap();
 
         let args = ChangelogArgs {
-            at: None,
             range: Some("v0.1.0..v0.2.0".to_string()),
-            for_version: None,
-            output: None,
-            owner: Some("test".to_string()),
-            repo: Some("repo".to_string()),
+            ..changelog_args()
         };
 
         let mut output = Vec::new();
@@ -709,4 +1112,265 @@ mod tests {
         }
         assert!(result.is_ok(), "Changelog with explicit range should work");
     }
+
+    #[test]
+    fn test_changelog_writes_output_file() {
+        let dir = create_test_git_repo_with_tags_and_commits(&["v0.1.0"], &[]);
+        let output_file = dir.path().join("CHANGELOG.md");
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(dir.path()).unwrap();
+
+        let args = ChangelogArgs {
+            for_version: Some("0.2.0".to_string()),
+            output: Some(output_file.to_string_lossy().to_string()),
+            ..changelog_args()
+        };
+
+        let result = changelog(args);
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok(), "Changelog generation should succeed");
+        let content = std::fs::read_to_string(&output_file).unwrap();
+        assert!(content.starts_with("# Changelog - v0.2.0"));
+    }
+
+    #[test]
+    fn test_changelog_count() {
+        let dir = create_test_git_repo_with_tags_and_commits(
+            &[],
+            &[
+                "feat(api): add endpoint",
+                "fix: handle empty input",
+                "feat!: drop old flag",
+                "chore: tidy up",
+            ],
+        );
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(dir.path()).unwrap();
+
+        let args = ChangelogArgs {
+            format: "json".to_string(),
+            count: true,
+            ..changelog_args()
+        };
+
+        let mut output = Vec::new();
+        let result = generate_changelog_to_writer(&mut output, args);
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok());
+        // "Initial commit" is not conventional and counts as other
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"feat\":2,\"fix\":1,\"breaking\":1,\"other\":2}\n"
+        );
+    }
+
+    #[test]
+    fn test_changelog_exclude_merges_and_authors() {
+        let dir = create_test_git_repo_with_tags_and_commits(&[], &["feat: add endpoint"]);
+        git(dir.path(), &["checkout", "-b", "deps"]);
+        std::fs::write(dir.path().join("Cargo.lock"), "# lock\n").unwrap();
+        git(dir.path(), &["add", "Cargo.lock"]);
+        git(
+            dir.path(),
+            &[
+                "commit",
+                "-m",
+                "fix(deps): bump serde",
+                "--author",
+                "dependabot[bot] <support@github.com>",
+            ],
+        );
+        git(dir.path(), &["checkout", "-"]);
+        git(
+            dir.path(),
+            &["merge", "--no-ff", "-m", "Merge branch 'deps'", "deps"],
+        );
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(dir.path()).unwrap();
+
+        let args = |count| ChangelogArgs {
+            count,
+            exclude_merges: true,
+            exclude_author: Some("dependabot".to_string()),
+            ..changelog_args()
+        };
+
+        let mut counts = Vec::new();
+        let counts_result = generate_changelog_to_writer(&mut counts, args(true));
+        let mut markdown = Vec::new();
+        let markdown_result = generate_changelog_to_writer(&mut markdown, args(false));
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(counts_result.is_ok());
+        assert!(markdown_result.is_ok());
+        // Only "feat: add endpoint" and "Initial commit" are left
+        assert_eq!(
+            String::from_utf8(counts).unwrap(),
+            "feat: 1\nfix: 0\nbreaking: 0\nother: 1\n"
+        );
+        let markdown = String::from_utf8(markdown).unwrap();
+        assert!(markdown.contains("add endpoint"));
+        assert!(!markdown.contains("bump serde"));
+    }
+
+    #[test]
+    fn test_changelog_scope() {
+        let dir = create_test_git_repo_with_tags_and_commits(
+            &[],
+            &[
+                "feat(api): add endpoint",
+                "fix(cli): parse flags",
+                "fix(core): handle overflow",
+                "feat: unscoped feature",
+            ],
+        );
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(dir.path()).unwrap();
+
+        let args = ChangelogArgs {
+            scope: vec!["api".to_string(), "cli".to_string()],
+            ..changelog_args()
+        };
+
+        let mut output = Vec::new();
+        let result = generate_changelog_to_writer(&mut output, args);
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok());
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("add endpoint"));
+        assert!(output.contains("parse flags"));
+        assert!(!output.contains("handle overflow"));
+        assert!(!output.contains("unscoped feature"));
+    }
+
+    #[test]
+    fn test_changelog_group_by_scope() {
+        let dir = create_test_git_repo_with_tags_and_commits(
+            &[],
+            &[
+                "feat(api): add endpoint",
+                "fix(api): validate input",
+                "fix(cli): parse flags",
+                "docs: update readme",
+            ],
+        );
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(dir.path()).unwrap();
+
+        let args = ChangelogArgs {
+            group_by: "scope".to_string(),
+            ..changelog_args()
+        };
+
+        let mut output = Vec::new();
+        let result = generate_changelog_to_writer(&mut output, args);
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok());
+        let output = String::from_utf8(output).unwrap();
+        let general = output.find("\n## General").unwrap();
+        let api = output.find("\n## api").unwrap();
+        let cli = output.find("\n## cli").unwrap();
+        assert!(general < api && api < cli, "{}", output);
+        // Types are sub-sections of each scope, features first
+        let api_section = &output[api..cli];
+        let features = api_section.find("### Features").unwrap();
+        let fixes = api_section.find("### Bug Fixes").unwrap();
+        assert!(features < fixes, "{}", output);
+        assert!(api_section.contains("validate input"));
+        assert!(output[general..api].contains("### Documentation"));
+        assert!(!output.contains("#### "), "{}", output);
+    }
+
+    #[test]
+    fn test_changelog_max_items() {
+        let dir = create_test_git_repo_with_tags_and_commits(
+            &[],
+            &[
+                "feat: first feature",
+                "fix: first fix",
+                "feat: second feature",
+                "fix: second fix",
+            ],
+        );
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(dir.path()).unwrap();
+
+        let changelog = |sort: &str| {
+            let args = ChangelogArgs {
+                max_items: Some(2),
+                sort: sort.to_string(),
+                ..changelog_args()
+            };
+            let mut output = Vec::new();
+            generate_changelog_to_writer(&mut output, args).map(|()| output)
+        };
+        let by_date = changelog("date");
+        let by_type = changelog("type");
+        std::env::set_current_dir(original_dir).unwrap();
+
+        let output = String::from_utf8(by_date.unwrap()).unwrap();
+        // The newest entries are kept
+        assert!(output.contains("second feature"));
+        assert!(output.contains("second fix"));
+        assert!(!output.contains("first feature"));
+        assert!(output.ends_with("…and more\n"), "{}", output);
+
+        // Entries are sorted before they are cut
+        let output = String::from_utf8(by_type.unwrap()).unwrap();
+        assert!(output.contains("first feature"));
+        assert!(output.contains("second feature"));
+        assert!(!output.contains("first fix"), "{}", output);
+        assert!(!output.contains("second fix"), "{}", output);
+        assert!(output.ends_with("…and 2 more\n"), "{}", output);
+    }
+
+    #[test]
+    fn test_truncate_items() {
+        let mut items = vec![1, 2, 3];
+        assert_eq!(truncate_items(&mut items, None), 0);
+        assert_eq!(truncate_items(&mut items, Some(5)), 0);
+        assert_eq!(truncate_items(&mut items, Some(1)), 2);
+        assert_eq!(items, [1]);
+    }
+
+    #[test]
+    fn test_sort_commits() {
+        let commit = |message: &str, date: i64| Commit {
+            date,
+            ..parse_conventional_commit(message).unwrap()
+        };
+        let mut commits = vec![
+            commit("fix: alpha", 1),
+            commit("feat(cli): beta", 2),
+            commit("fix: Zeta", 3),
+        ];
+        let subjects = |commits: &[Commit]| {
+            commits
+                .iter()
+                .map(|c| c.subject.clone())
+                .collect::<Vec<_>>()
+        };
+
+        sort_commits(&mut commits, "date").unwrap();
+        assert_eq!(subjects(&commits), ["Zeta", "beta", "alpha"]);
+        sort_commits(&mut commits, "type").unwrap();
+        assert_eq!(subjects(&commits), ["beta", "alpha", "Zeta"]);
+        sort_commits(&mut commits, "scope").unwrap();
+        assert_eq!(subjects(&commits), ["alpha", "Zeta", "beta"]);
+
+        let error = sort_commits(&mut commits, "author")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("expected date, type or scope"), "{}", error);
+    }
 }
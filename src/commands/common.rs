@@ -0,0 +1,632 @@
+//! Helpers shared by several commands.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{
+    BuildHasher,
+    Hasher,
+};
+use std::io::{
+    BufRead,
+    Write,
+};
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use anyhow::{
+    Context,
+    Result,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// A single version, printed with `--format json` by `current`, `dioxus` and
+/// `rust-toolchain`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct VersionOutput {
+    /// The version (e.g. `0.1.2`).
+    pub version: String,
+}
+
+/// How cargo may access the network, from the global `--offline` and
+/// `--frozen` flags.
+///
+/// Passed explicitly (in the command arguments, or
+/// [`BadgeOptions`](crate::commands::BadgeOptions) for badges) so that each
+/// caller gets its own settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CargoOptions {
+    /// Run cargo without network access (`--offline`).
+    pub offline: bool,
+    /// Like `offline`, and also require `Cargo.lock` to be up to date
+    /// (`--frozen`).
+    pub frozen: bool,
+}
+
+impl CargoOptions {
+    /// Whether cargo must run without network access (`--offline` or
+    /// `--frozen`).
+    ///
+    /// Cargo subprocesses other than `cargo metadata` get `CARGO_NET_OFFLINE`
+    /// set on their own environment when this is true.
+    pub fn is_offline(self) -> bool {
+        self.offline || self.frozen
+    }
+
+    /// Create a `cargo metadata` command passing `--frozen` or `--offline`.
+    pub fn metadata_command(self) -> cargo_metadata::MetadataCommand {
+        let mut command = cargo_metadata::MetadataCommand::new();
+        if self.frozen {
+            command.other_options(vec!["--frozen".to_string()]);
+        } else if self.offline {
+            command.other_options(vec!["--offline".to_string()]);
+        }
+        command
+    }
+}
+
+/// Find the package whose manifest is `manifest_path`, or the nearest
+/// `Cargo.toml` at or above the current directory when `None`.
+///
+/// This is the package `current` and `bump` act on without `--package`.
+/// `cargo metadata` is run with the given [`CargoOptions`].
+///
+/// # Errors
+///
+/// Returns an error if no manifest can be found, `cargo metadata` fails, or
+/// the manifest is a virtual workspace manifest. The latter lists the
+/// workspace members.
+pub fn find_manifest_package(
+    manifest_path: Option<&Path>,
+    cargo: CargoOptions,
+) -> Result<cargo_metadata::Package> {
+    let manifest_path = match manifest_path {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let current_dir = std::env::current_dir().context("Failed to get current directory")?;
+            current_dir
+                .ancestors()
+                .map(|dir| dir.join("Cargo.toml"))
+                .find(|path| path.is_file())
+                .context("Could not find Cargo.toml in the current directory or any parent")?
+        }
+    };
+    let metadata = cargo
+        .metadata_command()
+        .manifest_path(&manifest_path)
+        .no_deps()
+        .exec()
+        .context("Failed to get cargo metadata")?;
+
+    let canonical = manifest_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", manifest_path.display()))?;
+    let members = metadata.workspace_packages();
+    if let Some(package) = members.iter().find(|pkg| {
+        pkg.manifest_path
+            .as_std_path()
+            .canonicalize()
+            .is_ok_and(|path| path == canonical)
+    }) {
+        return Ok((*package).clone());
+    }
+    let names: Vec<&str> = members.iter().map(|pkg| pkg.name.as_str()).collect();
+    anyhow::bail!(
+        "{} is a virtual manifest; select a package with --package (members: {})",
+        manifest_path.display(),
+        names.join(", ")
+    )
+}
+
+/// Find the workspace member named `name` (cargo's `--package`/`-p`).
+///
+/// The workspace is located from `manifest_path` when given, otherwise from
+/// the current directory.
+///
+/// # Errors
+///
+/// Returns an error if `cargo metadata` fails or no workspace member has
+/// that name. The error lists the available members.
+pub fn find_workspace_member(
+    name: &str,
+    manifest_path: Option<&Path>,
+    cargo: CargoOptions,
+) -> Result<cargo_metadata::Package> {
+    let mut command = cargo.metadata_command();
+    if let Some(manifest_path) = manifest_path {
+        command.manifest_path(manifest_path);
+    }
+    let metadata = command.exec().context("Failed to get cargo metadata")?;
+
+    let members = metadata.workspace_packages();
+    if let Some(package) = members.iter().find(|pkg| pkg.name.as_str() == name) {
+        return Ok((*package).clone());
+    }
+    let names: Vec<&str> = members.iter().map(|pkg| pkg.name.as_str()).collect();
+    anyhow::bail!(
+        "Package `{}` is not a member of the workspace (members: {})",
+        name,
+        names.join(", ")
+    )
+}
+
+/// Append outputs to a GitHub Actions output file.
+///
+/// `output_file` is usually the value of `GITHUB_OUTPUT`; when it is `None`,
+/// the entries are written to stdout instead. The file is opened in append
+/// mode because `GITHUB_OUTPUT` accumulates outputs across steps.
+///
+/// # Errors
+///
+/// Returns an error if the output file cannot be opened or written.
+pub fn write_github_outputs(output_file: Option<&str>, outputs: &[(&str, &str)]) -> Result<()> {
+    let output_file = output_file.unwrap_or("/dev/stdout");
+    let output: String = outputs
+        .iter()
+        .map(|(name, value)| github_output_entry(name, value))
+        .collect();
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(output_file)
+        .with_context(|| format!("Failed to open {}", output_file))?;
+    file.write_all(output.as_bytes())
+        .with_context(|| format!("Failed to write to {}", output_file))?;
+
+    Ok(())
+}
+
+/// Format a single GitHub Actions output entry.
+///
+/// Single-line values use `name=value`. Multi-line values use the heredoc
+/// syntax Actions requires, with a random delimiter that does not occur in
+/// the value:
+///
+/// ```text
+/// name<<ghadelimiter_1a2b3c4d5e6f7a8b
+/// first line
+/// second line
+/// ghadelimiter_1a2b3c4d5e6f7a8b
+/// ```
+pub fn github_output_entry(name: &str, value: &str) -> String {
+    if !value.contains('\n') && !value.contains('\r') {
+        return format!("{}={}\n", name, value);
+    }
+
+    let delimiter = loop {
+        let candidate = format!("ghadelimiter_{:016x}", random_u64());
+        if !value.contains(&candidate) {
+            break candidate;
+        }
+    };
+    let value = value.strip_suffix('\n').unwrap_or(value);
+
+    format!("{}<<{}\n{}\n{}\n", name, delimiter, value, delimiter)
+}
+
+/// A random number from the standard library's randomly seeded hasher.
+fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    hasher.finish()
+}
+
+/// Shortest abbreviated SHA accepted by `--sha-length` (git's minimum).
+pub const MIN_SHA_LENGTH: usize = 4;
+
+/// Longest abbreviated SHA accepted by `--sha-length` (a full SHA-1).
+pub const MAX_SHA_LENGTH: usize = 40;
+
+/// Abbreviate a commit id.
+///
+/// With `length`, the SHA is cut to that many hex characters, clamped to
+/// [`MIN_SHA_LENGTH`]..=[`MAX_SHA_LENGTH`]. Without it, gix picks the
+/// shortest unambiguous abbreviation (respecting `core.abbrev`).
+///
+/// # Errors
+///
+/// Returns an error if gix fails to compute the automatic abbreviation.
+pub fn abbreviate_sha(id: gix::Id<'_>, length: Option<usize>) -> Result<String> {
+    match length {
+        Some(length) => {
+            let length = length.clamp(MIN_SHA_LENGTH, MAX_SHA_LENGTH);
+            Ok(id.to_hex_with_len(length).to_string())
+        }
+        None => Ok(id
+            .shorten()
+            .context("Failed to shorten commit SHA")?
+            .to_string()),
+    }
+}
+
+/// Branch assumed when the default branch cannot be detected.
+pub const FALLBACK_DEFAULT_BRANCH: &str = "main";
+
+/// Determine the repository's default branch.
+///
+/// Checks, in order:
+/// 1. The `origin` remote's `HEAD` (`refs/remotes/origin/HEAD`, set by `git
+///    clone` or `git remote set-head`)
+/// 2. `init.defaultBranch` from git config, if that branch exists locally
+/// 3. A local `main` or `master` branch
+///
+/// Falls back to [`FALLBACK_DEFAULT_BRANCH`] when none of these apply or the
+/// repository cannot be opened.
+pub fn default_branch(repo_path: &Path) -> String {
+    detect_default_branch(repo_path).unwrap_or_else(|| FALLBACK_DEFAULT_BRANCH.to_string())
+}
+
+/// Detect the default branch, returning `None` if it cannot be determined.
+fn detect_default_branch(repo_path: &Path) -> Option<String> {
+    let repo = gix::discover(repo_path).ok()?;
+
+    if let Ok(Some(origin_head)) = repo.try_find_reference("refs/remotes/origin/HEAD")
+        && let gix::refs::TargetRef::Symbolic(target) = origin_head.target()
+    {
+        let target = target.as_bstr().to_string();
+        if let Some(branch) = target.strip_prefix("refs/remotes/origin/") {
+            return Some(branch.to_string());
+        }
+    }
+
+    let has_local_branch = |name: &str| {
+        repo.try_find_reference(format!("refs/heads/{}", name).as_str())
+            .ok()
+            .flatten()
+            .is_some()
+    };
+
+    if let Some(configured) = repo.config_snapshot().string("init.defaultBranch") {
+        let configured = configured.to_string();
+        if has_local_branch(&configured) {
+            return Some(configured);
+        }
+    }
+
+    ["main", "master"]
+        .into_iter()
+        .find(|name| has_local_branch(name))
+        .map(str::to_string)
+}
+
+/// Read the version from a sidecar file such as `version.txt`.
+///
+/// The content is trimmed and must be a valid semantic version.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or does not contain a valid
+/// version.
+pub fn read_version_file(path: &Path) -> Result<String> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read version file {}", path.display()))?;
+    let version = content.trim();
+    cargo_metadata::semver::Version::parse(version)
+        .with_context(|| format!("Invalid version '{}' in {}", version, path.display()))?;
+
+    Ok(version.to_string())
+}
+
+/// Prefix of every cache file name.
+const CACHE_FILE_PREFIX: &str = ".cargo-version-info-";
+
+/// Suffix of every cache file name.
+const CACHE_FILE_SUFFIX: &str = "-cache.json";
+
+/// Get the path of a named cache file.
+///
+/// Caches live in [`cache_dir`] as
+/// `.cargo-version-info-<name>-<workspace-id>-cache.json`, where the
+/// workspace id is a hash of the workspace root (see [`workspace_id`]). A
+/// target directory shared between checkouts (e.g. via `CARGO_TARGET_DIR` in
+/// CI) therefore never serves one checkout's results to another.
+pub fn cache_file_path(cache_name: &str) -> Result<PathBuf> {
+    let workspace_id = workspace_id(&workspace_root()?);
+    Ok(cache_dir()?.join(cache_file_name(cache_name, &workspace_id)))
+}
+
+/// File name of a named cache for a workspace.
+fn cache_file_name(cache_name: &str, workspace_id: &str) -> String {
+    format!(
+        "{}{}-{}{}",
+        CACHE_FILE_PREFIX, cache_name, workspace_id, CACHE_FILE_SUFFIX
+    )
+}
+
+/// List the current workspace's cache files that exist in [`cache_dir`].
+///
+/// Caches belonging to other checkouts sharing the target directory are not
+/// included.
+pub fn cache_files() -> Result<Vec<PathBuf>> {
+    let dir = cache_dir()?;
+    let suffix = format!("-{}{}", workspace_id(&workspace_root()?), CACHE_FILE_SUFFIX);
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry.with_context(|| format!("Failed to read {}", dir.display()))?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with(CACHE_FILE_PREFIX) && name.ends_with(&suffix) {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+/// Get the directory holding cache files.
+///
+/// This is the cargo target directory: `CARGO_TARGET_DIR`, or the nearest
+/// `target/` directory above the current directory.
+pub fn cache_dir() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var("CARGO_TARGET_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+
+    // Find the target directory relative to the current dir, falling back to
+    // the current dir itself
+    let current_dir = std::env::current_dir().context("Failed to get current directory")?;
+    let target_dir = current_dir
+        .ancestors()
+        .map(|dir| dir.join("target"))
+        .find(|target| target.exists())
+        .unwrap_or_else(|| current_dir.join("target"));
+
+    Ok(target_dir)
+}
+
+/// Find the root of the workspace containing the current directory.
+///
+/// This is the nearest ancestor whose `Cargo.toml` has a `[workspace]`
+/// table, else the nearest ancestor with any `Cargo.toml`, else the current
+/// directory itself.
+pub fn workspace_root() -> Result<PathBuf> {
+    let current_dir = std::env::current_dir()?;
+    let mut package_root = None;
+    for dir in current_dir.ancestors() {
+        let manifest = dir.join("Cargo.toml");
+        let Ok(content) = std::fs::read_to_string(&manifest) else {
+            continue;
+        };
+        let is_workspace = content
+            .parse::<toml_edit::DocumentMut>()
+            .is_ok_and(|doc| doc.contains_key("workspace"));
+        if is_workspace {
+            return Ok(dir.to_path_buf());
+        }
+        package_root.get_or_insert_with(|| dir.to_path_buf());
+    }
+
+    Ok(package_root.unwrap_or(current_dir))
+}
+
+/// 64-bit FNV-1a hash of `bytes`.
+///
+/// Used for identifiers that end up in cache file names or keys: unlike the
+/// standard library's hashers, its output is fixed and will not change with a
+/// toolchain update.
+pub fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Short identifier of a workspace, derived from its canonical root path.
+///
+/// This is a [`fnv1a_64`] hash of the path bytes, so that old cache files
+/// are still found (and cleared by `cache clear`) after a toolchain update.
+pub fn workspace_id(workspace_root: &Path) -> String {
+    let root = workspace_root
+        .canonicalize()
+        .unwrap_or_else(|_| workspace_root.to_path_buf());
+    format!("{:016x}", fnv1a_64(root.as_os_str().as_encoded_bytes()))
+}
+
+/// Prompt on `output` and read a yes/no answer from `input`.
+///
+/// Only `y` or `yes` (case-insensitive) confirm; anything else, including
+/// an empty line or end of input, declines.
+pub fn confirm(input: &mut impl BufRead, output: &mut impl Write, prompt: &str) -> Result<bool> {
+    write!(output, "{}", prompt)?;
+    output.flush()?;
+
+    let mut answer = String::new();
+    input
+        .read_line(&mut answer)
+        .context("Failed to read confirmation")?;
+    let answer = answer.trim();
+
+    Ok(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    #[test]
+    fn test_github_output_entry_single_line() {
+        assert_eq!(github_output_entry("version", "1.2.3"), "version=1.2.3\n");
+    }
+
+    #[test]
+    fn test_github_output_entry_multi_line() {
+        let entry = github_output_entry("notes", "line one\nline two\n");
+        let mut lines = entry.lines();
+
+        let header = lines.next().unwrap();
+        let delimiter = header.strip_prefix("notes<<").unwrap();
+        assert!(delimiter.starts_with("ghadelimiter_"));
+        assert_eq!(lines.next(), Some("line one"));
+        assert_eq!(lines.next(), Some("line two"));
+        assert_eq!(lines.next(), Some(delimiter));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_abbreviate_sha() {
+        let dir = tempfile::tempdir().unwrap();
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.name", "Test User"],
+            &["config", "user.email", "test@example.com"],
+            &["commit", "-q", "--allow-empty", "-m", "initial"],
+        ] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap();
+        }
+        let repo = gix::discover(dir.path()).unwrap();
+        let id = repo.head_id().unwrap();
+        let full = id.to_string();
+
+        assert_eq!(abbreviate_sha(id, Some(12)).unwrap(), full[..12]);
+        assert_eq!(abbreviate_sha(id, Some(1)).unwrap().len(), MIN_SHA_LENGTH);
+        assert_eq!(abbreviate_sha(id, Some(100)).unwrap(), full);
+        assert!(full.starts_with(&abbreviate_sha(id, None).unwrap()));
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_default_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        git(dir.path(), &["init", "-q", "-b", "master"]);
+        git(dir.path(), &["config", "user.name", "Test User"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+        git(
+            dir.path(),
+            &["commit", "-q", "--allow-empty", "-m", "initial"],
+        );
+        assert_eq!(default_branch(dir.path()), "master");
+
+        git(
+            dir.path(),
+            &[
+                "symbolic-ref",
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/trunk",
+            ],
+        );
+        assert_eq!(default_branch(dir.path()), "trunk");
+
+        let not_a_repo = tempfile::tempdir().unwrap();
+        assert_eq!(default_branch(not_a_repo.path()), FALLBACK_DEFAULT_BRANCH);
+    }
+
+    #[test]
+    fn test_confirm() {
+        let answer = |input: &str| {
+            let mut output = Vec::new();
+            let confirmed = confirm(&mut input.as_bytes(), &mut output, "Proceed? [y/N] ").unwrap();
+            assert_eq!(output, b"Proceed? [y/N] ");
+            confirmed
+        };
+
+        assert!(answer("y\n"));
+        assert!(answer("YES\n"));
+        assert!(!answer("n\n"));
+        assert!(!answer("\n"));
+        assert!(!answer(""));
+    }
+
+    #[test]
+    fn test_fnv1a_64() {
+        // Reference values of the FNV-1a specification
+        assert_eq!(fnv1a_64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a_64(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_workspace_id() {
+        let a = tempfile::tempdir().unwrap();
+        let b = tempfile::tempdir().unwrap();
+
+        assert_eq!(workspace_id(a.path()), workspace_id(a.path()));
+        assert_ne!(workspace_id(a.path()), workspace_id(b.path()));
+        assert_eq!(workspace_id(a.path()).len(), 16);
+        // Cache file names must not change between releases
+        assert_eq!(
+            workspace_id(Path::new("/nonexistent/workspace")),
+            "2ea3c322acf5b8f7"
+        );
+        assert_eq!(
+            cache_file_name("coverage", "0123456789abcdef"),
+            ".cargo-version-info-coverage-0123456789abcdef-cache.json"
+        );
+    }
+
+    #[test]
+    fn test_read_version_file() {
+        let file = NamedTempFile::new().unwrap();
+
+        std::fs::write(file.path(), "1.2.3-rc.1\n").unwrap();
+        assert_eq!(read_version_file(file.path()).unwrap(), "1.2.3-rc.1");
+
+        std::fs::write(file.path(), "1.2\n").unwrap();
+        assert!(read_version_file(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_write_github_outputs_appends() {
+        let output_file = NamedTempFile::new().unwrap();
+        std::fs::write(output_file.path(), "earlier=output\n").unwrap();
+        let path = output_file.path().to_string_lossy().to_string();
+
+        write_github_outputs(Some(&path), &[("a", "1"), ("b", "2")]).unwrap();
+
+        let content = std::fs::read_to_string(output_file.path()).unwrap();
+        assert_eq!(content, "earlier=output\na=1\nb=2\n");
+    }
+
+    #[test]
+    fn test_cargo_options() {
+        let metadata_args = |cargo: CargoOptions| {
+            let command = cargo.metadata_command().cargo_command();
+            command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .filter(|arg| arg == "--offline" || arg == "--frozen")
+                .collect::<Vec<_>>()
+        };
+
+        let online = CargoOptions::default();
+        assert!(!online.is_offline());
+        assert!(metadata_args(online).is_empty());
+
+        let offline = CargoOptions {
+            offline: true,
+            ..CargoOptions::default()
+        };
+        assert!(offline.is_offline());
+        assert_eq!(metadata_args(offline), ["--offline"]);
+
+        // --frozen implies --offline, so it is passed on its own
+        let frozen = CargoOptions {
+            offline: true,
+            frozen: true,
+        };
+        assert!(frozen.is_offline());
+        assert_eq!(metadata_args(frozen), ["--frozen"]);
+    }
+}
@@ -1,7 +1,9 @@
 //! Compare two versions command.
 //!
 //! This command compares two semantic version strings and determines their
-//! relationship (greater than, less than, or equal).
+//! relationship (greater than, less than, or equal) and the size of the step
+//! between them (major, minor, patch, or prerelease). Pre-release versions are
+//! ordered by semantic versioning precedence, so `1.0.0-rc.1 < 1.0.0`.
 //!
 //! # Examples
 //!
@@ -10,16 +12,27 @@
 //! cargo version-info compare 0.2.0 0.1.0
 //!
 //! # Get JSON output
-//! cargo version-info compare 0.2.0 0.1.0 --format json
+//! cargo version-info compare 1.2.0 1.10.0 --format json
 //!
 //! # Get human-readable diff
 //! cargo version-info compare 0.2.0 0.1.0 --format diff
 //! ```
 
-use anyhow::Result;
+use std::cmp::Ordering;
+
+use anyhow::{
+    Context,
+    Result,
+};
 use clap::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
 
-use crate::version::compare_versions;
+use crate::version::{
+    cmp_versions,
+    parse_version,
+    version_step,
+};
 
 /// Arguments for the `compare` command.
 #[derive(Parser, Debug)]
@@ -37,23 +50,37 @@ pub struct CompareArgs {
     /// Output format for the comparison result.
     ///
     /// - `bool`: Print "true" if version1 > version2, "false" otherwise
-    /// - `json`: Print JSON with result, version1, and version2 fields
-    /// - `diff`: Print human-readable comparison (e.g., "0.2.0 > 0.1.0")
+    /// - `json`: Print JSON with left, right, ordering, and bump fields
+    /// - `diff`: Print human-readable comparison (e.g., "0.2.0 > 0.1.0
+    ///   (minor)")
     #[arg(long, default_value = "bool")]
     format: String,
 }
 
+/// Comparison result, printed with `--format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct CompareOutput<'a> {
+    /// The first version.
+    left: &'a str,
+    /// The second version.
+    right: &'a str,
+    /// How `left` compares to `right`: `less`, `equal` or `greater`.
+    ordering: &'a str,
+    /// The step between the versions: `major`, `minor`, `patch`,
+    /// `prerelease` or `none`.
+    bump: &'a str,
+}
+
 /// Compare two semantic version strings.
 ///
-/// Determines the relationship between two versions:
-/// - Returns `Some(true)` if version1 > version2
-/// - Returns `Some(false)` if version1 < version2
-/// - Returns `None` if version1 == version2
+/// Determines the ordering of version1 relative to version2 and the most
+/// significant component that differs between them.
 ///
 /// # Errors
 ///
 /// Returns an error if either version string cannot be parsed as a valid
-/// semantic version (major.minor.patch).
+/// semantic version (major.minor.patch). The error names the malformed
+/// argument.
 ///
 /// # Examples
 ///
@@ -79,39 +106,24 @@ pub struct CompareArgs {
 /// true
 /// ```
 ///
-/// With `--format bool` (version1 < version2):
-/// ```text
-/// false
-/// ```
-///
-/// With `--format bool` (version1 == version2):
+/// With `--format bool` (version1 <= version2):
 /// ```text
 /// false
 /// ```
 ///
-/// With `--format json` (version1 > version2):
-/// ```json
-/// {"result":"greater","version1":"0.2.0","version2":"0.1.0"}
-/// ```
-///
-/// With `--format json` (version1 < version2):
+/// With `--format json`:
 /// ```json
-/// {"result":"less","version1":"0.1.0","version2":"0.2.0"}
+/// {"left":"1.2.0","right":"1.10.0","ordering":"less","bump":"minor"}
 /// ```
 ///
-/// With `--format json` (version1 == version2):
+/// With `--format json` (equal versions):
 /// ```json
-/// {"result":"equal","version1":"0.1.0","version2":"0.1.0"}
+/// {"left":"0.1.0","right":"0.1.0","ordering":"equal","bump":"none"}
 /// ```
 ///
 /// With `--format diff`:
 /// ```text
-/// 0.2.0 > 0.1.0
-/// ```
-///
-/// Or:
-/// ```text
-/// 0.1.0 < 0.2.0
+/// 0.2.0 > 0.1.0 (minor)
 /// ```
 ///
 /// Or:
@@ -119,34 +131,34 @@ pub struct CompareArgs {
 /// 0.1.0 == 0.1.0
 /// ```
 pub fn compare(args: CompareArgs) -> Result<()> {
-    let comparison = compare_versions(&args.version1, &args.version2)?;
+    parse_version(&args.version1)
+        .with_context(|| format!("Invalid first version argument '{}'", args.version1))?;
+    parse_version(&args.version2)
+        .with_context(|| format!("Invalid second version argument '{}'", args.version2))?;
+
+    let ordering = cmp_versions(&args.version1, &args.version2)?;
+    let bump = version_step(&args.version1, &args.version2)?
+        .map(|step| step.as_str())
+        .unwrap_or("none");
 
     match args.format.as_str() {
-        "bool" => {
-            match comparison {
-                Some(true) => println!("true"),   // version1 > version2
-                Some(false) => println!("false"), // version1 < version2
-                None => println!("false"),        // version1 == version2
-            }
+        "bool" => println!("{}", ordering == Ordering::Greater),
+        "json" => {
+            let output = CompareOutput {
+                left: &args.version1,
+                right: &args.version2,
+                ordering: ordering_name(ordering),
+                bump,
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&output).context("Failed to serialize comparison")?
+            );
         }
-        "json" => match comparison {
-            Some(true) => println!(
-                "{{\"result\":\"greater\",\"version1\":\"{}\",\"version2\":\"{}\"}}",
-                args.version1, args.version2
-            ),
-            Some(false) => println!(
-                "{{\"result\":\"less\",\"version1\":\"{}\",\"version2\":\"{}\"}}",
-                args.version1, args.version2
-            ),
-            None => println!(
-                "{{\"result\":\"equal\",\"version1\":\"{}\",\"version2\":\"{}\"}}",
-                args.version1, args.version2
-            ),
-        },
-        "diff" => match comparison {
-            Some(true) => println!("{} > {}", args.version1, args.version2),
-            Some(false) => println!("{} < {}", args.version1, args.version2),
-            None => println!("{} == {}", args.version1, args.version2),
+        "diff" => match ordering {
+            Ordering::Greater => println!("{} > {} ({})", args.version1, args.version2, bump),
+            Ordering::Less => println!("{} < {} ({})", args.version1, args.version2, bump),
+            Ordering::Equal => println!("{} == {}", args.version1, args.version2),
         },
         _ => anyhow::bail!("Invalid format: {}", args.format),
     }
@@ -154,6 +166,15 @@ pub fn compare(args: CompareArgs) -> Result<()> {
     Ok(())
 }
 
+/// Name of an ordering as used in JSON output.
+fn ordering_name(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "less",
+        Ordering::Equal => "equal",
+        Ordering::Greater => "greater",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -277,4 +298,35 @@ mod tests {
         };
         assert!(compare(args).is_ok());
     }
+
+    #[test]
+    fn test_compare_prerelease_json() {
+        let args = CompareArgs {
+            version1: "1.0.0-rc.1".to_string(),
+            version2: "1.0.0".to_string(),
+            format: "json".to_string(),
+        };
+        assert!(compare(args).is_ok());
+    }
+
+    #[test]
+    fn test_compare_error_names_argument() {
+        let args = CompareArgs {
+            version1: "0.1.0".to_string(),
+            version2: "1.x".to_string(),
+            format: "bool".to_string(),
+        };
+        let err = compare(args).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Invalid second version argument '1.x'")
+        );
+    }
+
+    #[test]
+    fn test_ordering_name() {
+        assert_eq!(ordering_name(Ordering::Less), "less");
+        assert_eq!(ordering_name(Ordering::Equal), "equal");
+        assert_eq!(ordering_name(Ordering::Greater), "greater");
+    }
 }
@@ -12,11 +12,23 @@
 //! # Get version from a specific Cargo.toml (standard cargo flag)
 //! cargo version-info current --manifest-path ./path/to/Cargo.toml
 //!
+//! # Get version of a workspace member by name (standard cargo flag)
+//! cargo version-info current --package my-crate
+//!
 //! # Get JSON output
 //! cargo version-info current --format json
 //!
+//! # Print the version as `v1.2.3`
+//! cargo version-info current --prefix v
+//!
+//! # Read the version from a sidecar file instead of Cargo.toml
+//! cargo version-info current --version-file version.txt
+//!
 //! # Use in GitHub Actions
 //! cargo version-info current --format github-actions
+//!
+//! # Fail (exit code 11) unless the version is 1.4.0
+//! cargo version-info current --check 1.4.0
 //! ```
 
 use std::path::PathBuf;
@@ -25,8 +37,23 @@ use anyhow::{
     Context,
     Result,
 };
-use cargo_plugin_utils::common::find_package;
 use clap::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::common::{
+    CargoOptions,
+    VersionOutput,
+    find_manifest_package,
+    find_workspace_member,
+    read_version_file,
+    write_github_outputs,
+};
+use crate::exit_code::{
+    ExitCode,
+    ExitError,
+};
+use crate::version::apply_prefix;
 
 /// Arguments for the `current` command.
 #[derive(Parser, Debug)]
@@ -37,10 +64,40 @@ pub struct CurrentArgs {
     #[arg(long)]
     manifest_path: Option<PathBuf>,
 
+    /// Workspace member to read the version from (standard cargo flag).
+    #[arg(long, short = 'p', value_name = "NAME")]
+    package: Option<String>,
+
+    /// Read the version from this file (e.g. `version.txt`) instead of the
+    /// manifest.
+    ///
+    /// The file content is trimmed and must be a valid semantic version.
+    #[arg(long, value_name = "PATH")]
+    version_file: Option<PathBuf>,
+
+    /// Prefix to prepend to printed versions (e.g. `v` for `v1.2.3`).
+    ///
+    /// A `v`/`V` already on the version is replaced, never doubled.
+    #[arg(long, value_name = "PREFIX")]
+    prefix: Option<String>,
+
+    /// Print bare versions without any prefix (the default).
+    #[arg(long, conflicts_with = "prefix")]
+    no_prefix: bool,
+
+    /// Fail unless the version equals this expected version.
+    ///
+    /// A leading `v` on either side is ignored. On mismatch both versions are
+    /// reported and the command exits with code 11 (version drift); with
+    /// `--format json`, `{"expected":...,"actual":...,"match":false}` is
+    /// printed first.
+    #[arg(long, value_name = "VERSION")]
+    check: Option<String>,
+
     /// Output format for the version.
     ///
     /// - `version`: Print just the version number (e.g., "0.1.2")
-    /// - `json`: Print JSON with version field
+    /// - `json`: Print JSON with version field (or the `--check` result)
     /// - `github-actions`: Write to GITHUB_OUTPUT file in GitHub Actions format
     #[arg(long, default_value = "version")]
     format: String,
@@ -51,19 +108,39 @@ pub struct CurrentArgs {
     /// Defaults to the `GITHUB_OUTPUT` environment variable or stdout.
     #[arg(long, env = "GITHUB_OUTPUT")]
     github_output: Option<String>,
+    /// Network access for `cargo metadata`, from the global `--offline` and
+    /// `--frozen` flags.
+    #[arg(skip)]
+    pub cargo: CargoOptions,
+}
+
+/// Result of `current --check`, printed with `--format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct CheckOutput<'a> {
+    /// The version passed to `--check`.
+    expected: &'a str,
+    /// The version read from the manifest or version file.
+    actual: &'a str,
+    /// Whether both versions are equal.
+    #[serde(rename = "match")]
+    matches: bool,
 }
 
 /// Get the current version from a Cargo.toml manifest file.
 ///
 /// Extracts the version from the manifest, checking `[workspace.package]`
-/// first (for workspace members), then falling back to `[package]`.
+/// first (for workspace members), then falling back to `[package]`. With
+/// `--version-file`, the version is read from that file instead.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - The manifest file cannot be read
+/// - The `--package` name is not a workspace member
+/// - The version file cannot be read or is not a valid semantic version
 /// - No version field is found in either `[workspace.package]` or `[package]`
 /// - The output file cannot be written (for github-actions format)
+/// - The version differs from `--check` (exit code 11)
 ///
 /// # Examples
 ///
@@ -101,24 +178,88 @@ pub fn current(args: CurrentArgs) -> Result<()> {
     let mut logger = cargo_plugin_utils::logger::Logger::new();
 
     logger.status("Reading", "package version");
-    // Use find_package which automatically handles --manifest-path and workspace
-    // logic
-    let package = find_package(args.manifest_path.as_deref())?;
-    let version = package.version.to_string();
+    let version = if let Some(version_file) = &args.version_file {
+        read_version_file(version_file)?
+    } else {
+        // Resolves --manifest-path and workspace members like cargo does
+        let package = match &args.package {
+            Some(name) => find_workspace_member(name, args.manifest_path.as_deref(), args.cargo)?,
+            None => find_manifest_package(args.manifest_path.as_deref(), args.cargo)?,
+        };
+        package.version.to_string()
+    };
     logger.finish();
+    let prefix = if args.no_prefix {
+        None
+    } else {
+        args.prefix.as_deref()
+    };
+    let version = apply_prefix(&version, prefix);
+
+    if let Some(expected) = &args.check {
+        return check_version(&args, expected, &version);
+    }
 
     match args.format.as_str() {
         "version" => println!("{}", version),
-        "json" => println!("{{\"version\":\"{}\"}}", version),
+        "json" => println!(
+            "{}",
+            serde_json::to_string(&VersionOutput { version })
+                .context("Failed to serialize version")?
+        ),
+        "github-actions" => {
+            write_github_outputs(
+                args.github_output.as_deref(),
+                &[("version", version.as_str())],
+            )?;
+        }
+        _ => anyhow::bail!("Invalid format: {}", args.format),
+    }
+
+    Ok(())
+}
+
+/// Compare the version against `--check`, print the result, and fail on
+/// mismatch.
+fn check_version(args: &CurrentArgs, expected: &str, actual: &str) -> Result<()> {
+    let matches = apply_prefix(expected.trim(), None) == apply_prefix(actual, None);
+
+    match args.format.as_str() {
+        "version" => {
+            if matches {
+                println!("{}", actual);
+            }
+        }
+        "json" => println!(
+            "{}",
+            serde_json::to_string(&CheckOutput {
+                expected,
+                actual,
+                matches,
+            })
+            .context("Failed to serialize version check")?
+        ),
         "github-actions" => {
-            let output_file = args.github_output.as_deref().unwrap_or("/dev/stdout");
-            let output = format!("version={}\n", version);
-            std::fs::write(output_file, output)
-                .with_context(|| format!("Failed to write to {}", output_file))?;
+            write_github_outputs(
+                args.github_output.as_deref(),
+                &[
+                    ("version", actual),
+                    ("expected", expected),
+                    ("match", if matches { "true" } else { "false" }),
+                ],
+            )?;
         }
         _ => anyhow::bail!("Invalid format: {}", args.format),
     }
 
+    if !matches {
+        return Err(ExitError::new(
+            ExitCode::VersionDrift,
+            format!("Version mismatch: expected {}, found {}", expected, actual),
+        )
+        .into());
+    }
+
     Ok(())
 }
 
@@ -175,12 +316,103 @@ version.workspace = true
         let manifest_path = member_dir.join("Cargo.toml");
         let args = CurrentArgs {
             manifest_path: Some(manifest_path),
+            package: None,
+            version_file: None,
+            prefix: None,
+            no_prefix: false,
+            check: None,
             format: "version".to_string(),
             github_output: None,
+            cargo: CargoOptions::default(),
         };
         assert!(current(args).is_ok());
     }
 
+    #[test]
+    fn test_current_select_package() {
+        let dir = create_temp_cargo_project(
+            r#"
+[workspace]
+members = ["member1"]
+
+[package]
+name = "root"
+version = "1.0.0"
+"#,
+        );
+        let member_dir = dir.path().join("member1");
+        std::fs::create_dir_all(member_dir.join("src")).unwrap();
+        std::fs::write(
+            member_dir.join("Cargo.toml"),
+            r#"
+[package]
+name = "member1"
+version = "0.3.0"
+"#,
+        )
+        .unwrap();
+        std::fs::write(member_dir.join("src").join("lib.rs"), "// Test library\n").unwrap();
+
+        let manifest_path = dir.path().join("Cargo.toml");
+        let member =
+            find_workspace_member("member1", Some(&manifest_path), CargoOptions::default())
+                .unwrap();
+        assert_eq!(member.version.to_string(), "0.3.0");
+
+        let args = CurrentArgs {
+            manifest_path: Some(manifest_path),
+            package: Some("missing".to_string()),
+            version_file: None,
+            prefix: None,
+            no_prefix: false,
+            check: None,
+            format: "version".to_string(),
+            github_output: None,
+            cargo: CargoOptions::default(),
+        };
+        let error = current(args).unwrap_err().to_string();
+        assert!(error.contains("members: "), "{}", error);
+        assert!(error.contains("member1"), "{}", error);
+    }
+
+    #[test]
+    fn test_find_manifest_package() {
+        let dir = create_temp_cargo_project(
+            r#"
+[workspace]
+members = ["member1"]
+"#,
+        );
+        let member_dir = dir.path().join("member1");
+        std::fs::create_dir_all(member_dir.join("src")).unwrap();
+        std::fs::write(
+            member_dir.join("Cargo.toml"),
+            r#"
+[package]
+name = "member1"
+version = "0.3.0"
+"#,
+        )
+        .unwrap();
+        std::fs::write(member_dir.join("src").join("lib.rs"), "// Test library\n").unwrap();
+
+        let member = find_manifest_package(
+            Some(&member_dir.join("Cargo.toml")),
+            CargoOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(member.name.as_str(), "member1");
+
+        let error = find_manifest_package(
+            Some(&dir.path().join("Cargo.toml")),
+            CargoOptions::default(),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("virtual manifest"), "{}", error);
+        assert!(error.contains("member1"), "{}", error);
+    }
+
     #[test]
     fn test_current_package_version() {
         let _dir = create_temp_cargo_project(
@@ -193,8 +425,14 @@ version = "1.2.3"
         let manifest_path = _dir.path().join("Cargo.toml");
         let args = CurrentArgs {
             manifest_path: Some(manifest_path.clone()),
+            package: None,
+            version_file: None,
+            prefix: None,
+            no_prefix: false,
+            check: None,
             format: "version".to_string(),
             github_output: None,
+            cargo: CargoOptions::default(),
         };
         let result = current(args);
         if let Err(e) = &result {
@@ -204,6 +442,37 @@ version = "1.2.3"
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_current_check() {
+        let dir = create_temp_cargo_project(
+            r#"
+[package]
+name = "test"
+version = "1.2.3"
+"#,
+        );
+        let check = |expected: &str| CurrentArgs {
+            manifest_path: Some(dir.path().join("Cargo.toml")),
+            package: None,
+            version_file: None,
+            prefix: None,
+            no_prefix: false,
+            check: Some(expected.to_string()),
+            format: "json".to_string(),
+            github_output: None,
+            cargo: CargoOptions::default(),
+        };
+
+        assert!(current(check("v1.2.3")).is_ok());
+
+        let error = current(check("1.3.0")).unwrap_err();
+        assert_eq!(ExitCode::from_error(&error), ExitCode::VersionDrift);
+        assert_eq!(
+            error.to_string(),
+            "Version mismatch: expected 1.3.0, found 1.2.3"
+        );
+    }
+
     #[test]
     fn test_current_json_format() {
         let _dir = create_temp_cargo_project(
@@ -216,8 +485,14 @@ version = "0.5.0"
         let manifest_path = _dir.path().join("Cargo.toml");
         let args = CurrentArgs {
             manifest_path: Some(manifest_path),
+            package: None,
+            version_file: None,
+            prefix: None,
+            no_prefix: false,
+            check: None,
             format: "json".to_string(),
             github_output: None,
+            cargo: CargoOptions::default(),
         };
         assert!(current(args).is_ok());
     }
@@ -235,8 +510,14 @@ version = "2.0.0"
         let output_file = NamedTempFile::new().unwrap();
         let args = CurrentArgs {
             manifest_path: Some(manifest_path),
+            package: None,
+            version_file: None,
+            prefix: None,
+            no_prefix: false,
+            check: None,
             format: "github-actions".to_string(),
             github_output: Some(output_file.path().to_string_lossy().to_string()),
+            cargo: CargoOptions::default(),
         };
         assert!(current(args).is_ok());
 
@@ -244,6 +525,36 @@ version = "2.0.0"
         assert!(content.contains("version=2.0.0"));
     }
 
+    #[test]
+    fn test_current_github_actions_appends() {
+        let _dir = create_temp_cargo_project(
+            r#"
+[package]
+name = "test"
+version = "2.0.0"
+"#,
+        );
+        let manifest_path = _dir.path().join("Cargo.toml");
+        let output_file = NamedTempFile::new().unwrap();
+        std::fs::write(output_file.path(), "earlier=output\n").unwrap();
+
+        let args = CurrentArgs {
+            manifest_path: Some(manifest_path),
+            package: None,
+            version_file: None,
+            prefix: None,
+            no_prefix: false,
+            check: None,
+            format: "github-actions".to_string(),
+            github_output: Some(output_file.path().to_string_lossy().to_string()),
+            cargo: CargoOptions::default(),
+        };
+        assert!(current(args).is_ok());
+
+        let content = std::fs::read_to_string(output_file.path()).unwrap();
+        assert_eq!(content, "earlier=output\nversion=2.0.0\n");
+    }
+
     #[test]
     fn test_current_invalid_format() {
         let _dir = create_temp_cargo_project(
@@ -256,8 +567,14 @@ version = "1.0.0"
         let manifest_path = _dir.path().join("Cargo.toml");
         let args = CurrentArgs {
             manifest_path: Some(manifest_path),
+            package: None,
+            version_file: None,
+            prefix: None,
+            no_prefix: false,
+            check: None,
             format: "invalid".to_string(),
             github_output: None,
+            cargo: CargoOptions::default(),
         };
         assert!(current(args).is_err());
     }
@@ -266,8 +583,14 @@ version = "1.0.0"
     fn test_current_file_not_found() {
         let args = CurrentArgs {
             manifest_path: Some("/nonexistent/Cargo.toml".into()),
+            package: None,
+            version_file: None,
+            prefix: None,
+            no_prefix: false,
+            check: None,
             format: "version".to_string(),
             github_output: None,
+            cargo: CargoOptions::default(),
         };
         assert!(current(args).is_err());
     }
@@ -285,8 +608,14 @@ name = "test"
         let manifest_path = _dir.path().join("Cargo.toml");
         let args = CurrentArgs {
             manifest_path: Some(manifest_path),
+            package: None,
+            version_file: None,
+            prefix: None,
+            no_prefix: false,
+            check: None,
             format: "version".to_string(),
             github_output: None,
+            cargo: CargoOptions::default(),
         };
         // Cargo defaults to 0.0.0, so this should succeed
         let result = current(args);
@@ -295,4 +624,48 @@ name = "test"
         // (We can't easily capture stdout in this test, but the function should
         // complete)
     }
+
+    #[test]
+    fn test_current_version_file() {
+        let _dir = create_temp_cargo_project(
+            r#"
+[package]
+name = "test"
+version = "1.0.0"
+"#,
+        );
+        let version_file = _dir.path().join("version.txt");
+        std::fs::write(&version_file, "3.1.4\n").unwrap();
+        let output_file = NamedTempFile::new().unwrap();
+
+        let args = CurrentArgs {
+            manifest_path: Some(_dir.path().join("Cargo.toml")),
+            package: None,
+            version_file: Some(version_file.clone()),
+            prefix: None,
+            no_prefix: false,
+            check: None,
+            format: "github-actions".to_string(),
+            github_output: Some(output_file.path().to_string_lossy().to_string()),
+            cargo: CargoOptions::default(),
+        };
+        assert!(current(args).is_ok());
+
+        let content = std::fs::read_to_string(output_file.path()).unwrap();
+        assert_eq!(content, "version=3.1.4\n");
+
+        std::fs::write(&version_file, "not a version\n").unwrap();
+        let args = CurrentArgs {
+            manifest_path: None,
+            package: None,
+            version_file: Some(version_file),
+            prefix: None,
+            no_prefix: false,
+            check: None,
+            format: "version".to_string(),
+            github_output: None,
+            cargo: CargoOptions::default(),
+        };
+        assert!(current(args).is_err());
+    }
 }
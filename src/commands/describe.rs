@@ -0,0 +1,448 @@
+//! Describe HEAD relative to the nearest tag.
+//!
+//! This command emulates `git describe --tags` without shelling out to git:
+//! it finds the nearest tag reachable from HEAD, counts the commits since
+//! that tag and appends the abbreviated commit SHA. The result looks like
+//! `v1.2.0-5-gabc1234`, or just `v1.2.0` when HEAD is exactly on the tag.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Describe HEAD (e.g., "v1.2.0-5-gabc1234")
+//! cargo version-info describe
+//!
+//! # Only consider tags matching a pattern
+//! cargo version-info describe --match 'v*'
+//!
+//! # Use a 12-character SHA
+//! cargo version-info describe --sha-length 12
+//!
+//! # Get JSON output with the individual parts
+//! cargo version-info describe --format json
+//! ```
+
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+use std::fmt;
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use anyhow::{
+    Context,
+    Result,
+};
+use clap::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::common::abbreviate_sha;
+use crate::version::parse_version;
+
+/// Arguments for the `describe` command.
+#[derive(Parser, Debug)]
+pub struct DescribeArgs {
+    /// Path to the git repository.
+    ///
+    /// Defaults to the current directory. The command will search upward
+    /// from this path to find the repository root.
+    #[arg(long, default_value = ".")]
+    repo_path: PathBuf,
+
+    /// Only consider tags matching this glob pattern (e.g., `v*`).
+    ///
+    /// Supports `*` (any sequence) and `?` (any single character).
+    #[arg(long = "match", value_name = "PATTERN")]
+    match_pattern: Option<String>,
+
+    /// Number of hex characters in the SHA (clamped to 4..=40).
+    ///
+    /// Defaults to gix's automatic length, the shortest unambiguous
+    /// abbreviation.
+    #[arg(long, value_name = "N")]
+    sha_length: Option<usize>,
+
+    /// Output format for the description.
+    ///
+    /// - `version`: Print the description (e.g., "v1.2.0-5-gabc1234")
+    /// - `json`: Print JSON with tag, distance and sha fields
+    #[arg(long, default_value = "version")]
+    format: String,
+}
+
+/// Description of HEAD, printed with `--format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct DescribeOutput<'a> {
+    /// The `git describe` style description.
+    describe: &'a str,
+    /// Name of the nearest tag.
+    tag: &'a str,
+    /// Number of commits since the tag.
+    distance: usize,
+    /// Abbreviated SHA of HEAD.
+    sha: &'a str,
+}
+
+/// The position of a commit relative to its nearest tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Description {
+    /// Name of the nearest tag (without `refs/tags/`).
+    pub tag: String,
+    /// Number of commits reachable from HEAD but not from the tag.
+    pub distance: usize,
+    /// Abbreviated SHA of HEAD.
+    pub short_sha: String,
+}
+
+impl fmt::Display for Description {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.distance == 0 {
+            write!(f, "{}", self.tag)
+        } else {
+            write!(f, "{}-{}-g{}", self.tag, self.distance, self.short_sha)
+        }
+    }
+}
+
+/// Describe HEAD relative to the nearest reachable tag.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The git repository cannot be discovered
+/// - HEAD does not point to a valid commit
+/// - No (matching) tag is reachable from HEAD
+/// - The format is invalid
+///
+/// # Examples
+///
+/// ```no_run
+/// use cargo_version_info::commands::{
+///     DescribeArgs,
+///     describe,
+/// };
+/// use clap::Parser;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let args = DescribeArgs::parse_from(&["cargo", "version-info", "describe"]);
+/// describe(args)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Example Output
+///
+/// With `--format version`:
+/// ```text
+/// v1.2.0-5-gabc1234
+/// ```
+///
+/// With `--format json`:
+/// ```json
+/// {"describe":"v1.2.0-5-gabc1234","tag":"v1.2.0","distance":5,"sha":"abc1234"}
+/// ```
+pub fn describe(args: DescribeArgs) -> Result<()> {
+    let description = describe_head(
+        &args.repo_path,
+        args.match_pattern.as_deref(),
+        args.sha_length,
+    )?
+    .with_context(|| match &args.match_pattern {
+        Some(pattern) => format!("No tags matching '{}' are reachable from HEAD", pattern),
+        None => "No tags are reachable from HEAD".to_string(),
+    })?;
+
+    match args.format.as_str() {
+        "version" => println!("{}", description),
+        "json" => {
+            let output = DescribeOutput {
+                describe: &description.to_string(),
+                tag: &description.tag,
+                distance: description.distance,
+                sha: &description.short_sha,
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&output).context("Failed to serialize description")?
+            );
+        }
+        _ => anyhow::bail!("Invalid format: {}", args.format),
+    }
+
+    Ok(())
+}
+
+/// Find the nearest tag reachable from HEAD in the repository at `repo_path`.
+///
+/// Commits are visited breadth-first from HEAD and the first tagged commit
+/// wins. If several tags point at that commit, the highest semantic version
+/// is preferred, falling back to the lexically greatest name.
+///
+/// Returns `None` if no tag (matching `pattern`, if given) is reachable.
+/// `sha_length` controls the SHA abbreviation as in `--sha-length`.
+pub fn describe_head(
+    repo_path: &Path,
+    pattern: Option<&str>,
+    sha_length: Option<usize>,
+) -> Result<Option<Description>> {
+    let repo = gix::discover(repo_path).with_context(|| {
+        format!(
+            "Failed to discover git repository at {}",
+            repo_path.display()
+        )
+    })?;
+
+    let head = repo.head().context("Failed to read HEAD")?;
+    let head_id = head.id().context("HEAD does not point to a commit")?;
+
+    let tags_by_commit = collect_tags(&repo, pattern)?;
+    if tags_by_commit.is_empty() {
+        return Ok(None);
+    }
+
+    let mut nearest = None;
+    for info in repo.rev_walk([head_id]).all()? {
+        let id = info?.id;
+        if let Some(names) = tags_by_commit.get(&id) {
+            nearest = Some((id, best_tag(names)));
+            break;
+        }
+    }
+    let Some((tag_id, tag)) = nearest else {
+        return Ok(None);
+    };
+
+    // Count commits reachable from HEAD that are not reachable from the tag
+    let mut tag_ancestors = HashSet::new();
+    for info in repo.rev_walk([tag_id]).all()? {
+        tag_ancestors.insert(info?.id);
+    }
+    let mut distance = 0;
+    for info in repo.rev_walk([head_id]).all()? {
+        if !tag_ancestors.contains(&info?.id) {
+            distance += 1;
+        }
+    }
+
+    let short_sha = abbreviate_sha(head_id, sha_length)?;
+
+    Ok(Some(Description {
+        tag,
+        distance,
+        short_sha,
+    }))
+}
+
+/// Map each tagged commit to the names of the tags pointing at it.
+fn collect_tags(
+    repo: &gix::Repository,
+    pattern: Option<&str>,
+) -> Result<HashMap<gix::ObjectId, Vec<String>>> {
+    let mut tags: HashMap<gix::ObjectId, Vec<String>> = HashMap::new();
+
+    let refs = repo.references().context("Failed to read git references")?;
+    for reference in refs.prefixed("refs/tags/")? {
+        let Ok(mut reference) = reference else {
+            continue;
+        };
+        let full_name = reference.name().as_bstr().to_string();
+        let name = full_name
+            .strip_prefix("refs/tags/")
+            .unwrap_or(&full_name)
+            .to_string();
+        if let Some(pattern) = pattern
+            && !glob_match(pattern, &name)
+        {
+            continue;
+        }
+
+        // Follow annotated tags to the commit they point at
+        let Ok(id) = reference.peel_to_id() else {
+            continue;
+        };
+        let Ok(object) = repo.find_object(id) else {
+            continue;
+        };
+        if object.kind != gix::object::Kind::Commit {
+            continue;
+        }
+
+        tags.entry(id.detach()).or_default().push(name);
+    }
+
+    Ok(tags)
+}
+
+/// Pick the preferred tag among several pointing at the same commit.
+fn best_tag(names: &[String]) -> String {
+    let version_key = |name: &str| {
+        let version = name
+            .strip_prefix('v')
+            .or_else(|| name.strip_prefix('V'))
+            .unwrap_or(name);
+        parse_version(version).ok()
+    };
+
+    names
+        .iter()
+        .max_by(|a, b| version_key(a).cmp(&version_key(b)).then_with(|| a.cmp(b)))
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Match `text` against a glob `pattern` supporting `*` and `?`.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the last `*` swallow one more character
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn create_repo() -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["config", "user.name", "Test User"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+        git(dir.path(), &["config", "commit.gpgsign", "false"]);
+        git(dir.path(), &["config", "tag.gpgsign", "false"]);
+        dir
+    }
+
+    fn commit(dir: &Path, message: &str) {
+        git(dir, &["commit", "-q", "--allow-empty", "-m", message]);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("v*", "v1.2.0"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("v?.*", "v1.2.0"));
+        assert!(glob_match("release-*-final", "release-1.0-final"));
+        assert!(!glob_match("v*", "1.2.0"));
+        assert!(!glob_match("v?.0", "v10.0"));
+    }
+
+    #[test]
+    fn test_description_display() {
+        let on_tag = Description {
+            tag: "v1.2.0".to_string(),
+            distance: 0,
+            short_sha: "abc1234".to_string(),
+        };
+        assert_eq!(on_tag.to_string(), "v1.2.0");
+
+        let after_tag = Description {
+            distance: 5,
+            ..on_tag
+        };
+        assert_eq!(after_tag.to_string(), "v1.2.0-5-gabc1234");
+    }
+
+    #[test]
+    fn test_describe_head_counts_commits_since_tag() {
+        let dir = create_repo();
+        commit(dir.path(), "first");
+        git(dir.path(), &["tag", "v0.1.0"]);
+        commit(dir.path(), "second");
+        git(dir.path(), &["tag", "-a", "v0.2.0", "-m", "annotated"]);
+        commit(dir.path(), "third");
+        commit(dir.path(), "fourth");
+
+        let description = describe_head(dir.path(), None, None).unwrap().unwrap();
+        assert_eq!(description.tag, "v0.2.0");
+        assert_eq!(description.distance, 2);
+        assert!(
+            description
+                .to_string()
+                .starts_with(&format!("v0.2.0-2-g{}", description.short_sha))
+        );
+    }
+
+    #[test]
+    fn test_describe_head_on_tag() {
+        let dir = create_repo();
+        commit(dir.path(), "first");
+        git(dir.path(), &["tag", "v1.0.0"]);
+
+        let description = describe_head(dir.path(), None, None).unwrap().unwrap();
+        assert_eq!(description.to_string(), "v1.0.0");
+    }
+
+    #[test]
+    fn test_describe_head_sha_length() {
+        let dir = create_repo();
+        commit(dir.path(), "first");
+        git(dir.path(), &["tag", "v1.0.0"]);
+        commit(dir.path(), "second");
+
+        let description = describe_head(dir.path(), None, Some(12)).unwrap().unwrap();
+        assert_eq!(description.short_sha.len(), 12);
+    }
+
+    #[test]
+    fn test_describe_head_with_match_pattern() {
+        let dir = create_repo();
+        commit(dir.path(), "first");
+        git(dir.path(), &["tag", "v1.0.0"]);
+        commit(dir.path(), "second");
+        git(dir.path(), &["tag", "nightly"]);
+
+        let description = describe_head(dir.path(), Some("v*"), None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(description.tag, "v1.0.0");
+        assert_eq!(description.distance, 1);
+
+        assert!(
+            describe_head(dir.path(), Some("release-*"), None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_describe_head_no_tags() {
+        let dir = create_repo();
+        commit(dir.path(), "first");
+
+        assert!(describe_head(dir.path(), None, None).unwrap().is_none());
+    }
+}
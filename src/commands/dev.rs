@@ -14,6 +14,9 @@
 //!
 //! # Use a different repository path
 //! cargo version-info dev --repo-path /path/to/repo
+//!
+//! # Use a 12-character SHA
+//! cargo version-info dev --sha-length 12
 //! ```
 
 use std::path::PathBuf;
@@ -23,6 +26,10 @@ use anyhow::{
     Result,
 };
 use clap::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::common::abbreviate_sha;
 
 /// Arguments for the `dev` command.
 #[derive(Parser, Debug)]
@@ -34,6 +41,13 @@ pub struct DevArgs {
     #[arg(long, default_value = ".")]
     repo_path: PathBuf,
 
+    /// Number of hex characters in the SHA (clamped to 4..=40).
+    ///
+    /// Defaults to gix's automatic length, the shortest unambiguous
+    /// abbreviation.
+    #[arg(long, value_name = "N")]
+    sha_length: Option<usize>,
+
     /// Output format for the dev version.
     ///
     /// - `version`: Print just the dev version (e.g., "0.0.0-dev-a1b2c3d")
@@ -42,6 +56,15 @@ pub struct DevArgs {
     format: String,
 }
 
+/// Dev version and SHA, printed with `--format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct DevOutput<'a> {
+    /// The dev version (`0.0.0-dev-<short-sha>`).
+    version: &'a str,
+    /// Short SHA of HEAD.
+    sha: &'a str,
+}
+
 /// Generate a development version from the current git commit SHA.
 ///
 /// Reads the HEAD commit from the git repository and generates a version
@@ -92,18 +115,22 @@ pub fn dev(args: DevArgs) -> Result<()> {
 
     let head = repo.head().context("Failed to read HEAD")?;
     let commit_id = head.id().context("HEAD does not point to a commit")?;
-    let short_sha = commit_id
-        .shorten()
-        .context("Failed to shorten commit SHA")?;
+    let short_sha = abbreviate_sha(commit_id, args.sha_length)?;
 
     let dev_version = format!("0.0.0-dev-{}", short_sha);
 
     match args.format.as_str() {
         "version" => println!("{}", dev_version),
-        "json" => println!(
-            "{{\"version\":\"{}\",\"sha\":\"{}\"}}",
-            dev_version, short_sha
-        ),
+        "json" => {
+            let output = DevOutput {
+                version: &dev_version,
+                sha: &short_sha,
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&output).context("Failed to serialize dev version")?
+            );
+        }
         _ => anyhow::bail!("Invalid format: {}", args.format),
     }
 
@@ -119,6 +146,7 @@ mod tests {
         // Test with current directory (should work if run from git repo)
         let args = DevArgs {
             repo_path: ".".into(),
+            sha_length: None,
             format: "version".to_string(),
         };
         // This will only work if run from a git repository
@@ -140,6 +168,7 @@ mod tests {
     fn test_dev_json_format() {
         let args = DevArgs {
             repo_path: ".".into(),
+            sha_length: None,
             format: "json".to_string(),
         };
         // Same as above - will work if in git repo, otherwise fail gracefully
@@ -150,6 +179,7 @@ mod tests {
     fn test_dev_invalid_format() {
         let args = DevArgs {
             repo_path: ".".into(),
+            sha_length: None,
             format: "invalid".to_string(),
         };
         // Should fail on invalid format even if repo is valid
@@ -175,6 +205,7 @@ mod tests {
     fn test_dev_nonexistent_repo() {
         let args = DevArgs {
             repo_path: "/nonexistent/path".into(),
+            sha_length: None,
             format: "version".to_string(),
         };
         assert!(dev(args).is_err());
@@ -25,6 +25,8 @@ use anyhow::{
 };
 use clap::Parser;
 
+use super::common::VersionOutput;
+
 /// Arguments for the `dioxus` command.
 #[derive(Parser, Debug)]
 pub struct DioxusArgs {
@@ -115,7 +117,11 @@ pub fn dioxus(args: DioxusArgs) -> Result<()> {
 
     match args.format.as_str() {
         "version" => println!("{}", version),
-        "json" => println!("{{\"version\":\"{}\"}}", version),
+        "json" => println!(
+            "{}",
+            serde_json::to_string(&VersionOutput { version })
+                .context("Failed to serialize version")?
+        ),
         _ => anyhow::bail!("Invalid format: {}", args.format),
     }
 
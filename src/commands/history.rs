@@ -0,0 +1,322 @@
+//! List version changes from git history command.
+//!
+//! This command walks the commits reachable from HEAD, reads `Cargo.toml` at
+//! each commit that touched it and reports the commits where the version
+//! actually changed. It gives a quick timeline of releases without having to
+//! maintain a changelog.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Show all version changes, newest first
+//! cargo version-info history
+//!
+//! # Only the last five version changes
+//! cargo version-info history --limit 5
+//!
+//! # Get JSON output
+//! cargo version-info history --format json
+//! ```
+
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use anyhow::{
+    Context,
+    Result,
+};
+use clap::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::release_page::format_date;
+
+/// Arguments for the `history` command.
+#[derive(Parser, Debug)]
+pub struct HistoryArgs {
+    /// Path to the Cargo.toml manifest file (standard cargo flag).
+    ///
+    /// Defaults to `./Cargo.toml`. The git repository is discovered from the
+    /// manifest's directory.
+    #[arg(long)]
+    manifest_path: Option<PathBuf>,
+
+    /// Maximum number of version changes to print.
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+
+    /// Output format for the history.
+    ///
+    /// - `text`: One `version — date — commit` line per change
+    /// - `json`: JSON array with version, date and sha fields
+    #[arg(long, default_value = "text")]
+    format: String,
+}
+
+/// A commit where the manifest version changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct VersionChange {
+    /// The version introduced by this commit.
+    pub version: String,
+    /// Commit date as `YYYY-MM-DD` (UTC).
+    pub date: String,
+    /// Full commit SHA.
+    pub sha: String,
+    /// Abbreviated commit SHA.
+    pub short_sha: String,
+}
+
+/// Print the version history of a manifest.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The git repository cannot be discovered
+/// - The manifest is outside the repository's working tree
+/// - HEAD does not point to a valid commit
+/// - The format is invalid
+///
+/// # Example Output
+///
+/// With `--format text`:
+/// ```text
+/// 0.2.0 — 2025-11-03 — a1b2c3d
+/// 0.1.0 — 2025-10-01 — e4f5a6b
+/// ```
+pub fn history(args: HistoryArgs) -> Result<()> {
+    let manifest_path = args
+        .manifest_path
+        .unwrap_or_else(|| PathBuf::from("./Cargo.toml"));
+
+    let mut changes = version_history(&manifest_path)?;
+    if let Some(limit) = args.limit {
+        changes.truncate(limit);
+    }
+
+    match args.format.as_str() {
+        "text" => {
+            for change in &changes {
+                println!(
+                    "{} — {} — {}",
+                    change.version, change.date, change.short_sha
+                );
+            }
+        }
+        "json" => println!(
+            "{}",
+            serde_json::to_string(&changes).context("Failed to serialize history")?
+        ),
+        _ => anyhow::bail!("Invalid format: {}", args.format),
+    }
+
+    Ok(())
+}
+
+/// Collect the commits where the version in `manifest_path` changed.
+///
+/// Commits are returned newest first. A commit is included when the manifest
+/// blob differs from its first parent's and the parsed version (from
+/// `[package]` or `[workspace.package]`) differs as well; formatting-only or
+/// dependency-only edits are skipped.
+pub fn version_history(manifest_path: &Path) -> Result<Vec<VersionChange>> {
+    let manifest_dir = manifest_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let repo = gix::discover(manifest_dir).with_context(|| {
+        format!(
+            "Failed to discover git repository at {}",
+            manifest_dir.display()
+        )
+    })?;
+    let workdir = repo
+        .workdir()
+        .context("Repository has no working directory")?;
+
+    let manifest_abs = manifest_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", manifest_path.display()))?;
+    let workdir_abs = workdir
+        .canonicalize()
+        .context("Failed to resolve repository working directory")?;
+    let rel_path = manifest_abs
+        .strip_prefix(&workdir_abs)
+        .with_context(|| {
+            format!(
+                "{} is not inside the repository at {}",
+                manifest_path.display(),
+                workdir.display()
+            )
+        })?
+        .to_path_buf();
+
+    let head = repo.head().context("Failed to read HEAD")?;
+    let head_id = head.id().context("HEAD does not point to a commit")?;
+
+    let mut changes = Vec::new();
+    for info in repo.rev_walk([head_id]).all()? {
+        let info = info?;
+        let commit = repo
+            .find_object(info.id)
+            .context("Failed to find commit object")?
+            .try_into_commit()
+            .context("Object is not a commit")?;
+
+        let Some(blob_id) = manifest_blob_id(&commit, &rel_path)? else {
+            continue;
+        };
+        let parent_blob_id = match commit.parent_ids().next() {
+            Some(parent_id) => {
+                let parent = repo
+                    .find_object(parent_id)
+                    .context("Failed to find parent commit")?
+                    .try_into_commit()
+                    .context("Parent is not a commit")?;
+                manifest_blob_id(&parent, &rel_path)?
+            }
+            None => None,
+        };
+        if parent_blob_id == Some(blob_id) {
+            // Manifest not touched by this commit
+            continue;
+        }
+
+        let Some(version) = version_in_blob(&repo, blob_id)? else {
+            continue;
+        };
+        let parent_version = match parent_blob_id {
+            Some(id) => version_in_blob(&repo, id)?,
+            None => None,
+        };
+        if parent_version.as_deref() == Some(version.as_str()) {
+            continue;
+        }
+
+        let seconds = commit.time().map(|time| time.seconds).unwrap_or(0);
+        let short_sha = info
+            .id()
+            .shorten()
+            .context("Failed to shorten commit SHA")?;
+        changes.push(VersionChange {
+            version,
+            date: format_date(seconds),
+            sha: info.id.to_string(),
+            short_sha: short_sha.to_string(),
+        });
+    }
+
+    Ok(changes)
+}
+
+/// Look up the blob id of the manifest in a commit's tree.
+fn manifest_blob_id(commit: &gix::Commit<'_>, rel_path: &Path) -> Result<Option<gix::ObjectId>> {
+    let tree = commit.tree().context("Failed to read commit tree")?;
+    let entry = tree
+        .lookup_entry_by_path(rel_path)
+        .with_context(|| format!("Failed to look up {}", rel_path.display()))?;
+    Ok(entry.map(|entry| entry.object_id()))
+}
+
+/// Read a manifest blob and extract its version.
+fn version_in_blob(repo: &gix::Repository, blob_id: gix::ObjectId) -> Result<Option<String>> {
+    let blob = repo
+        .find_object(blob_id)
+        .context("Failed to find manifest blob")?;
+    let content = String::from_utf8_lossy(&blob.data);
+    Ok(manifest_version(&content))
+}
+
+/// Extract the version from manifest content.
+///
+/// Checks `[package]` first, then `[workspace.package]`. Returns `None` if
+/// the content is not valid TOML or carries no literal version (e.g.
+/// `version.workspace = true`).
+fn manifest_version(content: &str) -> Option<String> {
+    let doc: toml::Value = toml::from_str(content).ok()?;
+    let package_version = doc
+        .get("package")
+        .and_then(|package| package.get("version"))
+        .and_then(|version| version.as_str());
+    let workspace_version = doc
+        .get("workspace")
+        .and_then(|workspace| workspace.get("package"))
+        .and_then(|package| package.get("version"))
+        .and_then(|version| version.as_str());
+
+    package_version.or(workspace_version).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn commit_manifest(dir: &Path, content: &str, message: &str) {
+        std::fs::write(dir.join("Cargo.toml"), content).unwrap();
+        git(dir, &["add", "Cargo.toml"]);
+        git(dir, &["commit", "-q", "--allow-empty", "-m", message]);
+    }
+
+    #[test]
+    fn test_manifest_version() {
+        assert_eq!(
+            manifest_version("[package]\nname = \"a\"\nversion = \"1.2.3\"\n"),
+            Some("1.2.3".to_string())
+        );
+        assert_eq!(
+            manifest_version("[workspace.package]\nversion = \"0.4.0\"\n"),
+            Some("0.4.0".to_string())
+        );
+        assert_eq!(
+            manifest_version("[package]\nname = \"a\"\nversion.workspace = true\n"),
+            None
+        );
+        assert_eq!(manifest_version("not toml ["), None);
+    }
+
+    #[test]
+    fn test_version_history() {
+        let dir = tempfile::tempdir().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["config", "user.name", "Test User"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+        git(dir.path(), &["config", "commit.gpgsign", "false"]);
+
+        commit_manifest(
+            dir.path(),
+            "[package]\nname = \"a\"\nversion = \"0.1.0\"\n",
+            "initial",
+        );
+        commit_manifest(
+            dir.path(),
+            "[package]\nname = \"a\"\nversion = \"0.1.0\"\n\n[dependencies]\n",
+            "deps only",
+        );
+        commit_manifest(
+            dir.path(),
+            "[package]\nname = \"a\"\nversion = \"0.2.0\"\n\n[dependencies]\n",
+            "bump",
+        );
+        std::fs::write(dir.path().join("README.md"), "readme").unwrap();
+        git(dir.path(), &["add", "README.md"]);
+        git(dir.path(), &["commit", "-q", "-m", "docs"]);
+
+        let changes = version_history(&dir.path().join("Cargo.toml")).unwrap();
+        let versions: Vec<&str> = changes.iter().map(|c| c.version.as_str()).collect();
+        assert_eq!(versions, ["0.2.0", "0.1.0"]);
+        assert_eq!(changes[0].date.len(), "YYYY-MM-DD".len());
+        assert!(changes[0].sha.starts_with(&changes[0].short_sha));
+    }
+}
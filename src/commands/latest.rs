@@ -17,14 +17,20 @@
 //!
 //! # Specify repository explicitly
 //! cargo version-info latest --owner owner --repo repo
+//!
+//! # Bypass the on-disk release cache
+//! cargo version-info latest --no-cache
 //! ```
 
+use std::time::Duration;
+
 use anyhow::{
     Context,
     Result,
 };
-use cargo_plugin_utils::common::get_owner_repo;
 use clap::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
 
 use crate::github;
 use crate::version::{
@@ -56,6 +62,24 @@ pub struct LatestArgs {
     #[arg(long, env = "GITHUB_TOKEN")]
     github_token: Option<String>,
 
+    /// Tag prefix for per-crate release tags (e.g., `foo-v` for `foo-v1.2.3`).
+    ///
+    /// Only tags starting with the prefix are considered, and the prefix is
+    /// stripped before parsing. Defaults to stripping a leading `v`/`V`.
+    #[arg(long)]
+    tag_prefix: Option<String>,
+
+    /// Always query GitHub instead of using the on-disk release cache.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Maximum age in seconds of a cached release lookup.
+    ///
+    /// Lookups are cached in the cargo target directory so tight CI loops do
+    /// not query the GitHub API on every invocation.
+    #[arg(long, value_name = "SECONDS", default_value_t = github::DEFAULT_RELEASE_CACHE_TTL.as_secs())]
+    cache_ttl: u64,
+
     /// Output format for the version.
     ///
     /// - `version`: Print just the version number (e.g., "0.1.2")
@@ -63,6 +87,19 @@ pub struct LatestArgs {
     /// - `json`: Print JSON with version and tag fields
     #[arg(long, default_value = "version")]
     format: String,
+
+    /// Timeout of each GitHub API request, from the global `--timeout` flag.
+    #[arg(skip)]
+    pub timeout: Option<Duration>,
+}
+
+/// Latest version and its tag, printed with `--format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct LatestOutput<'a> {
+    /// The latest published version (`0.0.0` if there is none).
+    version: &'a str,
+    /// The corresponding git tag.
+    tag: &'a str,
 }
 
 /// Get the latest GitHub release version for a repository.
@@ -118,15 +155,29 @@ pub struct LatestArgs {
 /// {"version":"0.1.2","tag":"v0.1.2"}
 /// ```
 pub fn latest(args: LatestArgs) -> Result<()> {
-    let (owner, repo) = get_owner_repo(args.owner, args.repo)?;
+    let (owner, repo) = github::resolve_owner_repo(args.owner, args.repo)?;
     let github_token = args.github_token.as_deref();
 
     let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
-    let latest = rt.block_on(github::get_latest_release_version(
-        &owner,
-        &repo,
-        github_token,
-    ))?;
+    let tag_prefix = args.tag_prefix.as_deref();
+    let latest = if args.no_cache {
+        rt.block_on(github::get_latest_release_version(
+            &owner,
+            &repo,
+            github_token,
+            tag_prefix,
+            args.timeout,
+        ))?
+    } else {
+        rt.block_on(github::get_latest_release_version_cached(
+            &owner,
+            &repo,
+            github_token,
+            tag_prefix,
+            Duration::from_secs(args.cache_ttl),
+            args.timeout,
+        ))?
+    };
 
     let latest = latest.unwrap_or_else(|| "0.0.0".to_string());
 
@@ -137,10 +188,15 @@ pub fn latest(args: LatestArgs) -> Result<()> {
             println!("{}", format_tag(major, minor, patch));
         }
         "json" => {
-            println!("{{\"version\":\"{}\",\"tag\":\"{}\"}}", latest, {
-                let (major, minor, patch) = parse_version(&latest)?;
-                format_tag(major, minor, patch)
-            });
+            let (major, minor, patch) = parse_version(&latest)?;
+            let output = LatestOutput {
+                version: &latest,
+                tag: &format_tag(major, minor, patch),
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&output).context("Failed to serialize latest version")?
+            );
         }
         _ => anyhow::bail!("Invalid format: {}", args.format),
     }
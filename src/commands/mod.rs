@@ -1,14 +1,19 @@
 //! Command implementations.
 
-mod badge;
+pub(crate) mod badge;
+mod badges;
 mod build_version;
 pub mod bump;
+mod cache;
 mod changed;
 pub mod changelog;
+pub(crate) mod common;
 mod compare;
 mod current;
+mod describe;
 mod dev;
 mod dioxus;
+mod history;
 mod latest;
 mod next;
 mod post_bump_hook;
@@ -16,24 +21,52 @@ mod pr_log;
 mod pre_bump_hook;
 mod release_page;
 mod rust_toolchain;
+mod schema;
+mod set_edition;
 mod tag;
 mod update_readme;
 
 // Re-export all command argument structs
 pub use badge::{
+    BADGE_NAMES,
+    Badge,
     BadgeArgs,
+    BadgeFormat,
+    BadgeOptions,
+    BadgeOutputFormat,
+    CacheOptions,
+    CoverageTool,
     badge,
+    find_package,
+    generate_badges,
+};
+pub use badges::{
+    BADGE_TYPES,
+    BadgesArgs,
+    badges,
 };
 pub use build_version::{
     BuildVersionArgs,
+    BuildVersionOutput,
+    VersionSource,
+    build_env_directives,
     build_version,
     build_version_default,
     build_version_for_repo,
     compute_version_string,
+    emit_build_version,
 };
 pub use bump::{
     BumpArgs,
+    BumpOutcome,
+    VersionTarget,
     bump,
+    bump_version,
+};
+pub use cache::{
+    CacheArgs,
+    CacheSubcommand,
+    cache,
 };
 pub use changed::{
     ChangedArgs,
@@ -41,7 +74,13 @@ pub use changed::{
 };
 pub use changelog::{
     ChangelogArgs,
+    CommitCounts,
     changelog,
+    changelog_counts,
+};
+pub use common::{
+    CargoOptions,
+    find_workspace_member,
 };
 pub use compare::{
     CompareArgs,
@@ -51,6 +90,12 @@ pub use current::{
     CurrentArgs,
     current,
 };
+pub use describe::{
+    DescribeArgs,
+    Description,
+    describe,
+    describe_head,
+};
 pub use dev::{
     DevArgs,
     dev,
@@ -59,6 +104,12 @@ pub use dioxus::{
     DioxusArgs,
     dioxus,
 };
+pub use history::{
+    HistoryArgs,
+    VersionChange,
+    history,
+    version_history,
+};
 pub use latest::{
     LatestArgs,
     latest,
@@ -86,6 +137,16 @@ pub use rust_toolchain::{
     RustToolchainArgs,
     rust_toolchain,
 };
+pub use schema::{
+    SCHEMA_COMMANDS,
+    SchemaArgs,
+    schema,
+};
+pub use set_edition::{
+    EDITIONS,
+    SetEditionArgs,
+    set_edition,
+};
 pub use tag::{
     TagArgs,
     tag,
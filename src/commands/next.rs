@@ -17,17 +17,23 @@
 //!
 //! # Use in GitHub Actions (writes to GITHUB_OUTPUT)
 //! cargo version-info next --format github-actions
+//!
+//! # Monorepo with per-crate tags (e.g., "foo-v0.1.3")
+//! cargo version-info next --tag-prefix foo-v --format tag
 //! ```
 
 use anyhow::{
     Context,
     Result,
 };
-use cargo_plugin_utils::common::get_owner_repo;
 use clap::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
 
+use super::common::write_github_outputs;
 use crate::github;
 use crate::version::{
+    apply_prefix,
     format_tag,
     parse_version,
 };
@@ -56,6 +62,23 @@ pub struct NextArgs {
     #[arg(long, env = "GITHUB_TOKEN")]
     github_token: Option<String>,
 
+    /// Tag prefix for per-crate release tags (e.g., `foo-v` for `foo-v1.2.3`).
+    ///
+    /// Only tags starting with the prefix are considered, and the prefix is
+    /// stripped before parsing. Defaults to stripping a leading `v`/`V`.
+    #[arg(long)]
+    tag_prefix: Option<String>,
+
+    /// Prefix to prepend to printed versions (e.g. `v` for `v1.2.3`).
+    ///
+    /// A `v`/`V` already on the version is replaced, never doubled.
+    #[arg(long, value_name = "PREFIX")]
+    prefix: Option<String>,
+
+    /// Print bare versions without any prefix (the default).
+    #[arg(long, conflicts_with = "prefix")]
+    no_prefix: bool,
+
     /// Output format for the version information.
     ///
     /// - `version`: Print just the next version number (e.g., "0.1.3")
@@ -73,6 +96,17 @@ pub struct NextArgs {
     github_output: Option<String>,
 }
 
+/// Latest and next version, printed with `--format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct NextOutput<'a> {
+    /// The latest published version.
+    latest: &'a str,
+    /// The next version.
+    next: &'a str,
+    /// The git tag for the next version.
+    next_tag: &'a str,
+}
+
 /// Calculate the next patch version from the latest GitHub release.
 ///
 /// Queries the GitHub Releases API to find the latest release version,
@@ -133,35 +167,58 @@ pub struct NextArgs {
 /// next_tag=v0.1.3
 /// ```
 pub fn next(args: NextArgs) -> Result<()> {
-    let (owner, repo) = get_owner_repo(args.owner, args.repo)?;
+    let (owner, repo) = github::resolve_owner_repo(args.owner, args.repo)?;
     let github_token = args.github_token.as_deref();
 
     let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
-    let (latest, next) =
-        rt.block_on(github::calculate_next_version(&owner, &repo, github_token))?;
+    let (latest, next) = rt.block_on(github::calculate_next_version(
+        &owner,
+        &repo,
+        github_token,
+        args.tag_prefix.as_deref(),
+        false,
+    ))?;
+
+    let next_tag = match &args.tag_prefix {
+        Some(prefix) => format!("{}{}", prefix, next),
+        None => {
+            let (major, minor, patch) = parse_version(&next)?;
+            format_tag(major, minor, patch)
+        }
+    };
 
-    let next_tag = {
-        let (major, minor, patch) = parse_version(&next)?;
-        format_tag(major, minor, patch)
+    // The prefix applies to versions only; tags keep their own format
+    let prefix = if args.no_prefix {
+        None
+    } else {
+        args.prefix.as_deref()
     };
+    let latest = apply_prefix(&latest, prefix);
+    let next = apply_prefix(&next, prefix);
 
     match args.format.as_str() {
         "version" => println!("{}", next),
         "tag" => println!("{}", next_tag),
         "json" => {
+            let output = NextOutput {
+                latest: &latest,
+                next: &next,
+                next_tag: &next_tag,
+            };
             println!(
-                "{{\"latest\":\"{}\",\"next\":\"{}\",\"next_tag\":\"{}\"}}",
-                latest, next, next_tag
+                "{}",
+                serde_json::to_string(&output).context("Failed to serialize next version")?
             );
         }
         "github-actions" => {
-            let output_file = args.github_output.as_deref().unwrap_or("/dev/stdout");
-            let output = format!(
-                "latest_version={}\nnext_version={}\nnext_tag={}\n",
-                latest, next, next_tag
-            );
-            std::fs::write(output_file, output)
-                .with_context(|| format!("Failed to write to {}", output_file))?;
+            write_github_outputs(
+                args.github_output.as_deref(),
+                &[
+                    ("latest_version", latest.as_str()),
+                    ("next_version", next.as_str()),
+                    ("next_tag", next_tag.as_str()),
+                ],
+            )?;
         }
         _ => anyhow::bail!("Invalid format: {}", args.format),
     }
@@ -25,6 +25,8 @@
 //! # Warn only, don't fail (for testing)
 //! cargo version-info post-bump-hook --exit-on-error false
 //! ```
+//!
+//! A failed verification exits with code 11 (see [`crate::exit_code`]).
 
 use std::path::PathBuf;
 
@@ -35,6 +37,11 @@ use anyhow::{
 use cargo_plugin_utils::common::get_package_version_from_manifest;
 use clap::Parser;
 
+use crate::exit_code::{
+    ExitCode,
+    ExitError,
+};
+
 /// Arguments for the `post-bump-hook` command.
 #[derive(Parser, Debug)]
 pub struct PostBumpHookArgs {
@@ -154,7 +161,11 @@ pub fn post_bump_hook(args: PostBumpHookArgs) -> Result<()> {
                 cargo_version, target_trimmed
             );
             if args.exit_on_error {
-                anyhow::bail!("Version bump verification failed");
+                return Err(ExitError::new(
+                    ExitCode::VersionDrift,
+                    "Version bump verification failed",
+                )
+                .into());
             }
         } else {
             logger.print_message(&format!("✓ Version bump verified: {}", cargo_version));
@@ -173,7 +184,11 @@ pub fn post_bump_hook(args: PostBumpHookArgs) -> Result<()> {
                 cargo_version
             );
             if args.exit_on_error {
-                anyhow::bail!("Version bump appears to have failed");
+                return Err(ExitError::new(
+                    ExitCode::VersionDrift,
+                    "Version bump appears to have failed",
+                )
+                .into());
             }
         } else {
             logger.print_message(&format!("  Previous version: {}", previous_trimmed));
@@ -3,6 +3,11 @@
 //! This command generates a markdown list of merged pull requests since
 //! a given tag, useful for release notes.
 //!
+//! With `--no-network`, pull requests are read from local git history
+//! instead of the GitHub API: every merge commit whose subject looks like
+//! `Merge pull request #123 from owner/branch` is listed, with the PR title
+//! taken from the first line of the commit body.
+//!
 //! # Examples
 //!
 //! ```bash
@@ -12,13 +17,31 @@
 //! # Generate PR log since specific tag
 //! cargo version-info pr-log --since-tag v0.1.0
 //!
+//! # Build the PR log from local merge commits only
+//! cargo version-info pr-log --no-network
+//!
 //! # Output to file
 //! cargo version-info pr-log --output PR_LOG.md
+//!
+//! # Only the 20 newest pull requests, followed by "…and more"
+//! cargo version-info pr-log --no-network --max-items 20
 //! ```
 
-use anyhow::Result;
+use std::io::Write;
+
+use anyhow::{
+    Context,
+    Result,
+};
 use clap::Parser;
 
+use super::changelog::{
+    latest_version_tag,
+    resolve_to_commit_oid,
+    truncate_items,
+};
+use crate::github;
+
 /// Arguments for the `pr-log` command.
 #[derive(Parser, Debug)]
 pub struct PrLogArgs {
@@ -37,16 +60,182 @@ pub struct PrLogArgs {
     /// GitHub repository name.
     #[arg(long)]
     pub repo: Option<String>,
+
+    /// List merge commits from local git history instead of querying the
+    /// GitHub API.
+    #[arg(long)]
+    pub no_network: bool,
+
+    /// List at most this many pull requests (the newest ones), followed by
+    /// an "…and more" line if there are others.
+    ///
+    /// Unlimited by default. History is only read up to the first pull
+    /// request left out.
+    #[arg(long, value_name = "N")]
+    pub max_items: Option<usize>,
+}
+
+/// A pull request merged into the current history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PullRequest {
+    number: u64,
+    title: String,
 }
 
 /// Generate PR log from merged pull requests.
 ///
 /// # Note
 ///
-/// This command is currently a stub and not yet implemented. It will be
-/// available in a future release.
-pub fn pr_log(_args: PrLogArgs) -> Result<()> {
-    anyhow::bail!(
-        "PR log generation is not yet implemented. This feature will be available in a future release."
-    );
+/// Only the local mode (`--no-network`) is implemented so far; querying the
+/// GitHub API will be available in a future release.
+///
+/// # Errors
+///
+/// Returns an error if the git repository or the start tag cannot be read,
+/// the output cannot be written, or `--no-network` is not given.
+pub fn pr_log(args: PrLogArgs) -> Result<()> {
+    let output_path = args.output.clone();
+
+    let mut output = Vec::new();
+    writeln!(&mut output, "## Pull Requests\n")?;
+    generate_pr_log_to_writer(&mut output, &args)?;
+
+    if let Some(output_path) = output_path {
+        std::fs::write(&output_path, output)
+            .with_context(|| format!("Failed to write PR log to {}", output_path))?;
+        let mut logger = cargo_plugin_utils::logger::Logger::new();
+        logger.status("Written", &output_path);
+    } else {
+        std::io::stdout().write_all(&output)?;
+    }
+
+    Ok(())
+}
+
+/// Generate the PR log entries (without a heading) to a writer.
+pub fn generate_pr_log_to_writer(writer: &mut dyn Write, args: &PrLogArgs) -> Result<()> {
+    if !args.no_network {
+        anyhow::bail!(
+            "PR log generation from the GitHub API is not yet implemented. \
+             Pass --no-network to list merge commits from local history."
+        );
+    }
+
+    let git_repo = gix::discover(".").context("Failed to discover git repository")?;
+    let start_oid = match &args.since_tag {
+        Some(tag) => Some(
+            resolve_to_commit_oid(&git_repo, tag)
+                .with_context(|| format!("Failed to resolve tag: {}", tag))?,
+        ),
+        None => latest_version_tag(&git_repo)?,
+    };
+    let head = git_repo.head().context("Failed to read HEAD")?;
+    let head_oid = head.id().context("HEAD does not point to a commit")?;
+
+    let mut walk = git_repo.rev_walk([head_oid]);
+    if let Some(start) = start_oid {
+        walk = walk.with_hidden([start]);
+    }
+
+    let mut pull_requests = Vec::new();
+    for info in walk.all()? {
+        let info = info?;
+        let commit = git_repo
+            .find_object(info.id)
+            .context("Failed to find commit object")?
+            .try_into_commit()
+            .context("Object is not a commit")?;
+        let message_raw = commit
+            .message_raw()
+            .context("Failed to read raw commit message")?;
+        let message = String::from_utf8_lossy(message_raw.as_ref());
+        if let Some(pull_request) = parse_merge_commit(&message) {
+            pull_requests.push(pull_request);
+            // One pull request past the limit shows that more are left out
+            if args
+                .max_items
+                .is_some_and(|max_items| pull_requests.len() > max_items)
+            {
+                break;
+            }
+        }
+    }
+
+    // Link PR numbers when the GitHub repository is known (from --owner/--repo,
+    // GITHUB_REPOSITORY or the git remote)
+    let owner_repo = github::resolve_owner_repo(args.owner.clone(), args.repo.clone()).ok();
+
+    let truncated = truncate_items(&mut pull_requests, args.max_items) > 0;
+
+    if pull_requests.is_empty() {
+        writeln!(writer, "No pull requests found.")?;
+    }
+    for pull_request in &pull_requests {
+        let number = match &owner_repo {
+            Some((owner, repo)) => format!(
+                "[#{}](https://github.com/{}/{}/pull/{})",
+                pull_request.number, owner, repo, pull_request.number
+            ),
+            None => format!("#{}", pull_request.number),
+        };
+        writeln!(writer, "- {} ({})", pull_request.title, number)?;
+    }
+    if truncated {
+        // A blank line keeps the note out of the last list item
+        writeln!(writer, "\n…and more")?;
+    }
+
+    Ok(())
+}
+
+/// Parse a GitHub merge commit message.
+///
+/// The subject must look like `Merge pull request #123 from owner/branch`.
+/// The title is the first non-empty body line, falling back to the branch
+/// name when the body is empty.
+fn parse_merge_commit(message: &str) -> Option<PullRequest> {
+    let mut lines = message.lines();
+    let subject = lines.next()?.trim();
+    let rest = subject.strip_prefix("Merge pull request #")?;
+    let (number, branch) = match rest.split_once(' ') {
+        Some((number, from)) => (number, from.trim().strip_prefix("from ").unwrap_or("")),
+        None => (rest, ""),
+    };
+    let number = number.parse().ok()?;
+
+    let title = lines
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or(branch)
+        .to_string();
+
+    Some(PullRequest { number, title })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_merge_commit() {
+        assert_eq!(
+            parse_merge_commit(
+                "Merge pull request #123 from owner/feature\n\nAdd the feature\n\nDetails"
+            ),
+            Some(PullRequest {
+                number: 123,
+                title: "Add the feature".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_merge_commit("Merge pull request #7 from owner/fix-typo\n"),
+            Some(PullRequest {
+                number: 7,
+                title: "owner/fix-typo".to_string(),
+            })
+        );
+        assert_eq!(parse_merge_commit("Merge branch 'main' into feature"), None);
+        assert_eq!(parse_merge_commit("feat: add feature (#12)"), None);
+        assert_eq!(parse_merge_commit("Merge pull request #abc from x/y"), None);
+    }
 }
@@ -24,6 +24,9 @@
 //! # Allow bump even if checks fail (warn only)
 //! cargo version-info pre-bump-hook --exit-on-error false
 //! ```
+//!
+//! A version mismatch between Cargo.toml and the latest tag exits with code
+//! 11 (see [`crate::exit_code`]).
 
 use std::path::PathBuf;
 
@@ -34,6 +37,10 @@ use anyhow::{
 use cargo_plugin_utils::common::get_package_version_from_manifest;
 use clap::Parser;
 
+use crate::exit_code::{
+    ExitCode,
+    ExitError,
+};
 use crate::version::parse_version;
 
 /// Arguments for the `pre-bump-hook` command.
@@ -197,9 +204,11 @@ pub fn pre_bump_hook(args: PreBumpHookArgs) -> Result<()> {
             cargo_version, latest_tag_version
         );
         if args.exit_on_error {
-            anyhow::bail!(
-                "Version mismatch detected. Sync Cargo.toml with git tags before bumping."
-            );
+            return Err(ExitError::new(
+                ExitCode::VersionDrift,
+                "Version mismatch detected. Sync Cargo.toml with git tags before bumping.",
+            )
+            .into());
         }
     }
 
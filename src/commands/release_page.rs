@@ -12,20 +12,52 @@
 //! # Generate since specific tag
 //! cargo version-info release-page --since-tag v0.1.0
 //!
-//! # Skip network requests for badges
+//! # Generate fully offline (badge heuristics, PR log from merge commits)
 //! cargo version-info release-page --no-network
 //!
+//! # Include a table of contents
+//! cargo version-info release-page --toc
+//!
+//! # Add the release date and compared range under the title
+//! cargo version-info release-page --metadata --since-tag v0.1.0
+//!
+//! # Generate an AsciiDoc release page
+//! cargo version-info release-page --format asciidoc --output RELEASE.adoc
+//!
 //! # Output to file
 //! cargo version-info release-page --output RELEASE.md
+//!
+//! # Use in GitHub Actions (multi-line `release_page` output)
+//! cargo version-info release-page --format github-actions
+//!
+//! # Page plus a list of sections that failed, for pipelines
+//! cargo version-info release-page --format json
+//!
+//! # Custom layout with {{name}}, {{version}}, {{badges}}, {{pr_log}} and
+//! # {{changelog}} placeholders
+//! cargo version-info release-page --template .github/release-template.md
 //! ```
 
 use std::io::Write;
+use std::path::PathBuf;
 
 use anyhow::{
     Context,
     Result,
 };
 use clap::Parser;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::badge::{
+    BadgeFormat,
+    BadgeOptions,
+};
+use super::common::{
+    CargoOptions,
+    write_github_outputs,
+};
 
 /// Arguments for the `release-page` command.
 #[derive(Parser, Debug)]
@@ -49,7 +81,8 @@ pub struct ReleasePageArgs {
     #[arg(short, long)]
     pub output: Option<String>,
 
-    /// Skip network requests and use heuristics for badges.
+    /// Skip network requests: use heuristics for badges and build the PR
+    /// log from local merge commits.
     #[arg(long)]
     pub no_network: bool,
 
@@ -60,6 +93,73 @@ pub struct ReleasePageArgs {
     /// GitHub repository name (for linking commits/PRs).
     #[arg(long)]
     pub repo: Option<String>,
+
+    /// Insert a table of contents listing the page sections.
+    #[arg(long)]
+    pub toc: bool,
+
+    /// Add a metadata block with the release date (HEAD commit time) and the
+    /// compared range under the title.
+    #[arg(long)]
+    pub metadata: bool,
+
+    /// Output format for the release page.
+    ///
+    /// - `markdown`: Markdown document (default)
+    /// - `asciidoc`: AsciiDoc document with `image:` badge macros
+    /// - `github-actions`: Markdown written as a multi-line `release_page`
+    ///   output to the GITHUB_OUTPUT file
+    /// - `json`: `{"page": ..., "warnings": [...]}` with the Markdown page and
+    ///   the sections that failed to generate
+    #[arg(long, default_value = "markdown")]
+    pub format: String,
+
+    /// Path to GitHub Actions output file.
+    ///
+    /// Only used when `--format github-actions` is specified.
+    /// Defaults to the `GITHUB_OUTPUT` environment variable or stdout.
+    #[arg(long, env = "GITHUB_OUTPUT")]
+    pub github_output: Option<String>,
+
+    /// Lay out the page with a template file instead of the built-in layout.
+    ///
+    /// The placeholders `{{name}}`, `{{version}}`, `{{badges}}`,
+    /// `{{pr_log}}` and `{{changelog}}` are replaced with the generated
+    /// values; sections without a placeholder are left out. Unknown
+    /// placeholders are an error.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["toc", "metadata"])]
+    pub template: Option<PathBuf>,
+
+    /// Network access for cargo subprocesses, from the global `--offline`
+    /// and `--frozen` flags.
+    #[arg(skip)]
+    pub cargo: CargoOptions,
+
+    /// Timeout of each crates.io/docs.rs badge request, from the global
+    /// `--timeout` flag.
+    #[arg(skip)]
+    pub timeout: Option<std::time::Duration>,
+}
+
+/// Placeholders supported in `--template` files.
+const TEMPLATE_PLACEHOLDERS: &[&str] = &["name", "version", "badges", "pr_log", "changelog"];
+
+/// A release page section that could not be generated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct SectionWarning {
+    /// The section that degraded (e.g. `pr-log`, `changelog`).
+    pub section: String,
+    /// Why it failed.
+    pub message: String,
+}
+
+/// Release page with its warnings, printed with `--format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct ReleasePageOutput<'a> {
+    /// The rendered release page.
+    page: &'a str,
+    /// Sections that could not be generated.
+    warnings: &'a [SectionWarning],
 }
 
 /// Generate a complete release page.
@@ -75,8 +175,28 @@ async fn release_page_async(args: ReleasePageArgs) -> Result<()> {
 
     logger.status("Generating", "release page");
 
+    let github_actions = args.format == "github-actions";
+    let json = args.format == "json";
+    let format: BadgeFormat = if github_actions || json {
+        BadgeFormat::Markdown
+    } else {
+        args.format.parse()?
+    };
+
+    // Read and check the template before generating anything
+    let template = match &args.template {
+        Some(path) => {
+            let template = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read template {}", path.display()))?;
+            validate_template(&template)
+                .with_context(|| format!("Invalid template {}", path.display()))?;
+            Some(template)
+        }
+        None => None,
+    };
+
     // Find the package
-    let package = super::badge::find_package().await?;
+    let package = super::badge::find_package(args.cargo).await?;
 
     // Prepare output buffer
     let mut output = Vec::new();
@@ -101,6 +221,23 @@ async fn release_page_async(args: ReleasePageArgs) -> Result<()> {
         writeln!(&mut output, "{}\n", description)?;
     }
 
+    // Metadata block: release date and compared range
+    if args.metadata {
+        let package_dir = package
+            .manifest_path
+            .parent()
+            .map_or_else(|| std::path::Path::new("."), |dir| dir.as_std_path());
+        writeln!(
+            &mut output,
+            "- **Release date**: {}",
+            release_date(package_dir)
+        )?;
+        if let Some(range) = describe_range(&args, &version_display) {
+            writeln!(&mut output, "- **Changes**: {}", range)?;
+        }
+        writeln!(&mut output)?;
+    }
+
     // Add repository link if available
     if let Some(repository) = &package.repository {
         if repository.starts_with("https://github.com/") {
@@ -110,25 +247,52 @@ async fn release_page_async(args: ReleasePageArgs) -> Result<()> {
         }
     }
 
-    super::badge::badge_all(&mut output, &package, args.no_network).await?;
-    writeln!(&mut output)?;
+    // Badges are rendered directly in the target format
+    let mut badges = Vec::new();
+    let badge_options = BadgeOptions {
+        no_network: args.no_network,
+        cargo: args.cargo,
+        timeout: args.timeout,
+        ..BadgeOptions::default()
+    };
+    super::badge::badge_all(&mut badges, &package, &badge_options, format).await?;
+    writeln!(&mut badges)?;
+
+    // Sections are written to separate buffers so the table of contents can
+    // be built from their headings once everything has been generated, and
+    // so a template can place them
+    let mut pr_log = Vec::new();
+    let mut changelog = Vec::new();
+    // Sections that failed, reported with `--format json`
+    let mut warnings = Vec::new();
 
     // Section 2: PR Log (optional - skip if not available)
     logger.status("Generating", "PR log");
-    match generate_pr_log(&mut output, &args).await {
-        Ok(_) => {
-            writeln!(&mut output)?;
-        }
-        Err(_) => {
-            // PR log not implemented yet, skip silently
-            logger.warning("Skipping", "PR log (not yet implemented)");
+    let has_pr_log = match generate_pr_log(&mut pr_log, &args).await {
+        Ok(_) => true,
+        Err(e) => {
+            // Only the local (--no-network) PR log is implemented so far
+            logger.warning("Skipping", &format!("PR log: {:#}", e));
+            warnings.push(SectionWarning {
+                section: "pr-log".to_string(),
+                message: format!("{:#}", e),
+            });
+            pr_log.clear();
+            false
         }
-    }
+    };
 
     // Section 3: Changelog
     logger.status("Generating", "changelog");
-    writeln!(&mut output, "## What's Changed\n")?;
-    generate_changelog(&mut output, &args)?;
+    if let Err(e) = generate_changelog(&mut changelog, &args) {
+        // Keep the sections generated so far instead of aborting the page
+        logger.warning("Failed", &format!("changelog generation: {:#}", e));
+        writeln!(&mut changelog, "_Changelog generation failed: {:#}_", e)?;
+        warnings.push(SectionWarning {
+            section: "changelog".to_string(),
+            message: format!("{:#}", e),
+        });
+    }
 
     // Add full changelog link if we have repository info
     if let Some(repository) = &package.repository
@@ -141,24 +305,102 @@ async fn release_page_async(args: ReleasePageArgs) -> Result<()> {
                 let start_tag = parts[0].trim();
                 let end_tag = parts[1].trim();
                 writeln!(
-                    &mut output,
+                    &mut changelog,
                     "\n**Full Changelog**: [{}/compare/{}...{}]({}/compare/{}...{})\n",
                     repository, start_tag, end_tag, repository, start_tag, end_tag
                 )?;
             }
         } else if let Some(tag) = &args.since_tag {
             writeln!(
-                &mut output,
+                &mut changelog,
                 "\n**Full Changelog**: [{}/compare/{}...HEAD]({}/compare/{}...HEAD)\n",
                 repository, tag, repository, tag
             )?;
         }
     }
 
+    // Built-in layout: PR log, then changelog
+    let mut body = Vec::new();
+    if has_pr_log {
+        writeln!(&mut body, "## Pull Requests\n")?;
+        body.extend_from_slice(&pr_log);
+        writeln!(&mut body)?;
+    }
+    writeln!(&mut body, "## What's Changed\n")?;
+    body.extend_from_slice(&changelog);
+
+    let output = if let Some(template) = &template {
+        // Markdown sections are converted for AsciiDoc; badges already are
+        let section = |markdown: &[u8]| {
+            let markdown = String::from_utf8_lossy(markdown);
+            match format {
+                BadgeFormat::Markdown => markdown.into_owned(),
+                BadgeFormat::Asciidoc => markdown_to_asciidoc(&markdown),
+            }
+        };
+        render_template(template, |placeholder| match placeholder {
+            "name" => package.name.to_string(),
+            "version" => version_display.clone(),
+            "badges" => String::from_utf8_lossy(&badges).into_owned(),
+            "pr_log" => section(&pr_log),
+            "changelog" => section(&changelog),
+            _ => String::new(),
+        })
+        .into_bytes()
+    } else {
+        match format {
+            BadgeFormat::Markdown => {
+                if args.toc {
+                    let body_str = String::from_utf8_lossy(&body);
+                    write_table_of_contents(&mut output, &body_str)?;
+                }
+                output.extend_from_slice(&badges);
+                output.extend_from_slice(&body);
+                output
+            }
+            BadgeFormat::Asciidoc => {
+                let header = markdown_to_asciidoc(&String::from_utf8_lossy(&output));
+                let mut page = Vec::new();
+                for (i, line) in header.lines().enumerate() {
+                    writeln!(&mut page, "{}", line)?;
+                    // AsciiDoc renders the table of contents from a header attribute
+                    if i == 0 && args.toc {
+                        writeln!(&mut page, ":toc:")?;
+                    }
+                }
+                page.extend_from_slice(&badges);
+                write!(
+                    &mut page,
+                    "{}",
+                    markdown_to_asciidoc(&String::from_utf8_lossy(&body))
+                )?;
+                page
+            }
+        }
+    };
+
     logger.finish();
 
-    // Write output to file or stdout
-    if let Some(output_path) = args.output {
+    let output = if json {
+        let page = String::from_utf8(output).context("Release page is not valid UTF-8")?;
+        let json = serde_json::to_string(&ReleasePageOutput {
+            page: &page,
+            warnings: &warnings,
+        })
+        .context("Failed to serialize release page")?;
+        format!("{}\n", json).into_bytes()
+    } else {
+        output
+    };
+
+    // Write output to GitHub Actions, a file, or stdout
+    if github_actions {
+        let page = String::from_utf8(output).context("Release page is not valid UTF-8")?;
+        write_github_outputs(
+            args.github_output.as_deref(),
+            &[("release_page", page.as_str())],
+        )?;
+    } else if let Some(output_path) = args.output {
         std::fs::write(&output_path, output)
             .with_context(|| format!("Failed to write release page to {}", output_path))?;
         logger.status("Written", &output_path);
@@ -169,20 +411,174 @@ async fn release_page_async(args: ReleasePageArgs) -> Result<()> {
     Ok(())
 }
 
-/// Generate PR log section (stub for now).
-async fn generate_pr_log(_writer: &mut dyn Write, args: &ReleasePageArgs) -> Result<()> {
+/// Regex matching a `{{placeholder}}` in a template.
+fn placeholder_regex() -> Regex {
+    Regex::new(r"\{\{\s*([A-Za-z0-9_]+)\s*\}\}").expect("valid placeholder regex")
+}
+
+/// Check that a template only uses known placeholders.
+fn validate_template(template: &str) -> Result<()> {
+    let unknown: Vec<String> = placeholder_regex()
+        .captures_iter(template)
+        .map(|captures| captures[1].to_string())
+        .filter(|name| !TEMPLATE_PLACEHOLDERS.contains(&name.as_str()))
+        .collect();
+    if !unknown.is_empty() {
+        anyhow::bail!(
+            "Unknown placeholder(s): {} (expected {})",
+            unknown.join(", "),
+            TEMPLATE_PLACEHOLDERS.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Replace every `{{placeholder}}` in `template` with `value(placeholder)`.
+fn render_template(template: &str, value: impl Fn(&str) -> String) -> String {
+    placeholder_regex()
+        .replace_all(template, |captures: &regex::Captures| value(&captures[1]))
+        .into_owned()
+}
+
+/// Determine the release date as `YYYY-MM-DD`.
+///
+/// Uses the HEAD commit time of the repository containing `dir` (the
+/// package directory) when there is one, otherwise falls back to the system
+/// clock.
+fn release_date(dir: &std::path::Path) -> String {
+    let head_time = gix::discover(dir)
+        .ok()
+        .and_then(|repo| repo.head_commit().ok())
+        .and_then(|commit| commit.time().ok())
+        .map(|time| time.seconds);
+
+    let seconds = head_time.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0)
+    });
+
+    format_date(seconds)
+}
+
+/// Format seconds since the Unix epoch as a `YYYY-MM-DD` date (UTC).
+pub(crate) fn format_date(seconds: i64) -> String {
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let days = seconds.div_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Describe the compared range for the metadata block.
+///
+/// Returns `None` when the range is determined automatically from the latest
+/// tag.
+fn describe_range(args: &ReleasePageArgs, version_display: &str) -> Option<String> {
+    if let Some(range) = &args.range {
+        let (start, end) = range.split_once("..")?;
+        Some(format!("from {} to {}", start.trim(), end.trim()))
+    } else {
+        args.since_tag
+            .as_ref()
+            .map(|tag| format!("from {} to {}", tag, version_display))
+    }
+}
+
+/// Write a table of contents for all `##` headings in `body`.
+fn write_table_of_contents(writer: &mut dyn Write, body: &str) -> Result<()> {
+    let headings: Vec<&str> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("## "))
+        .map(str::trim)
+        .collect();
+
+    if headings.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "## Contents\n")?;
+    for heading in headings {
+        writeln!(writer, "- [{}](#{})", heading, heading_anchor(heading))?;
+    }
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+/// Compute the GitHub-style anchor for a markdown heading.
+fn heading_anchor(heading: &str) -> String {
+    heading
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                Some(c)
+            } else if c == ' ' {
+                Some('-')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Convert the markdown generated for the release page to AsciiDoc.
+///
+/// Only handles the constructs the release page emits: headings, list items,
+/// links, image links, and bold text.
+fn markdown_to_asciidoc(markdown: &str) -> String {
+    let image_link = Regex::new(r"\[!\[([^\]]*)\]\(([^)]*)\)\]\(([^)]*)\)").unwrap();
+    let link = Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").unwrap();
+    let bold = Regex::new(r"\*\*([^*]+)\*\*").unwrap();
+
+    let mut output = String::new();
+    for line in markdown.lines() {
+        let level = line.chars().take_while(|&c| c == '#').count();
+        let line = if level > 0 && line[level..].starts_with(' ') {
+            format!("{}{}", "=".repeat(level), &line[level..])
+        } else if let Some(item) = line.strip_prefix("- ") {
+            format!("* {}", item)
+        } else {
+            line.to_string()
+        };
+
+        let line = image_link.replace_all(&line, "image:$2[$1,link=$3]");
+        let line = link.replace_all(&line, "link:$2[$1]");
+        let line = bold.replace_all(&line, "*$1*");
+
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Generate PR log section.
+///
+/// With `--no-network`, the PR log is built from local merge commits; the
+/// GitHub API mode is not implemented yet and returns an error.
+async fn generate_pr_log(writer: &mut dyn Write, args: &ReleasePageArgs) -> Result<()> {
     // Build arguments for pr_log command
     let pr_log_args = crate::commands::PrLogArgs {
         since_tag: args.since_tag.clone(),
         output: None, // We handle output ourselves
         owner: args.owner.clone(),
         repo: args.repo.clone(),
+        no_network: args.no_network,
+        max_items: None,
     };
 
-    // Call pr_log - currently returns an error as it's not implemented
-    crate::commands::pr_log(pr_log_args)?;
-
-    Ok(())
+    crate::commands::pr_log::generate_pr_log_to_writer(writer, &pr_log_args)
 }
 
 /// Generate changelog section.
@@ -195,6 +591,15 @@ fn generate_changelog(writer: &mut dyn Write, args: &ReleasePageArgs) -> Result<
         output: None,                          // We handle output ourselves
         owner: args.owner.clone(),
         repo: args.repo.clone(),
+        format: "markdown".to_string(),
+        github_output: None,
+        count: false,
+        exclude_merges: false,
+        exclude_author: None,
+        scope: Vec::new(),
+        group_by: "type".to_string(),
+        max_items: None,
+        sort: "date".to_string(),
     };
 
     // Generate changelog to a temporary buffer so we can process it
@@ -292,6 +697,27 @@ repository = "https://github.com/test/repo"
         dir
     }
 
+    /// Arguments for an offline release page of `test/repo` with every other
+    /// option at its CLI default.
+    fn release_page_args() -> ReleasePageArgs {
+        ReleasePageArgs {
+            since_tag: None,
+            range: None,
+            for_version: None,
+            output: None,
+            no_network: true, // Skip network requests for badges
+            owner: Some("test".to_string()),
+            repo: Some("repo".to_string()),
+            toc: false,
+            metadata: false,
+            format: "markdown".to_string(),
+            github_output: None,
+            template: None,
+            cargo: CargoOptions::default(),
+            timeout: None,
+        }
+    }
+
     #[tokio::test]
     #[cfg_attr(target_os = "windows", ignore)] // Skip on Windows due to subprocess/directory issues
     async fn test_release_page_with_for_version() {
@@ -305,13 +731,9 @@ repository = "https://github.com/test/repo"
         let output_path = output_file.path().to_string_lossy().to_string();
 
         let args = ReleasePageArgs {
-            since_tag: None,
-            range: None,
             for_version: Some("v0.2.0".to_string()),
             output: Some(output_path.clone()),
-            no_network: true, // Skip network requests for badges
-            owner: Some("test".to_string()),
-            repo: Some("repo".to_string()),
+            ..release_page_args()
         };
 
         let result = release_page_async(args).await;
@@ -325,6 +747,37 @@ repository = "https://github.com/test/repo"
             content.contains("test-package v0.2.0"),
             "Header should include for_version"
         );
+        // The metadata block is opt-in
+        assert!(!content.contains("**Release date**"));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_os = "windows", ignore)] // Skip on Windows due to subprocess/directory issues
+    async fn test_release_page_github_actions_format() {
+        let _dir = create_test_cargo_project();
+        let dir_path = _dir.path().to_path_buf();
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(&dir_path).unwrap();
+
+        let github_output = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(github_output.path(), "earlier=output\n").unwrap();
+
+        let args = ReleasePageArgs {
+            for_version: Some("v0.2.0".to_string()),
+            format: "github-actions".to_string(),
+            github_output: Some(github_output.path().to_string_lossy().to_string()),
+            ..release_page_args()
+        };
+
+        let result = release_page_async(args).await;
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok(), "Release page generation should succeed");
+
+        let content = std::fs::read_to_string(github_output.path()).unwrap();
+        assert!(content.starts_with("earlier=output\nrelease_page<<ghadelimiter_"));
+        assert!(content.contains("test-package v0.2.0"));
     }
 
     #[tokio::test]
@@ -340,13 +793,9 @@ repository = "https://github.com/test/repo"
         let output_path = output_file.path().to_string_lossy().to_string();
 
         let args = ReleasePageArgs {
-            since_tag: None,
-            range: None,
             for_version: Some("0.2.0".to_string()), // No v prefix
             output: Some(output_path.clone()),
-            no_network: true,
-            owner: Some("test".to_string()),
-            repo: Some("repo".to_string()),
+            ..release_page_args()
         };
 
         let result = release_page_async(args).await;
@@ -372,13 +821,8 @@ repository = "https://github.com/test/repo"
         std::env::set_current_dir(&dir_path).unwrap();
 
         let args = ReleasePageArgs {
-            since_tag: None,
-            range: None,
             for_version: None, // Not specified - should use package version
-            output: None,
-            no_network: true,
-            owner: Some("test".to_string()),
-            repo: Some("repo".to_string()),
+            ..release_page_args()
         };
 
         let output_file = tempfile::NamedTempFile::new().unwrap();
@@ -399,4 +843,166 @@ repository = "https://github.com/test/repo"
             "Header should use package version from Cargo.toml when for_version not specified"
         );
     }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_767_225_599), "2025-12-31");
+    }
+
+    #[test]
+    fn test_heading_anchor() {
+        assert_eq!(heading_anchor("What's Changed"), "whats-changed");
+        assert_eq!(heading_anchor("Bug Fixes"), "bug-fixes");
+        assert_eq!(heading_anchor("CI/CD"), "cicd");
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_os = "windows", ignore)] // Skip on Windows due to subprocess/directory issues
+    async fn test_release_page_with_toc_and_metadata() {
+        let _dir = create_test_cargo_project();
+        let dir_path = _dir.path().to_path_buf();
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(&dir_path).unwrap();
+
+        let output_file = tempfile::NamedTempFile::new().unwrap();
+        let output_path = output_file.path().to_string_lossy().to_string();
+
+        let args = ReleasePageArgs {
+            range: Some("v0.1.0..HEAD".to_string()),
+            for_version: Some("v0.2.0".to_string()),
+            output: Some(output_path.clone()),
+            toc: true,
+            metadata: true,
+            ..release_page_args()
+        };
+
+        let result = release_page_async(args).await;
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok(), "Release page generation should succeed");
+
+        let content = std::fs::read_to_string(output_path).unwrap();
+        assert!(content.contains("**Release date**"));
+        assert!(content.contains("from v0.1.0 to HEAD"));
+        assert!(content.contains("- [What's Changed](#whats-changed)"));
+        // --no-network builds the PR log from local history
+        assert!(content.contains("- [Pull Requests](#pull-requests)"));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_os = "windows", ignore)] // Skip on Windows due to subprocess/directory issues
+    async fn test_release_page_continues_when_changelog_fails() {
+        let _dir = create_test_cargo_project();
+        let dir_path = _dir.path().to_path_buf();
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(&dir_path).unwrap();
+
+        let output_file = tempfile::NamedTempFile::new().unwrap();
+        let output_path = output_file.path().to_string_lossy().to_string();
+
+        let args = ReleasePageArgs {
+            since_tag: Some("v9.9.9".to_string()), // Tag does not exist
+            for_version: Some("v0.2.0".to_string()),
+            output: Some(output_path.clone()),
+            ..release_page_args()
+        };
+
+        let result = release_page_async(args).await;
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok(), "Release page should still be written");
+
+        let content = std::fs::read_to_string(output_path).unwrap();
+        assert!(content.contains("test-package v0.2.0"));
+        assert!(content.contains("_Changelog generation failed:"));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_os = "windows", ignore)] // Skip on Windows due to subprocess/directory issues
+    async fn test_release_page_json_reports_warnings() {
+        let _dir = create_test_cargo_project();
+        let dir_path = _dir.path().to_path_buf();
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(&dir_path).unwrap();
+
+        let output_file = tempfile::NamedTempFile::new().unwrap();
+        let output_path = output_file.path().to_string_lossy().to_string();
+
+        let args = ReleasePageArgs {
+            since_tag: Some("v9.9.9".to_string()), // Tag does not exist
+            for_version: Some("v0.2.0".to_string()),
+            output: Some(output_path.clone()),
+            format: "json".to_string(),
+            ..release_page_args()
+        };
+
+        let result = release_page_async(args).await;
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok(), "Release page should still be written");
+
+        let content = std::fs::read_to_string(output_path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert!(
+            json["page"]
+                .as_str()
+                .unwrap()
+                .contains("test-package v0.2.0")
+        );
+        let sections: Vec<&str> = json["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|warning| warning["section"].as_str().unwrap())
+            .collect();
+        assert_eq!(sections, ["pr-log", "changelog"]);
+    }
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template("# {{name}} {{ version }}\n{{badges}}{{pr_log}}").is_ok());
+        let err = validate_template("{{changelog}} {{authors}}").unwrap_err();
+        assert!(err.to_string().contains("authors"), "{}", err);
+    }
+
+    #[test]
+    fn test_render_template() {
+        let page = render_template(
+            "# {{name}} {{ version }}\n\n{{changelog}}",
+            |name| match name {
+                "name" => "crate".to_string(),
+                "version" => "v1.0.0".to_string(),
+                _ => String::new(),
+            },
+        );
+        assert_eq!(page, "# crate v1.0.0\n\n");
+    }
+
+    #[test]
+    fn test_markdown_to_asciidoc() {
+        let markdown = "# crate v1.0.0\n\n\
+                        ## What's Changed\n\n\
+                        - [abc1234](https://example.com/commit/abc): add feature\n\
+                        \n**Full Changelog**: [compare](https://example.com/compare)\n";
+        let asciidoc = markdown_to_asciidoc(markdown);
+
+        assert!(asciidoc.contains("= crate v1.0.0\n"));
+        assert!(asciidoc.contains("== What's Changed\n"));
+        assert!(asciidoc.contains("* link:https://example.com/commit/abc[abc1234]: add feature"));
+        assert!(asciidoc.contains("*Full Changelog*: link:https://example.com/compare[compare]"));
+    }
+
+    #[test]
+    fn test_markdown_to_asciidoc_image_link() {
+        let asciidoc = markdown_to_asciidoc("[![ADRs](https://img.shields.io/x)](docs/adr/)\n");
+        assert_eq!(
+            asciidoc,
+            "image:https://img.shields.io/x[ADRs,link=docs/adr/]\n"
+        );
+    }
 }
@@ -24,6 +24,8 @@ use anyhow::{
 };
 use clap::Parser;
 
+use super::common::VersionOutput;
+
 /// Arguments for the `rust-toolchain` command.
 #[derive(Parser, Debug)]
 pub struct RustToolchainArgs {
@@ -110,7 +112,11 @@ pub fn rust_toolchain(args: RustToolchainArgs) -> Result<()> {
 
     match args.format.as_str() {
         "version" => println!("{}", version),
-        "json" => println!("{{\"version\":\"{}\"}}", version),
+        "json" => println!(
+            "{}",
+            serde_json::to_string(&VersionOutput { version })
+                .context("Failed to serialize version")?
+        ),
         _ => anyhow::bail!("Invalid format: {}", args.format),
     }
 
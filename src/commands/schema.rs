@@ -0,0 +1,171 @@
+//! Print JSON Schemas for `--format json` outputs command.
+//!
+//! Each command's JSON output (for `changelog`, the `--count` output) is
+//! backed by a serde struct; this command prints the JSON Schema derived from
+//! it, so downstream tooling can rely on (and validate against) a documented
+//! contract.
+//!
+//! Without a command name, one document is printed with the schema of every
+//! command under `$defs`, keyed by command name.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Schemas of all commands
+//! cargo version-info schema
+//!
+//! # Schema of `build-version --format json`
+//! cargo version-info schema build-version
+//! ```
+
+use anyhow::{
+    Context,
+    Result,
+};
+use clap::Parser;
+use schemars::JsonSchema;
+use schemars::generate::SchemaSettings;
+use serde_json::Value;
+
+use super::badge::BadgeJson;
+use super::build_version::BuildVersionOutput;
+use super::bump::BumpOutput;
+use super::changed::ChangedOutput;
+use super::changelog::CommitCounts;
+use super::common::VersionOutput;
+use super::compare::CompareOutput;
+use super::describe::DescribeOutput;
+use super::dev::DevOutput;
+use super::history::VersionChange;
+use super::latest::LatestOutput;
+use super::next::NextOutput;
+use super::release_page::ReleasePageOutput;
+use super::tag::TagOutput;
+
+/// Commands with a `--format json` output.
+pub const SCHEMA_COMMANDS: &[&str] = &[
+    "badge",
+    "build-version",
+    "bump",
+    "changed",
+    "changelog",
+    "compare",
+    "current",
+    "describe",
+    "dev",
+    "dioxus",
+    "history",
+    "latest",
+    "next",
+    "release-page",
+    "rust-toolchain",
+    "tag",
+];
+
+/// Arguments for the `schema` command.
+#[derive(Parser, Debug)]
+pub struct SchemaArgs {
+    /// Command to print the schema for (default: all commands).
+    ///
+    /// One of [`SCHEMA_COMMANDS`].
+    pub command: Option<String>,
+}
+
+/// Print the JSON Schema of one or all commands' JSON output.
+///
+/// # Errors
+///
+/// Returns an error if the command has no JSON output or the schema cannot
+/// be serialized.
+pub fn schema(args: SchemaArgs) -> Result<()> {
+    let document = match &args.command {
+        Some(command) => command_schema(command)?,
+        None => combined_schema()?,
+    };
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&document).context("Failed to serialize schema")?
+    );
+    Ok(())
+}
+
+/// JSON Schema of `command`'s `--format json` output.
+fn command_schema(command: &str) -> Result<Value> {
+    let schema = match command {
+        "badge" => schema_for::<Vec<BadgeJson<'static>>>(),
+        "build-version" => schema_for::<BuildVersionOutput>(),
+        "bump" => schema_for::<BumpOutput<'static>>(),
+        "changed" => schema_for::<ChangedOutput<'static>>(),
+        "changelog" => schema_for::<CommitCounts>(),
+        "compare" => schema_for::<CompareOutput<'static>>(),
+        "current" | "dioxus" | "rust-toolchain" => schema_for::<VersionOutput>(),
+        "describe" => schema_for::<DescribeOutput<'static>>(),
+        "dev" => schema_for::<DevOutput<'static>>(),
+        "history" => schema_for::<Vec<VersionChange>>(),
+        "latest" => schema_for::<LatestOutput<'static>>(),
+        "next" => schema_for::<NextOutput<'static>>(),
+        "release-page" => schema_for::<ReleasePageOutput<'static>>(),
+        "tag" => schema_for::<TagOutput<'static>>(),
+        other => anyhow::bail!(
+            "No JSON output for command: {} (expected one of {})",
+            other,
+            SCHEMA_COMMANDS.join(", ")
+        ),
+    };
+    Ok(schema)
+}
+
+/// One schema document with every command's schema under `$defs`.
+fn combined_schema() -> Result<Value> {
+    let mut defs = serde_json::Map::new();
+    for command in SCHEMA_COMMANDS {
+        let mut schema = command_schema(command)?;
+        // Only the document root declares the dialect
+        if let Some(object) = schema.as_object_mut() {
+            object.remove("$schema");
+        }
+        defs.insert(command.to_string(), schema);
+    }
+
+    Ok(serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "cargo-version-info JSON output",
+        "$defs": defs,
+    }))
+}
+
+/// Derive a self-contained schema for `T` (no `$ref`s into `$defs`), so it
+/// can be embedded in the combined document.
+fn schema_for<T: JsonSchema>() -> Value {
+    let generator = SchemaSettings::draft2020_12()
+        .with(|settings| settings.inline_subschemas = true)
+        .into_generator();
+    Value::from(generator.into_root_schema_for::<T>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_schema() {
+        let current = command_schema("current").unwrap();
+        assert_eq!(current["properties"]["version"]["type"], "string");
+
+        let build_version = command_schema("build-version").unwrap();
+        let required = build_version["required"].as_array().unwrap();
+        assert!(required.contains(&Value::from("source")));
+        assert!(!required.contains(&Value::from("sha")));
+
+        let error = command_schema("pr-log").unwrap_err().to_string();
+        assert!(error.contains("build-version"), "{}", error);
+    }
+
+    #[test]
+    fn test_combined_schema() {
+        let combined = combined_schema().unwrap();
+        let defs = combined["$defs"].as_object().unwrap();
+        assert_eq!(defs.len(), SCHEMA_COMMANDS.len());
+        assert!(defs.values().all(|schema| schema.get("$schema").is_none()));
+    }
+}
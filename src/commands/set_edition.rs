@@ -0,0 +1,286 @@
+//! Set the Rust edition across a workspace command.
+//!
+//! This command rewrites the `edition` field of every workspace manifest
+//! (`[package] edition` and `[workspace.package] edition`) and commits the
+//! result, using the same formatting-preserving `toml_edit` editing and gix
+//! commit plumbing as `bump`. Members that inherit the edition with
+//! `edition.workspace = true` are left alone.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Move the whole workspace to edition 2024 and commit
+//! cargo version-info set-edition 2024
+//!
+//! # Migrate the code with `cargo fix --edition` as well
+//! cargo version-info set-edition 2024 --fix
+//!
+//! # Only edit the manifests
+//! cargo version-info set-edition 2021 --no-commit
+//! ```
+
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use anyhow::{
+    Context,
+    Result,
+};
+use clap::Parser;
+use toml_edit::DocumentMut;
+
+use super::bump::commit;
+use super::bump::version_update::preserve_line_endings;
+use super::common::CargoOptions;
+
+/// Editions accepted by `set-edition`.
+pub const EDITIONS: &[&str] = &["2015", "2018", "2021", "2024"];
+
+/// Arguments for the `set-edition` command.
+#[derive(Parser, Debug)]
+pub struct SetEditionArgs {
+    /// The edition to set (2015, 2018, 2021 or 2024).
+    pub edition: String,
+
+    /// Path to the Cargo.toml manifest file (standard cargo flag).
+    ///
+    /// Any manifest in the workspace works; every workspace member is
+    /// updated.
+    #[arg(long)]
+    pub manifest_path: Option<PathBuf>,
+
+    /// Don't commit changes, just update the manifests.
+    #[arg(long)]
+    pub no_commit: bool,
+
+    /// Run `cargo fix --edition` to migrate the code.
+    ///
+    /// `cargo fix --edition` migrates code to the edition following the one
+    /// in the manifests, so it runs before the manifests are changed. Source
+    /// changes it makes are left uncommitted for review.
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Network access for `cargo metadata` and `cargo fix`, from the global
+    /// `--offline` and `--frozen` flags.
+    #[arg(skip)]
+    pub cargo: CargoOptions,
+}
+
+/// Set the edition in every workspace manifest and commit the change.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The edition is not one of [`EDITIONS`]
+/// - Cargo metadata cannot be read
+/// - `cargo fix --edition` fails (with `--fix`)
+/// - A manifest cannot be parsed or written
+/// - Every manifest already uses the edition (nothing to change)
+/// - Git operations fail (when committing)
+pub fn set_edition(args: SetEditionArgs) -> Result<()> {
+    let mut logger = cargo_plugin_utils::logger::Logger::new();
+
+    let edition = args.edition.trim();
+    validate_edition(edition)?;
+
+    let manifest_path = args
+        .manifest_path
+        .unwrap_or_else(|| PathBuf::from("./Cargo.toml"));
+
+    logger.status("Reading", "workspace manifests");
+    let manifests = workspace_manifests(&manifest_path, args.cargo)?;
+    logger.finish();
+
+    if args.fix {
+        logger.status("Migrating", "code with cargo fix --edition");
+        let mut command = std::process::Command::new("cargo");
+        command
+            .args(["fix", "--edition", "--allow-dirty", "--workspace"])
+            .arg("--manifest-path")
+            .arg(&manifest_path);
+        if args.cargo.is_offline() {
+            command.env("CARGO_NET_OFFLINE", "true");
+        }
+        let status = command
+            .status()
+            .context("Failed to run cargo fix --edition")?;
+        logger.finish();
+        if !status.success() {
+            anyhow::bail!("cargo fix --edition failed ({})", status);
+        }
+    }
+
+    logger.status("Updating", "edition");
+    let mut changed = Vec::new();
+    for manifest in manifests {
+        if set_manifest_edition(&manifest, edition)? {
+            changed.push(manifest);
+        }
+    }
+    logger.finish();
+
+    if changed.is_empty() {
+        anyhow::bail!(
+            "Every manifest already uses edition {}. Nothing to change.",
+            edition
+        );
+    }
+    for manifest in &changed {
+        logger.print_message(&format!(
+            "Set edition {} in {}",
+            edition,
+            manifest.display()
+        ));
+    }
+
+    if args.no_commit {
+        logger.print_message(&format!("✓ Updated edition to {} (not committed)", edition));
+    } else {
+        logger.status("Committing", "edition changes");
+        commit::commit_files(
+            &changed,
+            &format!("chore(edition): set edition to {}", edition),
+        )?;
+        logger.finish();
+        logger.print_message(&format!("✓ Committed edition change to {}", edition));
+    }
+
+    Ok(())
+}
+
+/// Check that `edition` is one of [`EDITIONS`].
+fn validate_edition(edition: &str) -> Result<()> {
+    if !EDITIONS.contains(&edition) {
+        anyhow::bail!(
+            "Invalid edition: {} (expected one of {})",
+            edition,
+            EDITIONS.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// List the manifests of all workspace members plus the workspace root.
+fn workspace_manifests(manifest_path: &Path, cargo: CargoOptions) -> Result<Vec<PathBuf>> {
+    let metadata = cargo
+        .metadata_command()
+        .manifest_path(manifest_path)
+        .no_deps()
+        .exec()
+        .context("Failed to get cargo metadata")?;
+
+    let mut manifests: Vec<PathBuf> = metadata
+        .workspace_packages()
+        .iter()
+        .map(|pkg| pkg.manifest_path.clone().into_std_path_buf())
+        .collect();
+    let root_manifest = metadata
+        .workspace_root
+        .join("Cargo.toml")
+        .into_std_path_buf();
+    if !manifests.contains(&root_manifest) {
+        manifests.push(root_manifest);
+    }
+
+    Ok(manifests)
+}
+
+/// Set the edition in `[package]` and `[workspace.package]` of one manifest.
+///
+/// Only existing literal `edition` values are rewritten, keeping their
+/// comments; a missing or inherited (`edition.workspace = true`) edition is
+/// left alone. Returns `true` if the manifest was modified.
+pub fn set_manifest_edition(manifest_path: &Path, edition: &str) -> Result<bool> {
+    let content = std::fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse TOML in {}", manifest_path.display()))?;
+
+    let mut changed = false;
+    let package = doc.get_mut("package").and_then(|p| p.as_table_like_mut());
+    if let Some(package) = package {
+        changed |= set_edition_value(package, edition);
+    }
+    let workspace_package = doc
+        .get_mut("workspace")
+        .and_then(|w| w.as_table_like_mut())
+        .and_then(|w| w.get_mut("package"))
+        .and_then(|p| p.as_table_like_mut());
+    if let Some(workspace_package) = workspace_package {
+        changed |= set_edition_value(workspace_package, edition);
+    }
+
+    if changed {
+        let updated = preserve_line_endings(&content, &doc.to_string());
+        std::fs::write(manifest_path, updated)
+            .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+    }
+
+    Ok(changed)
+}
+
+/// Replace a literal `edition` string in a table, keeping its decor.
+fn set_edition_value(table: &mut dyn toml_edit::TableLike, edition: &str) -> bool {
+    let Some(value) = table.get_mut("edition").and_then(|e| e.as_value_mut()) else {
+        return false;
+    };
+    if value.as_str().is_none_or(|current| current == edition) {
+        return false;
+    }
+
+    let decor = value.decor().clone();
+    *value = edition.into();
+    *value.decor_mut() = decor;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_edition() {
+        for edition in EDITIONS {
+            assert!(validate_edition(edition).is_ok());
+        }
+        assert!(validate_edition("2022").is_err());
+        assert!(validate_edition("").is_err());
+    }
+
+    #[test]
+    fn test_set_manifest_edition() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            "[package]\nname = \"a\"\nedition = \"2021\" # migrate soon\n\n\
+             [workspace.package]\nedition = \"2018\"\n",
+        )
+        .unwrap();
+
+        assert!(set_manifest_edition(&manifest, "2024").unwrap());
+        assert_eq!(
+            std::fs::read_to_string(&manifest).unwrap(),
+            "[package]\nname = \"a\"\nedition = \"2024\" # migrate soon\n\n\
+             [workspace.package]\nedition = \"2024\"\n"
+        );
+
+        // Already up to date
+        assert!(!set_manifest_edition(&manifest, "2024").unwrap());
+    }
+
+    #[test]
+    fn test_set_manifest_edition_inherited() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        let original = "[package]\nname = \"a\"\nedition.workspace = true\n";
+        std::fs::write(&manifest, original).unwrap();
+
+        assert!(!set_manifest_edition(&manifest, "2024").unwrap());
+        assert_eq!(std::fs::read_to_string(&manifest).unwrap(), original);
+    }
+}
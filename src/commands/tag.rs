@@ -16,8 +16,13 @@
 //! cargo version-info tag v0.1.2
 //! ```
 
-use anyhow::Result;
+use anyhow::{
+    Context,
+    Result,
+};
 use clap::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
 
 use crate::version::{
     format_tag,
@@ -41,6 +46,15 @@ pub struct TagArgs {
     format: String,
 }
 
+/// Tag and version, printed with `--format json`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct TagOutput<'a> {
+    /// The git tag (e.g. `v0.1.2`).
+    tag: &'a str,
+    /// The version the tag was formatted from.
+    version: &'a str,
+}
+
 /// Generate a git tag name from a semantic version string.
 ///
 /// Parses the version string and formats it as a git tag with the 'v' prefix.
@@ -86,7 +100,16 @@ pub fn tag(args: TagArgs) -> Result<()> {
 
     match args.format.as_str() {
         "tag" => println!("{}", tag),
-        "json" => println!("{{\"tag\":\"{}\",\"version\":\"{}\"}}", tag, args.version),
+        "json" => {
+            let output = TagOutput {
+                tag: &tag,
+                version: &args.version,
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&output).context("Failed to serialize tag")?
+            );
+        }
         _ => anyhow::bail!("Invalid format: {}", args.format),
     }
 
@@ -0,0 +1,156 @@
+//! Process exit codes.
+//!
+//! Commands return `anyhow::Result`, which on its own only distinguishes
+//! success (0) from failure (1). Checks whose failure is an expected outcome
+//! rather than a malfunction return an [`ExitError`] instead, so scripts can
+//! tell "the check failed" apart from "the tool failed":
+//!
+//! | Code | Meaning                                                         |
+//! |------|-----------------------------------------------------------------|
+//! | 1    | Error (I/O, git, network, invalid input, ...)                   |
+//! | 11   | Versions drift between sources, e.g. Cargo.toml vs git tag      |
+//! | 12   | Coverage is below `--threshold` (`badge coverage`)              |
+//!
+//! Success exits with 0, and clap reports invalid command-line usage with 2.
+//!
+//! ```bash
+//! cargo version-info pre-bump-hook
+//! case $? in
+//!   0) echo "ok" ;;
+//!   11) echo "Cargo.toml and tags disagree" ;;
+//!   *) echo "error" ;;
+//! esac
+//! ```
+
+use std::fmt;
+
+/// Documented exit codes for `cargo version-info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ExitCode {
+    /// A genuine error occurred.
+    Error = 1,
+    /// Versions from different sources (Cargo.toml, git tags, expected
+    /// target) do not agree.
+    VersionDrift = 11,
+    /// A measured metric (test coverage) is below the required threshold.
+    BelowThreshold = 12,
+}
+
+impl ExitCode {
+    /// The numeric process exit code.
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Find the exit code for an error.
+    ///
+    /// Looks for an [`ExitError`] anywhere in the error chain (so added
+    /// context does not hide it), defaulting to [`ExitCode::Error`].
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<ExitError>())
+            .map_or(Self::Error, |exit| exit.code)
+    }
+}
+
+impl From<ExitCode> for std::process::ExitCode {
+    fn from(code: ExitCode) -> Self {
+        std::process::ExitCode::from(code.code())
+    }
+}
+
+/// An error that carries a specific [`ExitCode`].
+#[derive(Debug)]
+pub struct ExitError {
+    /// Exit code to report.
+    pub code: ExitCode,
+    /// Human-readable message.
+    pub message: String,
+}
+
+impl ExitError {
+    /// Create a new error with the given exit code.
+    pub fn new(code: ExitCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ExitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ExitError {}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+
+    use anyhow::Context;
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_exit_code_from_plain_error() {
+        let error = anyhow::anyhow!("something broke");
+        assert_eq!(ExitCode::from_error(&error), ExitCode::Error);
+    }
+
+    #[test]
+    fn test_exit_code_from_exit_error_with_context() {
+        let result: anyhow::Result<()> =
+            Err(ExitError::new(ExitCode::VersionDrift, "Version mismatch").into());
+        let error = result.context("pre-bump checks failed").unwrap_err();
+
+        assert_eq!(ExitCode::from_error(&error), ExitCode::VersionDrift);
+        assert_eq!(ExitCode::from_error(&error).code(), 11);
+    }
+
+    #[test]
+    fn test_exit_code_triggers() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest_path = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest_path,
+            "[package]\nname = \"test\"\nversion = \"1.0.0\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src").join("lib.rs"), "// Test library\n").unwrap();
+
+        let post_bump_hook = |manifest_path: &std::path::Path| {
+            let args = crate::commands::PostBumpHookArgs::try_parse_from([
+                OsStr::new("post-bump-hook"),
+                OsStr::new("--manifest-path"),
+                manifest_path.as_os_str(),
+                OsStr::new("--target-version"),
+                OsStr::new("2.0.0"),
+            ])
+            .unwrap();
+            crate::commands::post_bump_hook(args).unwrap_err()
+        };
+
+        // Matching on every variant keeps this test in step with the enum
+        for code in [
+            ExitCode::Error,
+            ExitCode::VersionDrift,
+            ExitCode::BelowThreshold,
+        ] {
+            let error = match code {
+                ExitCode::Error => post_bump_hook(&dir.path().join("missing.toml")),
+                ExitCode::VersionDrift => post_bump_hook(&manifest_path),
+                ExitCode::BelowThreshold => {
+                    crate::commands::badge::coverage::check_threshold(Some(79), 80).unwrap_err()
+                }
+            };
+            assert_eq!(ExitCode::from_error(&error), code, "{:#}", error);
+        }
+    }
+}
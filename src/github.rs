@@ -1,28 +1,244 @@
 //! GitHub API integration for version queries.
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::env;
+use std::sync::{
+    LazyLock,
+    Mutex,
+};
+use std::time::{
+    Duration,
+    SystemTime,
+    UNIX_EPOCH,
+};
 
 use anyhow::{
     Context,
     Result,
 };
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
 use crate::version::{
+    cmp_versions,
     format_version,
     increment_patch,
+    increment_prerelease,
     parse_version,
+    prerelease,
 };
 
+/// Cache key for release lookups: `(owner, repo, tag_prefix)`.
+type ReleaseKey = (String, String, Option<String>);
+
+/// Latest release versions already fetched by this process.
+///
+/// Avoids re-querying the GitHub API when several steps of one command need
+/// the same lookup. Lives only for the current process.
+static RELEASE_CACHE: LazyLock<Mutex<HashMap<ReleaseKey, Option<String>>>> =
+    LazyLock::new(Default::default);
+
+/// Default time-to-live for the on-disk release cache.
+pub const DEFAULT_RELEASE_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Latest-release lookup persisted between runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ReleaseCacheEntry {
+    owner: String,
+    repo: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag_prefix: Option<String>,
+    /// Latest version, or `None` if the repository had no matching release.
+    version: Option<String>,
+    /// Seconds since the Unix epoch when the version was fetched.
+    fetched_at: u64,
+}
+
+impl ReleaseCacheEntry {
+    fn matches(&self, owner: &str, repo: &str, tag_prefix: Option<&str>) -> bool {
+        self.owner == owner && self.repo == repo && self.tag_prefix.as_deref() == tag_prefix
+    }
+
+    fn is_fresh(&self, now: u64, ttl: Duration) -> bool {
+        now.saturating_sub(self.fetched_at) < ttl.as_secs()
+    }
+}
+
+/// Resolve the GitHub repository owner and name.
+///
+/// Explicit `--owner`/`--repo` values always win. When only one of them is
+/// given, the other half is filled in from detection (`GITHUB_REPOSITORY`,
+/// then the git remote) rather than failing. When neither is given, both are
+/// detected.
+pub fn resolve_owner_repo(owner: Option<String>, repo: Option<String>) -> Result<(String, String)> {
+    merge_owner_repo(owner, repo, || {
+        cargo_plugin_utils::common::get_owner_repo(None, None)
+    })
+}
+
+/// Merge explicit owner/repo values with a detected repository.
+///
+/// `detect` is only called when at least one value is missing.
+fn merge_owner_repo(
+    owner: Option<String>,
+    repo: Option<String>,
+    detect: impl FnOnce() -> Result<(String, String)>,
+) -> Result<(String, String)> {
+    match (owner, repo) {
+        (Some(owner), Some(repo)) => Ok((owner, repo)),
+        (owner, repo) => {
+            let (detected_owner, detected_repo) = detect().context(
+                "Failed to detect GitHub repository. Pass both --owner and --repo, or set \
+                 GITHUB_REPOSITORY",
+            )?;
+            Ok((
+                owner.unwrap_or(detected_owner),
+                repo.unwrap_or(detected_repo),
+            ))
+        }
+    }
+}
+
+/// Strip the tag prefix from a tag name, leaving the version.
+///
+/// With an explicit `tag_prefix` (e.g. `foo-v` for monorepo tags like
+/// `foo-v1.2.3`), tags that do not start with the prefix are rejected by
+/// returning `None`. Without a prefix, a leading `v`/`V` is stripped if
+/// present.
+pub fn strip_tag_prefix<'a>(tag_name: &'a str, tag_prefix: Option<&str>) -> Option<&'a str> {
+    match tag_prefix {
+        Some(prefix) => tag_name.strip_prefix(prefix),
+        None => Some(
+            tag_name
+                .strip_prefix('v')
+                .or_else(|| tag_name.strip_prefix('V'))
+                .unwrap_or(tag_name),
+        ),
+    }
+}
+
 /// Get the latest published release version from GitHub.
 ///
 /// Uses the GitHub API via octocrab. Works for public repos without a token
 /// (with rate limits). For private repos, a token is required (automatically
 /// detected from GITHUB_TOKEN env var if not provided).
-#[allow(clippy::disallowed_methods)] // CLI tool needs direct env access
+///
+/// With `tag_prefix`, only releases whose tag starts with the prefix are
+/// considered (see [`strip_tag_prefix`]). `timeout` bounds each API request;
+/// `None` means 30 seconds.
+///
+/// Successful lookups are memoized per `(owner, repo, tag_prefix)` for the
+/// rest of the process, so repeated calls do not re-query the API.
 pub async fn get_latest_release_version(
     owner: &str,
     repo: &str,
     github_token: Option<&str>,
+    tag_prefix: Option<&str>,
+    timeout: Option<Duration>,
+) -> Result<Option<String>> {
+    let key: ReleaseKey = (
+        owner.to_string(),
+        repo.to_string(),
+        tag_prefix.map(str::to_string),
+    );
+    if let Some(cached) = cached_release(&key) {
+        return Ok(cached);
+    }
+
+    let version =
+        fetch_latest_release_version(owner, repo, github_token, tag_prefix, timeout).await?;
+    RELEASE_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(key, version.clone());
+
+    Ok(version)
+}
+
+/// Get the latest release version, consulting the on-disk cache first.
+///
+/// A cached result younger than `ttl` is returned without querying GitHub;
+/// otherwise the version is fetched via [`get_latest_release_version`] and
+/// the cache is refreshed. The cache lives in the cargo target directory and
+/// holds the most recent lookup. Cache read and write failures are ignored,
+/// since the cache is only an optimization.
+///
+/// # Errors
+///
+/// Returns an error if the GitHub lookup fails.
+pub async fn get_latest_release_version_cached(
+    owner: &str,
+    repo: &str,
+    github_token: Option<&str>,
+    tag_prefix: Option<&str>,
+    ttl: Duration,
+    timeout: Option<Duration>,
+) -> Result<Option<String>> {
+    let now = unix_now();
+    let cache_path = crate::commands::common::cache_file_path("github-release").ok();
+
+    if let Some(path) = &cache_path
+        && let Ok(contents) = tokio::fs::read_to_string(path).await
+        && let Ok(entry) = serde_json::from_str::<ReleaseCacheEntry>(&contents)
+        && entry.matches(owner, repo, tag_prefix)
+        && entry.is_fresh(now, ttl)
+    {
+        return Ok(entry.version);
+    }
+
+    let version =
+        get_latest_release_version(owner, repo, github_token, tag_prefix, timeout).await?;
+
+    if let Some(path) = &cache_path {
+        let entry = ReleaseCacheEntry {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            tag_prefix: tag_prefix.map(str::to_string),
+            version: version.clone(),
+            fetched_at: now,
+        };
+        if let Ok(json) = serde_json::to_string_pretty(&entry) {
+            if let Some(parent) = path.parent() {
+                let _ = tokio::fs::create_dir_all(parent).await;
+            }
+            let _ = tokio::fs::write(path, json).await;
+        }
+    }
+
+    Ok(version)
+}
+
+/// Current time in seconds since the Unix epoch.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Look up a memoized release version.
+///
+/// Returns `None` on a cache miss and `Some(None)` if the repository is known
+/// to have no matching release.
+fn cached_release(key: &ReleaseKey) -> Option<Option<String>> {
+    RELEASE_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(key)
+        .cloned()
+}
+
+/// Query the GitHub API for the latest release version (uncached).
+#[allow(clippy::disallowed_methods)] // CLI tool needs direct env access
+async fn fetch_latest_release_version(
+    owner: &str,
+    repo: &str,
+    github_token: Option<&str>,
+    tag_prefix: Option<&str>,
+    timeout: Option<Duration>,
 ) -> Result<Option<String>> {
     // Auto-detect token from environment if not provided
     let env_token = env::var("GITHUB_TOKEN").ok();
@@ -31,10 +247,10 @@ pub async fn get_latest_release_version(
     // Try with token first (required for private repos, better rate limits for
     // public)
     let result = if let Some(token) = token {
-        get_latest_release_via_api(owner, repo, Some(token)).await
+        get_latest_release_via_api(owner, repo, Some(token), tag_prefix, timeout).await
     } else {
         // Try without token (public repos only)
-        get_latest_release_via_api(owner, repo, None).await
+        get_latest_release_via_api(owner, repo, None, tag_prefix, timeout).await
     };
 
     match result {
@@ -69,6 +285,9 @@ pub async fn get_latest_release_version(
     }
 }
 
+/// Default timeout of GitHub API requests (see `--timeout`).
+const GITHUB_API_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Get latest release via GitHub API.
 ///
 /// Works for public repositories even without a token (with rate limits).
@@ -77,33 +296,43 @@ async fn get_latest_release_via_api(
     owner: &str,
     repo: &str,
     token: Option<&str>,
+    tag_prefix: Option<&str>,
+    timeout: Option<Duration>,
 ) -> Result<String> {
+    let timeout = timeout.unwrap_or(GITHUB_API_TIMEOUT);
+    let builder = octocrab::OctocrabBuilder::new()
+        .set_connect_timeout(Some(timeout))
+        .set_read_timeout(Some(timeout))
+        .set_write_timeout(Some(timeout));
     let octocrab = if let Some(token) = token {
-        octocrab::OctocrabBuilder::new()
+        builder
             .personal_token(token.to_string())
             .build()
             .context("Failed to create GitHub API client")?
     } else {
         // For public repos, we can use octocrab without a token
-        octocrab::Octocrab::builder()
+        builder
             .build()
             .context("Failed to create GitHub API client")?
     };
 
+    // Releases are listed newest first; with a prefix, other crates' releases
+    // may come first, so look at a full page
+    let per_page = if tag_prefix.is_some() { 100 } else { 1 };
     let releases = octocrab
         .repos(owner, repo)
         .releases()
         .list()
-        .per_page(1)
+        .per_page(per_page)
         .send()
         .await
         .context("Failed to query GitHub releases")?;
 
-    let release = releases.items.first().context("No releases found")?;
-
-    let tag_name = release.tag_name.as_str();
-    let version = tag_name.strip_prefix('v').unwrap_or(tag_name);
-    let version = version.strip_prefix('V').unwrap_or(version);
+    let version = releases
+        .items
+        .iter()
+        .find_map(|release| strip_tag_prefix(release.tag_name.as_str(), tag_prefix))
+        .context("No releases found")?;
 
     Ok(version.to_string())
 }
@@ -111,59 +340,61 @@ async fn get_latest_release_via_api(
 /// Get the latest version from git tags.
 ///
 /// Queries git tags in the current repository to find the latest semantic
-/// version tag. Returns None if no version tags exist.
-fn get_latest_git_tag_version() -> Result<Option<String>> {
+/// version tag. Returns None if no version tags exist. With `tag_prefix`,
+/// only tags starting with the prefix are considered. Pre-release tags
+/// (e.g. `v1.0.0-rc.1`) are skipped unless `include_prereleases` is set.
+fn get_latest_git_tag_version(
+    tag_prefix: Option<&str>,
+    include_prereleases: bool,
+) -> Result<Option<String>> {
     let cwd = std::env::current_dir().context("Failed to get current directory")?;
     let repo = gix::discover(cwd)
         .context("Failed to discover git repository. Ensure you're in a git repository.")?;
 
-    let mut version_tags: Vec<(String, (u32, u32, u32))> = repo
+    let mut version_tags: Vec<String> = repo
         .references()?
         .prefixed("refs/tags/")?
         .filter_map(|r: Result<gix::Reference<'_>, _>| r.ok())
         .filter_map(|r| {
             let name_full = r.name().as_bstr().to_string();
             let name = name_full.strip_prefix("refs/tags/").unwrap_or(&name_full);
-            let version_str = name
-                .strip_prefix('v')
-                .or_else(|| name.strip_prefix('V'))
-                .unwrap_or(name);
+            let version_str = strip_tag_prefix(name, tag_prefix)?;
 
             // Try to parse as semantic version
-            if let Ok((major, minor, patch)) = parse_version(version_str) {
-                Some((name.to_string(), (major, minor, patch)))
-            } else {
-                None
+            parse_version(version_str).ok()?;
+            if !include_prereleases && prerelease(version_str).is_some() {
+                return None;
             }
+            Some(version_str.to_string())
         })
         .collect();
 
-    // Sort tags by semantic version (major, minor, patch)
-    version_tags.sort_by(|a, b| a.1.cmp(&b.1));
+    // Sort tags by semantic version precedence (pre-releases before releases)
+    version_tags.sort_by(|a, b| cmp_versions(a, b).unwrap_or(Ordering::Equal));
 
-    Ok(version_tags
-        .last()
-        .map(|(tag_name, _): &(String, (u32, u32, u32))| {
-            tag_name
-                .strip_prefix('v')
-                .or_else(|| tag_name.strip_prefix('V'))
-                .unwrap_or(tag_name)
-                .to_string()
-        }))
+    Ok(version_tags.pop())
 }
 
 /// Calculate next patch version from latest git tag.
 ///
 /// Queries git tags in the current repository (not GitHub releases) to find
 /// the latest version. If no tags exist, returns "0.0.0" as latest and
-/// "0.0.1" as next.
+/// "0.0.1" as next. With `tag_prefix`, only tags starting with the prefix
+/// (e.g. `foo-v` for `foo-v1.2.3`) are considered.
+///
+/// Pre-release tags are ignored by default. With `include_prereleases`, a
+/// pre-release can be the latest version, in which case the next version
+/// advances its pre-release counter (`1.0.0-rc.1` -> `1.0.0-rc.2`) instead of
+/// bumping the patch.
 pub async fn calculate_next_version(
     _owner: &str,
     _repo: &str,
     _github_token: Option<&str>,
+    tag_prefix: Option<&str>,
+    include_prereleases: bool,
 ) -> Result<(String, String)> {
     // Get latest version from git tags (not GitHub releases)
-    let latest_version_str = match get_latest_git_tag_version()? {
+    let latest_version_str = match get_latest_git_tag_version(tag_prefix, include_prereleases)? {
         Some(v) => v,
         None => {
             // No tags yet, start at 0.0.1
@@ -171,6 +402,11 @@ pub async fn calculate_next_version(
         }
     };
 
+    if prerelease(&latest_version_str).is_some() {
+        let next_version = increment_prerelease(&latest_version_str)?;
+        return Ok((latest_version_str, next_version));
+    }
+
     let (major, minor, patch) = parse_version(&latest_version_str)
         .with_context(|| format!("Failed to parse latest version: {}", latest_version_str))?;
 
@@ -236,13 +472,50 @@ mod tests {
         dir
     }
 
+    fn detected() -> Result<(String, String)> {
+        Ok(("fork-owner".to_string(), "fork-repo".to_string()))
+    }
+
+    #[test]
+    fn test_merge_owner_repo_explicit_wins() {
+        let result = merge_owner_repo(Some("upstream".to_string()), Some("x".to_string()), || {
+            panic!("detection should not run when both values are given")
+        })
+        .unwrap();
+        assert_eq!(result, ("upstream".to_string(), "x".to_string()));
+    }
+
+    #[test]
+    fn test_merge_owner_repo_partial_args() {
+        assert_eq!(
+            merge_owner_repo(Some("upstream".to_string()), None, detected).unwrap(),
+            ("upstream".to_string(), "fork-repo".to_string())
+        );
+        assert_eq!(
+            merge_owner_repo(None, Some("x".to_string()), detected).unwrap(),
+            ("fork-owner".to_string(), "x".to_string())
+        );
+        assert_eq!(
+            merge_owner_repo(None, None, detected).unwrap(),
+            ("fork-owner".to_string(), "fork-repo".to_string())
+        );
+    }
+
+    #[test]
+    fn test_merge_owner_repo_detection_failure() {
+        let result = merge_owner_repo(Some("upstream".to_string()), None, || {
+            anyhow::bail!("no remote")
+        });
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_get_latest_git_tag_version_no_tags() {
         let dir = create_test_git_repo_with_tags(&[]);
         let original_dir = std::env::current_dir().unwrap();
 
         std::env::set_current_dir(dir.path()).unwrap();
-        let result = get_latest_git_tag_version().unwrap();
+        let result = get_latest_git_tag_version(None, false).unwrap();
         std::env::set_current_dir(original_dir).unwrap();
 
         assert_eq!(result, None);
@@ -255,7 +528,7 @@ mod tests {
         let original_dir = std::env::current_dir().unwrap();
 
         std::env::set_current_dir(&dir_path).unwrap();
-        let result = get_latest_git_tag_version().unwrap();
+        let result = get_latest_git_tag_version(None, false).unwrap();
         std::env::set_current_dir(original_dir).unwrap();
 
         assert_eq!(result, Some("0.1.0".to_string()));
@@ -268,7 +541,7 @@ mod tests {
         let original_dir = std::env::current_dir().unwrap();
 
         std::env::set_current_dir(&dir_path).unwrap();
-        let result = get_latest_git_tag_version().unwrap();
+        let result = get_latest_git_tag_version(None, false).unwrap();
         std::env::set_current_dir(original_dir).unwrap();
 
         // Should return the latest version (0.2.0)
@@ -282,7 +555,7 @@ mod tests {
         let original_dir = std::env::current_dir().unwrap();
 
         std::env::set_current_dir(&dir_path).unwrap();
-        let result = get_latest_git_tag_version().unwrap();
+        let result = get_latest_git_tag_version(None, false).unwrap();
         std::env::set_current_dir(original_dir).unwrap();
 
         // Should return the latest version (0.3.0)
@@ -296,7 +569,9 @@ mod tests {
         let original_dir = std::env::current_dir().unwrap();
 
         std::env::set_current_dir(&dir_path).unwrap();
-        let (latest, next) = calculate_next_version("test", "repo", None).await.unwrap();
+        let (latest, next) = calculate_next_version("test", "repo", None, None, false)
+            .await
+            .unwrap();
         std::env::set_current_dir(original_dir).unwrap();
 
         assert_eq!(latest, "0.0.0");
@@ -310,19 +585,142 @@ mod tests {
         let original_dir = std::env::current_dir().unwrap();
 
         std::env::set_current_dir(&dir_path).unwrap();
-        let (latest, next) = calculate_next_version("test", "repo", None).await.unwrap();
+        let (latest, next) = calculate_next_version("test", "repo", None, None, false)
+            .await
+            .unwrap();
         std::env::set_current_dir(original_dir).unwrap();
 
         assert_eq!(latest, "0.1.2");
         assert_eq!(next, "0.1.3");
     }
 
+    #[tokio::test]
+    async fn test_calculate_next_version_prereleases() {
+        let _dir = create_test_git_repo_with_tags(&["v0.9.0", "v1.0.0-rc.1", "v1.0.0-rc.2"]);
+        let dir_path = _dir.path().to_path_buf();
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(&dir_path).unwrap();
+        let stable = calculate_next_version("test", "repo", None, None, false)
+            .await
+            .unwrap();
+        let train = calculate_next_version("test", "repo", None, None, true)
+            .await
+            .unwrap();
+        std::env::set_current_dir(original_dir).unwrap();
+
+        // Pre-release tags are ignored by default
+        assert_eq!(stable, ("0.9.0".to_string(), "0.9.1".to_string()));
+        // With pre-releases, the counter advances
+        assert_eq!(train, ("1.0.0-rc.2".to_string(), "1.0.0-rc.3".to_string()));
+    }
+
+    #[test]
+    fn test_strip_tag_prefix() {
+        assert_eq!(strip_tag_prefix("v1.2.3", None), Some("1.2.3"));
+        assert_eq!(strip_tag_prefix("V1.2.3", None), Some("1.2.3"));
+        assert_eq!(strip_tag_prefix("1.2.3", None), Some("1.2.3"));
+        assert_eq!(strip_tag_prefix("foo-v1.2.3", Some("foo-v")), Some("1.2.3"));
+        assert_eq!(strip_tag_prefix("bar-v2.0.0", Some("foo-v")), None);
+        assert_eq!(strip_tag_prefix("v1.2.3", Some("foo-v")), None);
+    }
+
+    #[test]
+    fn test_get_latest_git_tag_version_with_tag_prefix() {
+        let _dir = create_test_git_repo_with_tags(&["foo-v0.1.0", "bar-v0.9.0", "v1.0.0"]);
+        let dir_path = _dir.path().to_path_buf();
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(&dir_path).unwrap();
+        let foo = get_latest_git_tag_version(Some("foo-v"), false).unwrap();
+        let bar = get_latest_git_tag_version(Some("bar-v"), false).unwrap();
+        let baz = get_latest_git_tag_version(Some("baz-v"), false).unwrap();
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(foo, Some("0.1.0".to_string()));
+        assert_eq!(bar, Some("0.9.0".to_string()));
+        assert_eq!(baz, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_release_version_uses_cache() {
+        let key: ReleaseKey = (
+            "cache-test-owner".to_string(),
+            "cache-test-repo".to_string(),
+            Some("foo-v".to_string()),
+        );
+        RELEASE_CACHE
+            .lock()
+            .unwrap()
+            .insert(key.clone(), Some("9.9.9".to_string()));
+
+        // Served from the cache, so no network request is made
+        let version = get_latest_release_version(
+            "cache-test-owner",
+            "cache-test-repo",
+            None,
+            Some("foo-v"),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(version, Some("9.9.9".to_string()));
+
+        // A different prefix is a different key
+        assert_eq!(
+            cached_release(&("cache-test-owner".into(), "cache-test-repo".into(), None)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_release_cache_entry_freshness() {
+        let entry = ReleaseCacheEntry {
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            tag_prefix: None,
+            version: Some("1.2.3".to_string()),
+            fetched_at: 1_000,
+        };
+        let ttl = Duration::from_secs(300);
+
+        assert!(entry.matches("owner", "repo", None));
+        assert!(!entry.matches("owner", "repo", Some("foo-v")));
+        assert!(!entry.matches("other", "repo", None));
+        assert!(entry.is_fresh(1_000, ttl));
+        assert!(entry.is_fresh(1_299, ttl));
+        assert!(!entry.is_fresh(1_300, ttl));
+        assert!(!entry.is_fresh(5_000, Duration::ZERO));
+    }
+
+    #[test]
+    fn test_release_cache_entry_json() {
+        let entry = ReleaseCacheEntry {
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            tag_prefix: None,
+            version: Some("1.2.3".to_string()),
+            fetched_at: 1_000,
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(
+            json,
+            r#"{"owner":"owner","repo":"repo","version":"1.2.3","fetched_at":1000}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<ReleaseCacheEntry>(&json).unwrap(),
+            entry
+        );
+    }
+
     #[tokio::test]
     #[ignore] // Requires network access
     async fn test_get_latest_release_via_api() {
         // This test requires network access
         // Only run manually
-        if let Ok(Some(version)) = get_latest_release_version("rust-lang", "rust", None).await {
+        if let Ok(Some(version)) =
+            get_latest_release_version("rust-lang", "rust", None, None, None).await
+        {
             println!("Latest rust release: {}", version);
         }
     }
@@ -30,8 +30,57 @@
 /// 2. GitHub API (in GitHub Actions)
 /// 3. Cargo.toml version + git SHA
 /// 4. Git SHA fallback (`0.0.0-dev-<sha>`)
+///
+/// # Example: Generating badges from Rust
+///
+/// Badge generation is a stable library API. [`commands::generate_badges`]
+/// returns structured [`commands::Badge`] values that can be rendered with
+/// [`commands::Badge::render`] or used directly:
+///
+/// ```no_run
+/// use cargo_version_info::commands::{
+///     BadgeFormat,
+///     BadgeOptions,
+///     find_package,
+///     generate_badges,
+/// };
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let options = BadgeOptions {
+///     no_network: true,
+///     ..BadgeOptions::default()
+/// };
+/// let package = find_package(options.cargo).await?;
+/// for badge in generate_badges(&package, &options).await? {
+///     badge.render(&mut std::io::stdout(), BadgeFormat::Markdown)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Example: Bumping from Rust
+///
+/// [`commands::bump_version`] runs `cargo version-info bump` without argument
+/// parsing and reports the result as a [`commands::BumpOutcome`]:
+///
+/// ```no_run
+/// use cargo_version_info::commands::{
+///     VersionTarget,
+///     bump_version,
+/// };
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let outcome = bump_version("Cargo.toml".as_ref(), VersionTarget::Patch, true)?;
+/// if let Some(commit) = &outcome.commit {
+///     println!("Bumped {} -> {} in {}", outcome.old, outcome.new, commit);
+/// }
+/// # Ok(())
+/// # }
+/// ```
 pub mod commands;
+/// Documented process exit codes.
+pub mod exit_code;
 /// GitHub helpers.
 pub mod github;
-/// Version helpers.
+/// Version parsing and increment helpers (stable API).
 pub mod version;
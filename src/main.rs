@@ -12,19 +12,23 @@
 
 use std::fs;
 use std::path::PathBuf;
+use std::process::ExitCode;
 
 use anyhow::Result;
-use cargo_version_info::commands;
 use cargo_version_info::commands::{
     BadgeArgs,
+    BadgesArgs,
     BuildVersionArgs,
     BumpArgs,
+    CacheArgs,
     ChangedArgs,
     ChangelogArgs,
     CompareArgs,
     CurrentArgs,
+    DescribeArgs,
     DevArgs,
     DioxusArgs,
+    HistoryArgs,
     LatestArgs,
     NextArgs,
     PostBumpHookArgs,
@@ -32,9 +36,15 @@ use cargo_version_info::commands::{
     PreBumpHookArgs,
     ReleasePageArgs,
     RustToolchainArgs,
+    SchemaArgs,
+    SetEditionArgs,
     TagArgs,
     UpdateReadmeArgs,
 };
+use cargo_version_info::{
+    commands,
+    exit_code,
+};
 use clap::{
     ArgAction,
     CommandFactory,
@@ -78,6 +88,28 @@ struct VersionInfoCli {
     #[command(subcommand)]
     command: Option<VersionInfoCommand>,
 
+    /// How to report errors on stderr.
+    ///
+    /// - `human`: anyhow's error chain (default)
+    /// - `json`: a single `{"error": "...", "code": N}` object for scripting
+    #[arg(long, global = true, default_value = "human")]
+    error_format: String,
+
+    /// Run `cargo metadata` (and other cargo invocations) without network
+    /// access, for air-gapped builds.
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Like `--offline`, and also require `Cargo.lock` to be up to date.
+    #[arg(long, global = true)]
+    frozen: bool,
+
+    /// Timeout in seconds for network requests (crates.io/docs.rs badge
+    /// checks and GitHub API calls). Defaults to 5s for badge checks and 30s
+    /// for the GitHub API.
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+
     /// Capture trailing args after `--` (e.g., `--version`).
     #[arg(trailing_var_arg = true, hide = true)]
     passthrough: Vec<String>,
@@ -97,6 +129,9 @@ enum VersionInfoCommand {
     /// Generate dev version from git SHA
     #[command(name = "dev")]
     Dev(DevArgs),
+    /// Describe HEAD relative to the nearest tag (like `git describe --tags`)
+    #[command(name = "describe")]
+    Describe(DescribeArgs),
     /// Generate tag name (e.g., v0.0.1)
     #[command(name = "tag")]
     Tag(TagArgs),
@@ -115,9 +150,15 @@ enum VersionInfoCommand {
     /// Check if Cargo.toml version changed since last git tag
     #[command(name = "changed")]
     Changed(ChangedArgs),
+    /// List version changes from git history
+    #[command(name = "history")]
+    History(HistoryArgs),
     /// Bump version in Cargo.toml and commit changes (does not create tags)
     #[command(name = "bump")]
     Bump(BumpArgs),
+    /// Set the Rust edition in every workspace manifest and commit it
+    #[command(name = "set-edition")]
+    SetEdition(SetEditionArgs),
     /// Pre-bump hook for cog integration (verifies state before bumping)
     #[command(name = "pre-bump-hook")]
     PreBumpHook(PreBumpHookArgs),
@@ -136,6 +177,15 @@ enum VersionInfoCommand {
     /// Generate badges for quality metrics
     #[command(name = "badge")]
     Badge(BadgeArgs),
+    /// Generate badges by type (alias of `badge` with `--type`/`--output`)
+    #[command(name = "badges")]
+    Badges(BadgesArgs),
+    /// Show or clear cached badge and release lookups
+    #[command(name = "cache")]
+    Cache(CacheArgs),
+    /// Print the JSON Schema of `--format json` outputs
+    #[command(name = "schema")]
+    Schema(SchemaArgs),
     /// Update README with badges
     #[command(name = "update-readme")]
     UpdateReadme(UpdateReadmeArgs),
@@ -144,6 +194,44 @@ enum VersionInfoCommand {
     Version,
 }
 
+/// Format for errors reported by `main`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ErrorFormat {
+    Human,
+    Json,
+}
+
+impl std::str::FromStr for ErrorFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => anyhow::bail!("Invalid error format: {}", format),
+        }
+    }
+}
+
+/// Report a top-level error on stderr and return the process exit code.
+///
+/// See [`exit_code`] for the documented codes.
+fn report_error(error: &anyhow::Error, format: ErrorFormat) -> ExitCode {
+    let code = exit_code::ExitCode::from_error(error);
+    match format {
+        // Same output as returning the error from `main`
+        ErrorFormat::Human => eprintln!("Error: {:?}", error),
+        ErrorFormat::Json => eprintln!(
+            "{}",
+            serde_json::json!({
+                "error": format!("{:#}", error),
+                "code": code.code(),
+            })
+        ),
+    }
+    code.into()
+}
+
 /// Check if any .env* files exist in the current directory.
 fn has_env_files() -> bool {
     let current_dir = match std::env::current_dir() {
@@ -173,7 +261,7 @@ fn has_env_files() -> bool {
     false
 }
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     // Load environment variables from .env* files using dotenvage
     // This allows cargo-version-info to access encrypted secrets like GITHUB_TOKEN
     // stored in .env.local files protected by dotenvage
@@ -187,11 +275,34 @@ fn main() -> Result<()> {
 
     let args = CargoArgs::parse();
 
+    let error_format = match &args.subcmd {
+        Some(TopCommand::VersionInfo(cli)) => cli.error_format.parse(),
+        None => Ok(ErrorFormat::Human),
+    };
+    let error_format = match error_format {
+        Ok(format) => format,
+        Err(e) => return report_error(&e, ErrorFormat::Human),
+    };
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => report_error(&e, error_format),
+    }
+}
+
+/// Dispatch the parsed command line.
+fn run(args: CargoArgs) -> Result<()> {
     if args.tool_version_flag {
         return commands::build_version_for_repo(PathBuf::from(env!("CARGO_MANIFEST_DIR")));
     }
 
     if let Some(TopCommand::VersionInfo(cli)) = args.subcmd {
+        let timeout = cli.timeout.map(std::time::Duration::from_secs);
+        let cargo = commands::CargoOptions {
+            offline: cli.offline,
+            frozen: cli.frozen,
+        };
+
         if cli.version_flag {
             return commands::build_version_default();
         }
@@ -199,22 +310,48 @@ fn main() -> Result<()> {
         if let Some(command) = cli.command {
             return match command {
                 VersionInfoCommand::Next(args) => commands::next(args),
-                VersionInfoCommand::Current(args) => commands::current(args),
-                VersionInfoCommand::Latest(args) => commands::latest(args),
+                VersionInfoCommand::Current(mut args) => {
+                    args.cargo = cargo;
+                    commands::current(args)
+                }
+                VersionInfoCommand::Latest(mut args) => {
+                    args.timeout = timeout;
+                    commands::latest(args)
+                }
                 VersionInfoCommand::Dev(args) => commands::dev(args),
+                VersionInfoCommand::Describe(args) => commands::describe(args),
                 VersionInfoCommand::Tag(args) => commands::tag(args),
                 VersionInfoCommand::Compare(args) => commands::compare(args),
                 VersionInfoCommand::RustToolchain(args) => commands::rust_toolchain(args),
                 VersionInfoCommand::Dioxus(args) => commands::dioxus(args),
                 VersionInfoCommand::BuildVersion(args) => commands::build_version(args),
                 VersionInfoCommand::Changed(args) => commands::changed(args),
-                VersionInfoCommand::Bump(args) => commands::bump(args),
+                VersionInfoCommand::History(args) => commands::history(args),
+                VersionInfoCommand::Bump(args) => commands::bump(BumpArgs { cargo, ..args }),
+                VersionInfoCommand::SetEdition(args) => {
+                    commands::set_edition(SetEditionArgs { cargo, ..args })
+                }
                 VersionInfoCommand::PreBumpHook(args) => commands::pre_bump_hook(args),
                 VersionInfoCommand::PostBumpHook(args) => commands::post_bump_hook(args),
                 VersionInfoCommand::Changelog(args) => commands::changelog(args),
                 VersionInfoCommand::PrLog(args) => commands::pr_log(args),
-                VersionInfoCommand::ReleasePage(args) => commands::release_page(args),
-                VersionInfoCommand::Badge(args) => commands::badge(args),
+                VersionInfoCommand::ReleasePage(args) => commands::release_page(ReleasePageArgs {
+                    cargo,
+                    timeout,
+                    ..args
+                }),
+                VersionInfoCommand::Badge(args) => commands::badge(BadgeArgs {
+                    cargo,
+                    timeout,
+                    ..args
+                }),
+                VersionInfoCommand::Badges(args) => commands::badges(BadgesArgs {
+                    cargo,
+                    timeout,
+                    ..args
+                }),
+                VersionInfoCommand::Cache(args) => commands::cache(args),
+                VersionInfoCommand::Schema(args) => commands::schema(args),
                 VersionInfoCommand::UpdateReadme(args) => commands::update_readme(args),
                 VersionInfoCommand::Version => commands::build_version_default(),
             };
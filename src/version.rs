@@ -1,4 +1,34 @@
 //! Version parsing and manipulation utilities.
+//!
+//! These are the same helpers the `next`, `bump`, and `compare` commands use,
+//! and are part of the crate's stable public API so build scripts and release
+//! tooling can reuse the exact increment semantics.
+//!
+//! # Pre-release handling
+//!
+//! Versions are handled as a `(major, minor, patch)` triple. A pre-release
+//! suffix on the patch component (e.g. `1.2.3-beta.1`) is accepted by
+//! [`parse_version`] but discarded, so incrementing a pre-release yields the
+//! next plain release and [`compare_versions`] ignores pre-release
+//! identifiers. Use [`cmp_versions`] and [`version_step`] when pre-releases
+//! must be ordered according to semantic versioning precedence.
+//!
+//! ```
+//! use cargo_version_info::version::{
+//!     format_version,
+//!     increment_minor,
+//!     parse_version,
+//! };
+//!
+//! let (major, minor, patch) = parse_version("v1.2.3-beta.1").unwrap();
+//! let (major, minor, patch) = increment_minor(major, minor, patch);
+//! assert_eq!(format_version(major, minor, patch), "1.3.0");
+//! ```
+//!
+//! For the full build version string (including git SHA for dev builds), see
+//! [`crate::commands::compute_version_string`].
+
+use std::cmp::Ordering;
 
 use anyhow::{
     Context,
@@ -6,6 +36,18 @@ use anyhow::{
 };
 
 /// Parse a semantic version string (e.g., "0.1.2" or "v0.1.2").
+///
+/// A leading `v`/`V` is stripped and any pre-release suffix on the patch
+/// component is ignored.
+///
+/// ```
+/// use cargo_version_info::version::parse_version;
+///
+/// assert_eq!(parse_version("v0.1.2").unwrap(), (0, 1, 2));
+/// assert_eq!(parse_version("1.0.0-rc.1").unwrap(), (1, 0, 0));
+/// assert_eq!(parse_version("1.0.0+build.5").unwrap(), (1, 0, 0));
+/// assert!(parse_version("1.0").is_err());
+/// ```
 pub fn parse_version(version_str: &str) -> Result<(u32, u32, u32)> {
     // Strip optional v/V prefix
     let version_str = version_str.strip_prefix('v').unwrap_or(version_str);
@@ -26,7 +68,7 @@ pub fn parse_version(version_str: &str) -> Result<(u32, u32, u32)> {
         .parse::<u32>()
         .with_context(|| format!("Invalid minor version: {}", parts[1]))?;
     let patch = parts[2]
-        .split('-')
+        .split(['-', '+'])
         .next()
         .unwrap_or(parts[2])
         .parse::<u32>()
@@ -36,30 +78,120 @@ pub fn parse_version(version_str: &str) -> Result<(u32, u32, u32)> {
 }
 
 /// Increment patch version.
+///
+/// ```
+/// use cargo_version_info::version::increment_patch;
+///
+/// assert_eq!(increment_patch(0, 1, 2), (0, 1, 3));
+/// ```
 pub fn increment_patch(major: u32, minor: u32, patch: u32) -> (u32, u32, u32) {
     (major, minor, patch + 1)
 }
 
 /// Increment minor version (resets patch to 0).
+///
+/// ```
+/// use cargo_version_info::version::increment_minor;
+///
+/// assert_eq!(increment_minor(0, 1, 2), (0, 2, 0));
+/// ```
 pub fn increment_minor(major: u32, minor: u32, _patch: u32) -> (u32, u32, u32) {
     (major, minor + 1, 0)
 }
 
 /// Increment major version (resets minor and patch to 0).
+///
+/// ```
+/// use cargo_version_info::version::increment_major;
+///
+/// assert_eq!(increment_major(0, 1, 2), (1, 0, 0));
+/// ```
 pub fn increment_major(major: u32, _minor: u32, _patch: u32) -> (u32, u32, u32) {
     (major + 1, 0, 0)
 }
 
+/// Advance the pre-release counter of a version.
+///
+/// The last numeric pre-release identifier is incremented; if there is none,
+/// `.1` is appended. A version without a pre-release gets a patch bump.
+///
+/// ```
+/// use cargo_version_info::version::increment_prerelease;
+///
+/// assert_eq!(increment_prerelease("1.0.0-rc.1").unwrap(), "1.0.0-rc.2");
+/// assert_eq!(increment_prerelease("v1.0.0-beta").unwrap(), "1.0.0-beta.1");
+/// assert_eq!(increment_prerelease("1.0.0").unwrap(), "1.0.1");
+/// ```
+pub fn increment_prerelease(version_str: &str) -> Result<String> {
+    let (major, minor, patch) = parse_version(version_str)?;
+    let Some(pre) = prerelease(version_str) else {
+        let (major, minor, patch) = increment_patch(major, minor, patch);
+        return Ok(format_version(major, minor, patch));
+    };
+
+    let mut identifiers: Vec<String> = pre.split('.').map(str::to_string).collect();
+    match identifiers
+        .iter_mut()
+        .rev()
+        .find(|id| id.parse::<u64>().is_ok())
+    {
+        Some(counter) => {
+            let next = counter.parse::<u64>()? + 1;
+            *counter = next.to_string();
+        }
+        None => identifiers.push("1".to_string()),
+    }
+
+    Ok(format!(
+        "{}-{}",
+        format_version(major, minor, patch),
+        identifiers.join(".")
+    ))
+}
+
 /// Format version as string.
+///
+/// ```
+/// use cargo_version_info::version::format_version;
+///
+/// assert_eq!(format_version(1, 2, 3), "1.2.3");
+/// ```
 pub fn format_version(major: u32, minor: u32, patch: u32) -> String {
     format!("{}.{}.{}", major, minor, patch)
 }
 
 /// Format version as tag (with v prefix).
+///
+/// ```
+/// use cargo_version_info::version::format_tag;
+///
+/// assert_eq!(format_tag(1, 2, 3), "v1.2.3");
+/// ```
 pub fn format_tag(major: u32, minor: u32, patch: u32) -> String {
     format!("v{}.{}.{}", major, minor, patch)
 }
 
+/// Print a version with the given prefix (`--prefix`), or bare without one.
+///
+/// A leading `v`/`V` already on the version is dropped first, so the output
+/// carries exactly one prefix whether or not the source had one.
+///
+/// ```
+/// use cargo_version_info::version::apply_prefix;
+///
+/// assert_eq!(apply_prefix("1.2.3", None), "1.2.3");
+/// assert_eq!(apply_prefix("v1.2.3", None), "1.2.3");
+/// assert_eq!(apply_prefix("1.2.3", Some("v")), "v1.2.3");
+/// assert_eq!(apply_prefix("v1.2.3", Some("v")), "v1.2.3");
+/// ```
+pub fn apply_prefix(version: &str, prefix: Option<&str>) -> String {
+    let bare = version
+        .strip_prefix(['v', 'V'])
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(version);
+    format!("{}{}", prefix.unwrap_or(""), bare)
+}
+
 /// Compare two versions.
 ///
 /// Returns:
@@ -83,6 +215,181 @@ pub fn compare_versions(version1: &str, version2: &str) -> Result<Option<bool>>
     Ok(None)
 }
 
+/// The most significant component that differs between two versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionStep {
+    /// The major components differ.
+    Major,
+    /// The minor components differ.
+    Minor,
+    /// The patch components differ.
+    Patch,
+    /// Only the pre-release identifiers differ.
+    Prerelease,
+}
+
+impl VersionStep {
+    /// Lowercase name of the step (e.g. `"minor"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Major => "major",
+            Self::Minor => "minor",
+            Self::Patch => "patch",
+            Self::Prerelease => "prerelease",
+        }
+    }
+}
+
+/// Extract the pre-release identifiers of a version, if any.
+///
+/// Build metadata (`+...`) is ignored.
+///
+/// ```
+/// use cargo_version_info::version::prerelease;
+///
+/// assert_eq!(prerelease("1.2.3-rc.1+build.5"), Some("rc.1"));
+/// assert_eq!(prerelease("v1.2.3"), None);
+/// ```
+pub fn prerelease(version_str: &str) -> Option<&str> {
+    let version_str = version_str.split('+').next().unwrap_or(version_str);
+    version_str
+        .split_once('-')
+        .map(|(_, pre)| pre)
+        .filter(|pre| !pre.is_empty())
+}
+
+/// Write a version in canonical semver form, `x.y.z[-pre][+build]`.
+///
+/// A leading `v`/`V` and surrounding whitespace are dropped, and numeric
+/// components lose any leading zeros.
+///
+/// ```
+/// use cargo_version_info::version::canonical_version;
+///
+/// assert_eq!(canonical_version("v1.0.0").unwrap(), "1.0.0");
+/// assert_eq!(
+///     canonical_version(" 1.02.3-rc.1+build.5 ").unwrap(),
+///     "1.2.3-rc.1+build.5"
+/// );
+/// ```
+pub fn canonical_version(version_str: &str) -> Result<String> {
+    let version_str = version_str.trim();
+    let (version, build) = match version_str.split_once('+') {
+        Some((version, build)) => (version, Some(build).filter(|build| !build.is_empty())),
+        None => (version_str, None),
+    };
+
+    let (major, minor, patch) = parse_version(version)?;
+    let mut canonical = format_version(major, minor, patch);
+    if let Some(pre) = prerelease(version) {
+        canonical.push('-');
+        canonical.push_str(pre);
+    }
+    if let Some(build) = build {
+        canonical.push('+');
+        canonical.push_str(build);
+    }
+
+    Ok(canonical)
+}
+
+/// Compare two versions using semantic versioning precedence.
+///
+/// Unlike [`compare_versions`], pre-release identifiers are taken into
+/// account: a pre-release sorts before its release, and identifiers are
+/// compared numerically or lexically as the semver spec prescribes.
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// use cargo_version_info::version::cmp_versions;
+///
+/// assert_eq!(cmp_versions("1.2.0", "1.10.0").unwrap(), Ordering::Less);
+/// assert_eq!(cmp_versions("1.0.0-rc.1", "1.0.0").unwrap(), Ordering::Less);
+/// assert_eq!(
+///     cmp_versions("1.0.0-rc.10", "1.0.0-rc.2").unwrap(),
+///     Ordering::Greater
+/// );
+/// ```
+pub fn cmp_versions(version1: &str, version2: &str) -> Result<Ordering> {
+    let core1 = parse_version(version1)?;
+    let core2 = parse_version(version2)?;
+
+    Ok(core1
+        .cmp(&core2)
+        .then_with(|| cmp_prerelease(prerelease(version1), prerelease(version2))))
+}
+
+/// Determine the most significant component that differs between versions.
+///
+/// Returns `None` if the versions have equal precedence.
+///
+/// ```
+/// use cargo_version_info::version::{
+///     VersionStep,
+///     version_step,
+/// };
+///
+/// assert_eq!(
+///     version_step("1.2.0", "1.10.0").unwrap(),
+///     Some(VersionStep::Minor)
+/// );
+/// assert_eq!(
+///     version_step("1.0.0-rc.1", "1.0.0").unwrap(),
+///     Some(VersionStep::Prerelease)
+/// );
+/// assert_eq!(version_step("1.0.0", "v1.0.0").unwrap(), None);
+/// ```
+pub fn version_step(version1: &str, version2: &str) -> Result<Option<VersionStep>> {
+    let (major1, minor1, patch1) = parse_version(version1)?;
+    let (major2, minor2, patch2) = parse_version(version2)?;
+
+    let step = if major1 != major2 {
+        Some(VersionStep::Major)
+    } else if minor1 != minor2 {
+        Some(VersionStep::Minor)
+    } else if patch1 != patch2 {
+        Some(VersionStep::Patch)
+    } else if cmp_prerelease(prerelease(version1), prerelease(version2)) != Ordering::Equal {
+        Some(VersionStep::Prerelease)
+    } else {
+        None
+    };
+
+    Ok(step)
+}
+
+/// Compare pre-release identifiers by semver precedence.
+fn cmp_prerelease(pre1: Option<&str>, pre2: Option<&str>) -> Ordering {
+    match (pre1, pre2) {
+        (None, None) => Ordering::Equal,
+        // A release has higher precedence than any of its pre-releases
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(pre1), Some(pre2)) => {
+            let mut ids1 = pre1.split('.');
+            let mut ids2 = pre2.split('.');
+            loop {
+                let ordering = match (ids1.next(), ids2.next()) {
+                    (None, None) => return Ordering::Equal,
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (Some(id1), Some(id2)) => match (id1.parse::<u64>(), id2.parse::<u64>()) {
+                        (Ok(n1), Ok(n2)) => n1.cmp(&n2),
+                        // Numeric identifiers sort before alphanumeric ones
+                        (Ok(_), Err(_)) => Ordering::Less,
+                        (Err(_), Ok(_)) => Ordering::Greater,
+                        (Err(_), Err(_)) => id1.cmp(id2),
+                    },
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,6 +402,13 @@ mod tests {
         assert_eq!(parse_version("10.20.30").unwrap(), (10, 20, 30));
     }
 
+    #[test]
+    fn test_parse_version_prerelease() {
+        assert_eq!(parse_version("1.2.3-beta.1").unwrap(), (1, 2, 3));
+        let (major, minor, patch) = parse_version("1.2.3-rc.1").unwrap();
+        assert_eq!(increment_patch(major, minor, patch), (1, 2, 4));
+    }
+
     #[test]
     fn test_increment_patch() {
         assert_eq!(increment_patch(0, 1, 2), (0, 1, 3));
@@ -131,4 +445,68 @@ mod tests {
         assert_eq!(compare_versions("0.1.2", "0.1.2").unwrap(), None);
         assert_eq!(compare_versions("1.0.0", "0.9.9").unwrap(), Some(true));
     }
+
+    #[test]
+    fn test_cmp_versions_prerelease_precedence() {
+        // Ordering from the semver specification
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(
+                cmp_versions(pair[0], pair[1]).unwrap(),
+                Ordering::Less,
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+        assert_eq!(
+            cmp_versions("1.0.0+build", "1.0.0").unwrap(),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_version_step() {
+        assert_eq!(
+            version_step("1.2.3", "2.0.0").unwrap(),
+            Some(VersionStep::Major)
+        );
+        assert_eq!(
+            version_step("1.2.3", "1.3.0").unwrap(),
+            Some(VersionStep::Minor)
+        );
+        assert_eq!(
+            version_step("1.2.3", "1.2.4").unwrap(),
+            Some(VersionStep::Patch)
+        );
+        assert_eq!(
+            version_step("1.2.3-rc.1", "1.2.3-rc.2").unwrap(),
+            Some(VersionStep::Prerelease)
+        );
+        assert_eq!(version_step("1.2.3", "1.2.3").unwrap(), None);
+    }
+
+    #[test]
+    fn test_increment_prerelease() {
+        assert_eq!(increment_prerelease("1.0.0-rc.1").unwrap(), "1.0.0-rc.2");
+        assert_eq!(
+            increment_prerelease("1.0.0-alpha.9.x").unwrap(),
+            "1.0.0-alpha.10.x"
+        );
+        assert_eq!(increment_prerelease("1.0.0-beta").unwrap(), "1.0.0-beta.1");
+        assert_eq!(
+            increment_prerelease("1.0.0-rc.1+build.7").unwrap(),
+            "1.0.0-rc.2"
+        );
+        assert_eq!(increment_prerelease("1.2.3").unwrap(), "1.2.4");
+    }
 }